@@ -1,13 +1,40 @@
 use std::collections::HashMap;
 
 use crate::internal::{
-    interfaces::pubsub_repository::PubSubRepositoryInterface,
-    models::view_models::responses::redis_stream_message::RedisStreamMessage,
+    interfaces::{
+        pubsub_repository::PubSubRepositoryInterface,
+        upload_progress_repository::UploadProgressRepositoryInterface,
+    },
+    metrics::reconciliation_metrics::{
+        time_dapr_publish, time_redis_read, CLAIM_CHECK_READ, COMPARISON_FILE_CHUNK_READ,
+        FX_RATE_TABLE_READ, PRIMARY_FILE_CHUNKS_QUEUE, RECON_RESULTS_QUEUE,
+        UPLOAD_RECONCILIATION_COMPLETED_QUEUE, VALUE_MAPPING_TABLE_READ,
+    },
+    models::{
+        entities::{
+            chunk_content_type::ChunkContentType,
+            chunk_payload_limits::ChunkPayloadLimits,
+            file_chunk_claim_check::{FileChunkClaimCheck, FILE_CHUNK_CLAIM_CHECK_CONTENT_TYPE},
+            upload_progress::UploadProgress,
+        },
+        view_models::{
+            requests::reconcile_file_chunk_request::ReconcileFileChunkRequest,
+            responses::{
+                reconciled_file_chunk::ReconciledFileChunk,
+                redis_stream_message::RedisStreamMessage,
+                upload_reconciliation_completed_event::UploadReconciliationCompletedEvent,
+            },
+        },
+    },
+    services::core_logic::chunk_serialization::{
+        deserialize_chunk_payload, serialize_chunk_payload,
+    },
     shared_reconciler_rust_libraries::models::entities::{
         app_errors::{AppError, AppErrorKind},
         file_chunk_queue::FileChunkQueue,
         file_upload_chunk::FileUploadChunk,
     },
+    telemetry::trace_context::{add_trace_context_link, get_cloud_event_trace_context_metadata},
 };
 use async_trait::async_trait;
 use dapr::{dapr::dapr::proto::runtime::v1::dapr_client::DaprClient, Client};
@@ -16,7 +43,9 @@ use redis::{
     Commands, Value,
 };
 //use std::collections::HashMap;
+use std::time::Duration;
 use tonic::transport::Channel as TonicChannel;
+use tracing::Span;
 
 pub struct DaprPubSubRepositoryManager {
     //the dapr server ip
@@ -28,8 +57,34 @@ pub struct DaprPubSubRepositoryManager {
     //the dapr pub sub topic
     pub dapr_pubsub_topic: String,
 
+    //the topic an event is published to once every primary file chunk of an upload is reconciled
+    pub dapr_upload_reconciliation_completed_topic: String,
+
     //the redis url
     pub redis_url: String,
+
+    //the content type primary file chunks are requeued with
+    pub primary_file_chunk_content_type: ChunkContentType,
+
+    //requeued and reconciled chunks larger than this are kept in redis
+    //and a claim check is published instead
+    pub max_published_file_chunk_bytes: usize,
+
+    //how long the body of a claim checked chunk is kept in redis
+    pub file_chunk_claim_check_ttl_seconds: usize,
+
+    //how long the progress of an upload request is kept in redis after it was last updated
+    pub upload_progress_ttl_seconds: usize,
+
+    //how long a claim on publishing the completion event of an upload request
+    //is kept before another chunk of the upload may publish it
+    pub upload_completion_claim_ttl_seconds: usize,
+
+    //the sequence number the file splitter gives the first chunk of both files
+    pub first_chunk_sequence_number: i64,
+
+    //how large a comparison file chunk may get once decompressed
+    pub chunk_payload_limits: ChunkPayloadLimits,
 }
 
 #[async_trait]
@@ -38,12 +93,16 @@ impl PubSubRepositoryInterface for DaprPubSubRepositoryManager {
         &self,
         queue: &FileChunkQueue,
     ) -> Result<FileUploadChunk, AppError> {
-        let mut redis_connection = self.get_redis_connection().await?;
+        //connecting to redis is timed as part of the read
+        return time_redis_read(COMPARISON_FILE_CHUNK_READ, async {
+            let mut redis_connection = self.get_redis_connection().await?;
 
-        let read_reply: StreamReadReply =
-            self.read_from_stream(&mut redis_connection, &queue).await?;
+            let read_reply: StreamReadReply =
+                self.read_from_stream(&mut redis_connection, &queue).await?;
 
-        return self.deserialize_stream_reply(&read_reply).await;
+            return self.deserialize_stream_reply(&read_reply).await;
+        })
+        .await;
     }
 
     async fn mark_comparison_file_chunk_as_processed(
@@ -60,53 +119,424 @@ impl PubSubRepositoryInterface for DaprPubSubRepositoryManager {
 
     async fn insert_file_chunk_in_primary_file_queue(
         &self,
-        file_chunk: &FileUploadChunk,
+        reconcile_file_chunk_request: &ReconcileFileChunkRequest,
     ) -> Result<bool, AppError> {
         //create a dapr client
         let mut client = self.get_dapr_connection().await?;
 
         //call the binding
+        //we publish the whole request so that the reconciliation options
+        //are still attached when the chunk comes back to us
         let pubsub_name = self.dapr_pubsub_name.clone();
-        let pubsub_topic = file_chunk.primary_file_chunks_queue.topic_id.clone();
-        let data_content_type = "json".to_string();
-        let data = serde_json::to_vec(&file_chunk).unwrap();
-        let metadata = None::<HashMap<String, String>>;
-        let binding_response = client
-            .publish_event(pubsub_name, pubsub_topic, data_content_type, data, metadata)
-            .await;
+        let pubsub_topic = reconcile_file_chunk_request
+            .primary_file_chunk
+            .primary_file_chunks_queue
+            .topic_id
+            .clone();
+        //the chunk is requeued after every comparison file chunk, so it can be sent
+        //in a compact encoding since it is only ever read back by this service
+        let data_content_type = self
+            .primary_file_chunk_content_type
+            .to_content_type_header();
+        let data = serialize_chunk_payload(
+            &reconcile_file_chunk_request,
+            &self.primary_file_chunk_content_type,
+        )?;
+
+        //reasons pile up on the rows with every comparison file chunk, so the chunk
+        //can outgrow the pubsub message size
+        let (data_content_type, data) = self
+            .claim_check_if_too_large(
+                &reconcile_file_chunk_request.primary_file_chunk,
+                data_content_type,
+                data,
+            )
+            .await?;
+        //the cloud event carries on the trace this chunk is being reconciled in
+        let metadata = Some(get_cloud_event_trace_context_metadata(&Span::current()));
+        let binding_response = time_dapr_publish(PRIMARY_FILE_CHUNKS_QUEUE, async {
+            return client
+                .publish_event(pubsub_name, pubsub_topic, data_content_type, data, metadata)
+                .await
+                .map_err(|e| AppError::new(AppErrorKind::NotFound, e.to_string()));
+        })
+        .await;
 
         //handle the bindings response
         match binding_response {
             //success
             Ok(_) => return Ok(true),
             //failure
-            Err(e) => return Err(AppError::new(AppErrorKind::NotFound, e.to_string())),
+            Err(e) => return Err(e),
         }
     }
 
     async fn insert_file_chunk_into_recon_results_queue(
         &self,
-        file_chunk: &FileUploadChunk,
+        reconciled_file_chunk: &ReconciledFileChunk,
     ) -> Result<bool, AppError> {
         //create a dapr client
         let mut client = self.get_dapr_connection().await?;
 
         //call the binding
         let pubsub_name = self.dapr_pubsub_name.clone();
-        let pubsub_topic = file_chunk.result_chunks_queue.topic_id.clone();
+        let pubsub_topic = reconciled_file_chunk
+            .file_chunk
+            .result_chunks_queue
+            .topic_id
+            .clone();
         let data_content_type = "json".to_string();
-        let data = serde_json::to_vec(&file_chunk).unwrap();
-        let metadata = None::<HashMap<String, String>>;
-        let binding_response = client
-            .publish_event(pubsub_name, pubsub_topic, data_content_type, data, metadata)
-            .await;
+        let data = serde_json::to_vec(&reconciled_file_chunk)
+            .map_err(|e| AppError::new(AppErrorKind::InternalError, e.to_string()))?;
+        //the last reconciled chunk carries the details of every row, so it is the
+        //largest payload we publish and gets the same claim check as requeued chunks
+        let (data_content_type, data) = self
+            .claim_check_if_too_large(&reconciled_file_chunk.file_chunk, data_content_type, data)
+            .await?;
+        let metadata = Some(get_cloud_event_trace_context_metadata(&Span::current()));
+        let binding_response = time_dapr_publish(RECON_RESULTS_QUEUE, async {
+            return client
+                .publish_event(pubsub_name, pubsub_topic, data_content_type, data, metadata)
+                .await
+                .map_err(|e| AppError::new(AppErrorKind::NotFound, e.to_string()));
+        })
+        .await;
 
         //handle the bindings response
         match binding_response {
             //success
             Ok(_) => return Ok(true),
             //failure
-            Err(e) => return Err(AppError::new(AppErrorKind::NotFound, e.to_string())),
+            Err(e) => return Err(e),
+        }
+    }
+
+    async fn publish_upload_reconciliation_completed_event(
+        &self,
+        upload_reconciliation_completed_event: &UploadReconciliationCompletedEvent,
+    ) -> Result<bool, AppError> {
+        //create a dapr client
+        let mut client = self.get_dapr_connection().await?;
+
+        //call the binding
+        let pubsub_name = self.dapr_pubsub_name.clone();
+        let pubsub_topic = self.dapr_upload_reconciliation_completed_topic.clone();
+        let data_content_type = "json".to_string();
+        let data = serde_json::to_vec(&upload_reconciliation_completed_event)
+            .map_err(|e| AppError::new(AppErrorKind::InternalError, e.to_string()))?;
+        let metadata = Some(get_cloud_event_trace_context_metadata(&Span::current()));
+        let binding_response = time_dapr_publish(UPLOAD_RECONCILIATION_COMPLETED_QUEUE, async {
+            return client
+                .publish_event(pubsub_name, pubsub_topic, data_content_type, data, metadata)
+                .await
+                .map_err(|e| AppError::new(AppErrorKind::NotFound, e.to_string()));
+        })
+        .await;
+
+        //handle the bindings response
+        match binding_response {
+            //success
+            Ok(_) => return Ok(true),
+            //failure
+            Err(e) => return Err(e),
+        }
+    }
+
+    async fn get_value_mapping_table_entries(
+        &self,
+        redis_hash_key: &String,
+    ) -> Result<HashMap<String, String>, AppError> {
+        return time_redis_read(VALUE_MAPPING_TABLE_READ, async {
+            let mut redis_connection = self.get_redis_connection().await?;
+
+            //every field in the hash is a comparison file value
+            //and its value is the primary file value it maps to
+            let read_result: Result<HashMap<String, String>, redis::RedisError> =
+                redis_connection.hgetall(redis_hash_key);
+
+            //handle the read_result
+            match read_result {
+                Ok(entries) => return Ok(entries),
+                Err(e) => {
+                    return Err(AppError::new(
+                        AppErrorKind::ResponseUnmarshalError,
+                        e.to_string(),
+                    ))
+                }
+            }
+        })
+        .await;
+    }
+
+    async fn get_fx_rate_table_rates(
+        &self,
+        redis_hash_key: &String,
+    ) -> Result<HashMap<String, f64>, AppError> {
+        return time_redis_read(FX_RATE_TABLE_READ, async {
+            let mut redis_connection = self.get_redis_connection().await?;
+
+            //every field in the hash is a currency code
+            //and its value is the rate into the base currency
+            let read_result: Result<HashMap<String, f64>, redis::RedisError> =
+                redis_connection.hgetall(redis_hash_key);
+
+            //handle the read_result
+            match read_result {
+                Ok(rates) => return Ok(rates),
+                Err(e) => {
+                    return Err(AppError::new(
+                        AppErrorKind::ResponseUnmarshalError,
+                        e.to_string(),
+                    ))
+                }
+            }
+        })
+        .await;
+    }
+
+    async fn get_file_chunk_claim_check_body(
+        &self,
+        claim_check: &FileChunkClaimCheck,
+    ) -> Result<Vec<u8>, AppError> {
+        return time_redis_read(CLAIM_CHECK_READ, async {
+            let mut redis_connection = self.get_redis_connection().await?;
+
+            let read_result: Result<Option<Vec<u8>>, redis::RedisError> =
+                redis_connection.get(&claim_check.claim_check_key);
+
+            //handle the read_result
+            match read_result {
+                Ok(Some(claim_check_body)) => return Ok(claim_check_body),
+                Ok(None) => {
+                    return Err(AppError::new(
+                        AppErrorKind::NotFound,
+                        format!(
+                            "the body of file chunk [{}] under claim check [{}] \
+                             has expired or was already reconciled",
+                            claim_check.primary_file_chunk_id, claim_check.claim_check_key
+                        ),
+                    ))
+                }
+                Err(e) => {
+                    return Err(AppError::new(
+                        AppErrorKind::ResponseUnmarshalError,
+                        e.to_string(),
+                    ))
+                }
+            }
+        })
+        .await;
+    }
+
+    async fn delete_file_chunk_claim_check_body(
+        &self,
+        claim_check: &FileChunkClaimCheck,
+    ) -> Result<bool, AppError> {
+        let mut redis_connection = self.get_redis_connection().await?;
+
+        let delete_result: Result<usize, redis::RedisError> =
+            redis_connection.del(&claim_check.claim_check_key);
+
+        //handle the delete_result
+        match delete_result {
+            Ok(deleted_keys) => return Ok(deleted_keys > 0),
+            Err(e) => return Err(AppError::new(AppErrorKind::InternalError, e.to_string())),
+        }
+    }
+
+    async fn ping_redis(&self, timeout: Duration) -> Result<String, AppError> {
+        let client = self.open_redis_connection().await?;
+
+        //the redis connection blocks, so the ping runs on the blocking pool
+        //and the connection is given the timeout, to keep an unreachable redis
+        //from holding up the worker thread serving the health check
+        let ping_task = tokio::task::spawn_blocking(move || {
+            let mut redis_connection = client.get_connection_with_timeout(timeout)?;
+            redis_connection.set_read_timeout(Some(timeout))?;
+            redis_connection.set_write_timeout(Some(timeout))?;
+            return redis::cmd("PING").query::<String>(&mut redis_connection);
+        });
+
+        //handle the ping_result
+        match ping_task.await {
+            Ok(Ok(reply)) => return Ok(reply),
+            Ok(Err(e)) => return Err(AppError::new(AppErrorKind::ConnectionError, e.to_string())),
+            Err(e) => return Err(AppError::new(AppErrorKind::InternalError, e.to_string())),
+        }
+    }
+
+    async fn is_dapr_pubsub_component_loaded(&self) -> Result<bool, AppError> {
+        let mut client = self.get_dapr_connection().await?;
+
+        let metadata_response = client.get_metadata().await;
+
+        //handle the metadata_response
+        match metadata_response {
+            Ok(metadata) => {
+                return Ok(metadata
+                    .registered_components
+                    .iter()
+                    .any(|component| component.name == self.dapr_pubsub_name))
+            }
+            Err(e) => return Err(AppError::new(AppErrorKind::ConnectionError, e.to_string())),
+        }
+    }
+}
+
+#[async_trait]
+impl UploadProgressRepositoryInterface for DaprPubSubRepositoryManager {
+    async fn record_primary_file_chunk_progress(
+        &self,
+        upload_request_id: &String,
+        primary_chunk_sequence_number: i64,
+        comparison_chunk_sequence_number: i64,
+        last_primary_chunk_sequence_number: Option<i64>,
+    ) -> Result<(), AppError> {
+        //the chunk is set to its latest comparison file chunk rather than counted up,
+        //so a redelivered chunk is not counted twice
+        return self
+            .write_upload_progress_field(
+                format!("upload-progress:{}:primary-file-chunks", upload_request_id),
+                upload_request_id,
+                primary_chunk_sequence_number,
+                comparison_chunk_sequence_number.to_string(),
+                last_primary_chunk_sequence_number,
+            )
+            .await;
+    }
+
+    async fn record_completed_primary_file_chunk(
+        &self,
+        upload_request_id: &String,
+        primary_chunk_sequence_number: i64,
+        last_primary_chunk_sequence_number: Option<i64>,
+        rows_by_recon_status: &HashMap<String, u64>,
+    ) -> Result<UploadProgress, AppError> {
+        let rows_by_recon_status = serde_json::to_string(rows_by_recon_status)
+            .map_err(|e| AppError::new(AppErrorKind::InternalError, e.to_string()))?;
+
+        self.write_upload_progress_field(
+            format!(
+                "upload-progress:{}:completed-primary-file-chunks",
+                upload_request_id
+            ),
+            upload_request_id,
+            primary_chunk_sequence_number,
+            rows_by_recon_status,
+            last_primary_chunk_sequence_number,
+        )
+        .await?;
+
+        //the progress is read after the write, so of two chunks completing at the same time
+        //at least the one written last sees the other
+        return self.get_upload_progress(upload_request_id).await;
+    }
+
+    async fn get_upload_progress(
+        &self,
+        upload_request_id: &String,
+    ) -> Result<UploadProgress, AppError> {
+        let mut redis_connection = self.get_redis_connection().await?;
+
+        let read_result: Result<
+            (HashMap<i64, i64>, HashMap<i64, String>, Option<i64>),
+            redis::RedisError,
+        > = redis::pipe()
+            .hgetall(format!(
+                "upload-progress:{}:primary-file-chunks",
+                upload_request_id
+            ))
+            .hgetall(format!(
+                "upload-progress:{}:completed-primary-file-chunks",
+                upload_request_id
+            ))
+            .get(format!(
+                "upload-progress:{}:last-primary-file-chunk",
+                upload_request_id
+            ))
+            .query(&mut redis_connection);
+
+        let (
+            primary_file_chunks,
+            completed_primary_file_chunks,
+            last_primary_chunk_sequence_number,
+        ) = match read_result {
+            Ok(progress) => progress,
+            Err(e) => {
+                return Err(AppError::new(
+                    AppErrorKind::ResponseUnmarshalError,
+                    e.to_string(),
+                ))
+            }
+        };
+
+        let mut upload_progress = UploadProgress {
+            primary_file_chunks,
+            completed_primary_file_chunks: HashMap::new(),
+            last_primary_chunk_sequence_number,
+            first_chunk_sequence_number: self.first_chunk_sequence_number,
+        };
+
+        for (primary_chunk_sequence_number, rows_by_recon_status) in completed_primary_file_chunks {
+            let rows_by_recon_status: HashMap<String, u64> =
+                serde_json::from_str(&rows_by_recon_status).map_err(|e| {
+                    AppError::new(AppErrorKind::ResponseUnmarshalError, e.to_string())
+                })?;
+
+            upload_progress
+                .completed_primary_file_chunks
+                .insert(primary_chunk_sequence_number, rows_by_recon_status);
+        }
+
+        return Ok(upload_progress);
+    }
+
+    async fn claim_upload_completion(&self, upload_request_id: &String) -> Result<bool, AppError> {
+        let mut redis_connection = self.get_redis_connection().await?;
+
+        //only the first to set the key gets to publish the completion event
+        let claim_result: Result<Option<String>, redis::RedisError> = redis::cmd("SET")
+            .arg(format!(
+                "upload-progress:{}:completion-claimed",
+                upload_request_id
+            ))
+            .arg(1)
+            .arg("NX")
+            .arg("EX")
+            .arg(self.upload_completion_claim_ttl_seconds)
+            .query(&mut redis_connection);
+
+        match claim_result {
+            Ok(reply) => return Ok(reply.is_some()),
+            Err(e) => return Err(AppError::new(AppErrorKind::InternalError, e.to_string())),
+        }
+    }
+
+    async fn confirm_upload_completion(&self, upload_request_id: &String) -> Result<(), AppError> {
+        let mut redis_connection = self.get_redis_connection().await?;
+
+        let expire_result: Result<bool, redis::RedisError> = redis_connection.expire(
+            format!("upload-progress:{}:completion-claimed", upload_request_id),
+            self.upload_progress_ttl_seconds,
+        );
+
+        match expire_result {
+            Ok(_) => return Ok(()),
+            Err(e) => return Err(AppError::new(AppErrorKind::InternalError, e.to_string())),
+        }
+    }
+
+    async fn release_upload_completion(&self, upload_request_id: &String) -> Result<(), AppError> {
+        let mut redis_connection = self.get_redis_connection().await?;
+
+        let delete_result: Result<usize, redis::RedisError> = redis_connection.del(format!(
+            "upload-progress:{}:completion-claimed",
+            upload_request_id
+        ));
+
+        match delete_result {
+            Ok(_) => return Ok(()),
+            Err(e) => return Err(AppError::new(AppErrorKind::InternalError, e.to_string())),
         }
     }
 }
@@ -146,7 +576,7 @@ impl DaprPubSubRepositoryManager {
 
     async fn open_redis_connection(&self) -> Result<redis::Client, AppError> {
         // Create the client
-        let open_result = redis::Client::open("redis://127.0.0.1:6379/");
+        let open_result = redis::Client::open(self.redis_url.as_str());
 
         //handle the connection result
         match open_result {
@@ -157,6 +587,103 @@ impl DaprPubSubRepositoryManager {
         }
     }
 
+    //sets a field of one of the hashes the progress of an upload request is kept in,
+    //along with the sequence number of the last primary file chunk once it is known.
+    //every write pushes back when the progress expires
+    async fn write_upload_progress_field(
+        &self,
+        redis_hash_key: String,
+        upload_request_id: &String,
+        primary_chunk_sequence_number: i64,
+        value: String,
+        last_primary_chunk_sequence_number: Option<i64>,
+    ) -> Result<(), AppError> {
+        let mut redis_connection = self.get_redis_connection().await?;
+
+        let mut pipeline = redis::pipe();
+        pipeline
+            .atomic()
+            .hset(&redis_hash_key, primary_chunk_sequence_number, value)
+            .ignore()
+            .expire(&redis_hash_key, self.upload_progress_ttl_seconds)
+            .ignore();
+
+        if let Some(last_primary_chunk_sequence_number) = last_primary_chunk_sequence_number {
+            pipeline
+                .set_ex(
+                    format!(
+                        "upload-progress:{}:last-primary-file-chunk",
+                        upload_request_id
+                    ),
+                    last_primary_chunk_sequence_number,
+                    self.upload_progress_ttl_seconds,
+                )
+                .ignore();
+        }
+
+        let write_result: Result<(), redis::RedisError> = pipeline.query(&mut redis_connection);
+
+        match write_result {
+            Ok(_) => return Ok(()),
+            Err(e) => return Err(AppError::new(AppErrorKind::InternalError, e.to_string())),
+        }
+    }
+
+    //a payload that outgrows the pubsub message size is kept in redis
+    //and the claim check is published in its place
+    async fn claim_check_if_too_large(
+        &self,
+        primary_file_chunk: &FileUploadChunk,
+        data_content_type: String,
+        data: Vec<u8>,
+    ) -> Result<(String, Vec<u8>), AppError> {
+        if data.len() <= self.max_published_file_chunk_bytes {
+            return Ok((data_content_type, data));
+        }
+
+        return self
+            .store_file_chunk_claim_check_body(primary_file_chunk, data_content_type, data)
+            .await;
+    }
+
+    //keeps the encoded payload in redis and returns the claim check to publish in its place
+    async fn store_file_chunk_claim_check_body(
+        &self,
+        primary_file_chunk: &FileUploadChunk,
+        data_content_type: String,
+        data: Vec<u8>,
+    ) -> Result<(String, Vec<u8>), AppError> {
+        let claim_check = FileChunkClaimCheck {
+            claim_check_key: format!(
+                "file-chunk-claim-checks:{}:{}",
+                primary_file_chunk.upload_request_id,
+                uuid::Uuid::new_v4()
+            ),
+            content_type: data_content_type,
+            primary_file_chunk_id: primary_file_chunk.id.clone(),
+        };
+
+        let mut redis_connection = self.get_redis_connection().await?;
+
+        let write_result: Result<(), redis::RedisError> = redis_connection.set_ex(
+            &claim_check.claim_check_key,
+            data,
+            self.file_chunk_claim_check_ttl_seconds,
+        );
+
+        if let Err(e) = write_result {
+            return Err(AppError::new(AppErrorKind::InternalError, e.to_string()));
+        }
+
+        let claim_check_data = serde_json::to_vec(&claim_check)
+            .map_err(|e| AppError::new(AppErrorKind::InternalError, e.to_string()))?;
+
+        return Ok((
+            FILE_CHUNK_CLAIM_CHECK_CONTENT_TYPE.to_string(),
+            claim_check_data,
+        ));
+    }
+
     async fn read_from_stream(
         &self,
         redis_connection: &mut redis::Connection,
@@ -203,10 +730,19 @@ impl DaprPubSubRepositoryManager {
 
                         //handle deserialization results
                         match deserialize_result {
-                            Ok(mut deserialized_message) => {
+                            Ok(deserialized_message) => {
+                                //the read is linked to the trace the comparison file chunk was published in
+                                add_trace_context_link(
+                                    &Span::current(),
+                                    &deserialized_message.traceparent,
+                                    &deserialized_message.tracestate,
+                                );
+
                                 //if successfull, attach the id of the deserialized message to the file_chunk
-                                deserialized_message.data.id = id.clone();
-                                return Ok(deserialized_message.data);
+                                let mut file_chunk =
+                                    self.get_stream_message_file_chunk(deserialized_message)?;
+                                file_chunk.id = id.clone();
+                                return Ok(file_chunk);
                             }
                             Err(e) => {
                                 //transform error to app error
@@ -232,4 +768,37 @@ impl DaprPubSubRepositoryManager {
             String::from("No Data returned"),
         ));
     }
+
+    //chunks published as json are embedded in the message as they are,
+    //while chunks published in a binary encoding are embedded in base64
+    fn get_stream_message_file_chunk(
+        &self,
+        stream_message: RedisStreamMessage,
+    ) -> Result<FileUploadChunk, AppError> {
+        if let Some(file_chunk) = stream_message.data {
+            return Ok(file_chunk);
+        }
+
+        let data_base64 = match stream_message.data_base64 {
+            Some(data_base64) => data_base64,
+            None => {
+                return Err(AppError::new(
+                    AppErrorKind::ResponseUnmarshalError,
+                    String::from("No Data returned"),
+                ))
+            }
+        };
+
+        let data = base64::decode(data_base64)
+            .map_err(|e| AppError::new(AppErrorKind::ResponseUnmarshalError, e.to_string()))?;
+
+        let chunk_content_type = ChunkContentType::parse(&stream_message.datacontenttype)
+            .map_err(|e| AppError::new(AppErrorKind::ResponseUnmarshalError, e.message))?;
+
+        return deserialize_chunk_payload(
+            &data,
+            &chunk_content_type,
+            self.chunk_payload_limits.max_decompressed_bytes,
+        );
+    }
 }
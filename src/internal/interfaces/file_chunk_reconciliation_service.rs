@@ -1,7 +1,10 @@
 use crate::internal::{
-    models::view_models::{
-        requests::reconcile_file_chunk_request::ReconcileFileChunkRequest,
-        responses::reconcile_file_chunk_response::ReconcileFileChunkResponse,
+    models::{
+        entities::file_chunk_claim_check::FileChunkClaimCheck,
+        view_models::{
+            requests::reconcile_file_chunk_request::ReconcileFileChunkRequest,
+            responses::reconcile_file_chunk_response::ReconcileFileChunkResponse,
+        },
     },
     shared_reconciler_rust_libraries::models::entities::app_errors::AppError,
 };
@@ -13,6 +16,12 @@ use mockall::automock;
 pub trait FileChunkReconciliationServiceInterface: Send + Sync {
     async fn reconcile_file_chunk(
         &self,
-        primary_file_chunk: &ReconcileFileChunkRequest,
+        primary_file_chunk: ReconcileFileChunkRequest,
+    ) -> Result<ReconcileFileChunkResponse, AppError>;
+
+    //loads the request a claim check stands for and reconciles it
+    async fn reconcile_claim_checked_file_chunk(
+        &self,
+        claim_check: FileChunkClaimCheck,
     ) -> Result<ReconcileFileChunkResponse, AppError>;
 }
@@ -1,16 +1,27 @@
 use async_trait::async_trait;
 use mockall::automock;
+use std::sync::Arc;
 
-use crate::internal::shared_reconciler_rust_libraries::models::entities::{
-    app_errors::AppError, file_upload_chunk::FileUploadChunk,
+use crate::internal::{
+    models::entities::{
+        reconciliation_options::ReconciliationOptions,
+        row_reconciliation_details::RowReconciliationDetails,
+    },
+    shared_reconciler_rust_libraries::models::entities::{
+        app_errors::AppError, file_upload_chunk::FileUploadChunk,
+    },
 };
 
 #[automock]
 #[async_trait]
 pub trait FileReconciliationAlgorithmInterface: Send + Sync {
+    //reconciles the rows of the primary file chunk in place. the comparison file chunk
+    //is shared rather than copied, so it can be read from other threads
     async fn reconcile_primary_file_chunk(
         &self,
         primary_file_chunk: &mut FileUploadChunk,
-        comparison_file_chunk: &FileUploadChunk,
-    ) -> Result<FileUploadChunk, AppError>;
+        comparison_file_chunk: &Arc<FileUploadChunk>,
+        reconciliation_options: &ReconciliationOptions,
+        rows_reconciliation_details: &mut Vec<RowReconciliationDetails>,
+    ) -> Result<(), AppError>;
 }
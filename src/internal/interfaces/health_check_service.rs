@@ -0,0 +1,11 @@
+use async_trait::async_trait;
+use mockall::automock;
+
+use crate::internal::models::view_models::responses::health_check_response::ReadinessResponse;
+
+#[automock]
+#[async_trait]
+pub trait HealthCheckServiceInterface: Send + Sync {
+    //checks every dependency the processor needs to reconcile file chunks
+    async fn check_readiness(&self) -> ReadinessResponse;
+}
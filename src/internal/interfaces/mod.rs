@@ -1,3 +1,6 @@
 pub mod file_chunk_reconciliation_service;
 pub mod file_reconciliation_algorithm;
+pub mod health_check_service;
 pub mod pubsub_repository;
+pub mod upload_progress_repository;
+pub mod upload_progress_service;
@@ -1,8 +1,21 @@
-use crate::internal::shared_reconciler_rust_libraries::models::entities::{
-    app_errors::AppError, file_chunk_queue::FileChunkQueue, file_upload_chunk::FileUploadChunk,
+use crate::internal::{
+    models::{
+        entities::file_chunk_claim_check::FileChunkClaimCheck,
+        view_models::{
+            requests::reconcile_file_chunk_request::ReconcileFileChunkRequest,
+            responses::{
+                reconciled_file_chunk::ReconciledFileChunk,
+                upload_reconciliation_completed_event::UploadReconciliationCompletedEvent,
+            },
+        },
+    },
+    shared_reconciler_rust_libraries::models::entities::{
+        app_errors::AppError, file_chunk_queue::FileChunkQueue, file_upload_chunk::FileUploadChunk,
+    },
 };
 use async_trait::async_trait;
 use mockall::automock;
+use std::{collections::HashMap, time::Duration};
 
 #[automock]
 #[async_trait]
@@ -19,11 +32,45 @@ pub trait PubSubRepositoryInterface: Send + Sync {
 
     async fn insert_file_chunk_in_primary_file_queue(
         &self,
-        file_chunk: &FileUploadChunk,
+        reconcile_file_chunk_request: &ReconcileFileChunkRequest,
     ) -> Result<bool, AppError>;
 
     async fn insert_file_chunk_into_recon_results_queue(
         &self,
-        file_chunk: &FileUploadChunk,
+        reconciled_file_chunk: &ReconciledFileChunk,
+    ) -> Result<bool, AppError>;
+
+    async fn publish_upload_reconciliation_completed_event(
+        &self,
+        upload_reconciliation_completed_event: &UploadReconciliationCompletedEvent,
+    ) -> Result<bool, AppError>;
+
+    async fn get_value_mapping_table_entries(
+        &self,
+        redis_hash_key: &String,
+    ) -> Result<HashMap<String, String>, AppError>;
+
+    async fn get_fx_rate_table_rates(
+        &self,
+        redis_hash_key: &String,
+    ) -> Result<HashMap<String, f64>, AppError>;
+
+    //gets the encoded body of the request a claim check stands for
+    async fn get_file_chunk_claim_check_body(
+        &self,
+        claim_check: &FileChunkClaimCheck,
+    ) -> Result<Vec<u8>, AppError>;
+
+    async fn delete_file_chunk_claim_check_body(
+        &self,
+        claim_check: &FileChunkClaimCheck,
     ) -> Result<bool, AppError>;
+
+    //pings redis, returning the reply,
+    //giving up once redis has not answered within the timeout
+    async fn ping_redis(&self, timeout: Duration) -> Result<String, AppError>;
+
+    //gets the metadata of the dapr sidecar, returning whether
+    //the pubsub component file chunks are published to is loaded
+    async fn is_dapr_pubsub_component_loaded(&self) -> Result<bool, AppError>;
 }
@@ -0,0 +1,50 @@
+use async_trait::async_trait;
+use mockall::automock;
+use std::collections::HashMap;
+
+use crate::internal::{
+    models::entities::upload_progress::UploadProgress,
+    shared_reconciler_rust_libraries::models::entities::app_errors::AppError,
+};
+
+#[automock]
+#[async_trait]
+pub trait UploadProgressRepositoryInterface: Send + Sync {
+    //records the last comparison file chunk a primary file chunk has been reconciled against,
+    //along with its sequence number if it is the last primary file chunk
+    async fn record_primary_file_chunk_progress(
+        &self,
+        upload_request_id: &String,
+        primary_chunk_sequence_number: i64,
+        comparison_chunk_sequence_number: i64,
+        last_primary_chunk_sequence_number: Option<i64>,
+    ) -> Result<(), AppError>;
+
+    //records the rows of a primary file chunk sent to the recon results queue,
+    //returning the progress of the upload request that includes it
+    async fn record_completed_primary_file_chunk(
+        &self,
+        upload_request_id: &String,
+        primary_chunk_sequence_number: i64,
+        last_primary_chunk_sequence_number: Option<i64>,
+        rows_by_recon_status: &HashMap<String, u64>,
+    ) -> Result<UploadProgress, AppError>;
+
+    //gets what has been recorded about an upload request, which is empty if nothing has
+    async fn get_upload_progress(
+        &self,
+        upload_request_id: &String,
+    ) -> Result<UploadProgress, AppError>;
+
+    //claims the publishing of the completion event of an upload request,
+    //returning false if it has already been claimed. the claim expires after a short while,
+    //so a process that stops before publishing the event does not keep it from being published
+    async fn claim_upload_completion(&self, upload_request_id: &String) -> Result<bool, AppError>;
+
+    //keeps the claim on the completion event of an upload request for as long as
+    //its progress is kept, once the event has been published
+    async fn confirm_upload_completion(&self, upload_request_id: &String) -> Result<(), AppError>;
+
+    //lets the completion event of an upload request be claimed again
+    async fn release_upload_completion(&self, upload_request_id: &String) -> Result<(), AppError>;
+}
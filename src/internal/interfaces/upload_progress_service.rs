@@ -0,0 +1,17 @@
+use async_trait::async_trait;
+use mockall::automock;
+
+use crate::internal::{
+    models::view_models::responses::upload_progress_response::UploadProgressResponse,
+    shared_reconciler_rust_libraries::models::entities::app_errors::AppError,
+};
+
+#[automock]
+#[async_trait]
+pub trait UploadProgressServiceInterface: Send + Sync {
+    //sums up how far reconciliation of an upload request has got
+    async fn get_upload_progress(
+        &self,
+        upload_request_id: String,
+    ) -> Result<UploadProgressResponse, AppError>;
+}
@@ -0,0 +1,5 @@
+pub mod reconciliation_metrics;
+
+#[cfg(test)]
+#[path = "./reconciliation_metrics_tests.rs"]
+mod reconciliation_metrics_tests;
@@ -0,0 +1,159 @@
+use lazy_static::lazy_static;
+use prometheus::{
+    register_histogram_vec, register_int_counter_vec, Encoder, HistogramVec, IntCounterVec,
+    TextEncoder,
+};
+use std::{collections::HashMap, future::Future, time::Instant};
+
+use crate::internal::shared_reconciler_rust_libraries::models::entities::app_errors::{
+    AppError, AppErrorKind,
+};
+
+//what happened to a primary file chunk that was sent to us
+pub const FILE_CHUNK_PROCESSED: &str = "processed";
+pub const FILE_CHUNK_REQUEUED: &str = "requeued";
+pub const FILE_CHUNK_COMPLETED: &str = "completed";
+
+//the reconciliation algorithms a chunk can be reconciled with
+pub const GENERIC_ALGORITHM: &str = "generic";
+pub const MATCH_RULE_ALGORITHM: &str = "match_rule";
+pub const MULTI_PASS_ALGORITHM: &str = "multi_pass";
+
+//what is being read from redis
+pub const COMPARISON_FILE_CHUNK_READ: &str = "comparison_file_chunk";
+pub const VALUE_MAPPING_TABLE_READ: &str = "value_mapping_table";
+pub const FX_RATE_TABLE_READ: &str = "fx_rate_table";
+pub const CLAIM_CHECK_READ: &str = "claim_check";
+
+//the queues chunks are published to through dapr
+pub const PRIMARY_FILE_CHUNKS_QUEUE: &str = "primary_file_chunks";
+pub const RECON_RESULTS_QUEUE: &str = "recon_results";
+pub const UPLOAD_RECONCILIATION_COMPLETED_QUEUE: &str = "upload_reconciliation_completed";
+
+//the operations errors are counted against. a failed redis read or dapr publish fails
+//the chunk, so it is counted there once and only shows up on its duration by outcome
+pub const RECONCILE_FILE_CHUNK_OPERATION: &str = "reconcile_file_chunk";
+
+//how a redis read or dapr publish turned out
+pub const SUCCEEDED_OUTCOME: &str = "succeeded";
+pub const FAILED_OUTCOME: &str = "failed";
+
+//a chunk is reconciled against a whole comparison file chunk at a time,
+//so the algorithm buckets go well past the default of ten seconds
+const ALGORITHM_DURATION_BUCKETS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0, 60.0,
+];
+
+lazy_static! {
+    pub static ref RECONCILED_ROWS: IntCounterVec = register_int_counter_vec!(
+        "recon_file_chunk_processor_reconciled_rows_total",
+        "Rows of primary file chunks sent to the recon results queue, by their recon status",
+        &["recon_status"]
+    )
+    .unwrap();
+    pub static ref FILE_CHUNKS: IntCounterVec = register_int_counter_vec!(
+        "recon_file_chunk_processor_file_chunks_total",
+        "Primary file chunks reconciled against a comparison file chunk, requeued or completed",
+        &["outcome"]
+    )
+    .unwrap();
+    pub static ref RECONCILIATION_ALGORITHM_DURATION: HistogramVec = register_histogram_vec!(
+        "recon_file_chunk_processor_reconciliation_algorithm_duration_seconds",
+        "Time taken to reconcile a primary file chunk against a comparison file chunk",
+        &["algorithm"],
+        ALGORITHM_DURATION_BUCKETS.to_vec()
+    )
+    .unwrap();
+    pub static ref REDIS_READ_DURATION: HistogramVec = register_histogram_vec!(
+        "recon_file_chunk_processor_redis_read_duration_seconds",
+        "Time taken to read from redis, by whether the read succeeded",
+        &["operation", "outcome"]
+    )
+    .unwrap();
+    pub static ref DAPR_PUBLISH_DURATION: HistogramVec = register_histogram_vec!(
+        "recon_file_chunk_processor_dapr_publish_duration_seconds",
+        "Time taken to publish a file chunk through the dapr sidecar, by whether it succeeded",
+        &["queue", "outcome"]
+    )
+    .unwrap();
+    pub static ref ERRORS: IntCounterVec = register_int_counter_vec!(
+        "recon_file_chunk_processor_errors_total",
+        "Errors by the operation that failed and the kind of error",
+        &["operation", "kind"]
+    )
+    .unwrap();
+}
+
+//counts the rows of a completed primary file chunk by their recon status
+pub fn record_reconciled_rows(rows_by_recon_status: &HashMap<String, u64>) {
+    for (recon_status, row_count) in rows_by_recon_status {
+        RECONCILED_ROWS
+            .with_label_values(&[recon_status])
+            .inc_by(*row_count);
+    }
+}
+
+pub fn record_file_chunk(outcome: &str) {
+    FILE_CHUNKS.with_label_values(&[outcome]).inc();
+}
+
+pub fn record_error(operation: &str, error: &AppError) {
+    ERRORS
+        .with_label_values(&[operation, &format!("{:?}", error.kind)])
+        .inc();
+}
+
+pub fn observe_reconciliation_algorithm_duration(algorithm: &str, duration_in_seconds: f64) {
+    RECONCILIATION_ALGORITHM_DURATION
+        .with_label_values(&[algorithm])
+        .observe(duration_in_seconds);
+}
+
+//times a read from redis by whether it succeeded
+pub async fn time_redis_read<T>(
+    operation: &str,
+    redis_read: impl Future<Output = Result<T, AppError>>,
+) -> Result<T, AppError> {
+    let started_at = Instant::now();
+    let read_result = redis_read.await;
+
+    REDIS_READ_DURATION
+        .with_label_values(&[operation, get_outcome(&read_result)])
+        .observe(started_at.elapsed().as_secs_f64());
+
+    return read_result;
+}
+
+//times the publishing of a file chunk by whether it succeeded
+pub async fn time_dapr_publish<T>(
+    queue: &str,
+    dapr_publish: impl Future<Output = Result<T, AppError>>,
+) -> Result<T, AppError> {
+    let started_at = Instant::now();
+    let publish_result = dapr_publish.await;
+
+    DAPR_PUBLISH_DURATION
+        .with_label_values(&[queue, get_outcome(&publish_result)])
+        .observe(started_at.elapsed().as_secs_f64());
+
+    return publish_result;
+}
+
+fn get_outcome<T>(result: &Result<T, AppError>) -> &'static str {
+    return match result {
+        Ok(_) => SUCCEEDED_OUTCOME,
+        Err(_) => FAILED_OUTCOME,
+    };
+}
+
+//renders every registered metric in the prometheus text format
+pub fn gather_metrics() -> Result<String, AppError> {
+    let mut buffer = vec![];
+
+    TextEncoder::new()
+        .encode(&prometheus::gather(), &mut buffer)
+        .map_err(|e| AppError::new(AppErrorKind::InternalError, e.to_string()))?;
+
+    return String::from_utf8(buffer)
+        .map_err(|e| AppError::new(AppErrorKind::InternalError, e.to_string()));
+}
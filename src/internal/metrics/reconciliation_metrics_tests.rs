@@ -0,0 +1,162 @@
+use crate::internal::{
+    models::entities::upload_progress::count_rows_by_recon_status,
+    shared_reconciler_rust_libraries::models::entities::{
+        app_errors::{AppError, AppErrorKind},
+        file_chunk_queue::FileChunkQueue,
+        file_upload_chunk::{
+            FileUploadChunk, FileUploadChunkRow, FileUploadChunkSource, ReconStatus,
+        },
+        recon_tasks_models::ReconciliationConfigs,
+    },
+};
+
+use super::reconciliation_metrics::{
+    gather_metrics, record_error, record_reconciled_rows, time_dapr_publish, time_redis_read,
+    DAPR_PUBLISH_DURATION, ERRORS, FAILED_OUTCOME, RECONCILED_ROWS, REDIS_READ_DURATION,
+    SUCCEEDED_OUTCOME,
+};
+
+//the metrics are kept in the global registry and other tests reconcile chunks at the
+//same time, so the tests use labels of their own or check for at least what they added
+
+#[actix_web::test]
+async fn test_record_reconciled_rows_counts_rows_by_recon_status() {
+    //setup
+    let file_chunk = build_file_chunk(vec![
+        ReconStatus::Successful,
+        ReconStatus::Successful,
+        ReconStatus::Failed,
+    ]);
+    let successful_rows_before = RECONCILED_ROWS.with_label_values(&["Successful"]).get();
+    let failed_rows_before = RECONCILED_ROWS.with_label_values(&["Failed"]).get();
+
+    //act
+    record_reconciled_rows(&count_rows_by_recon_status(&file_chunk));
+
+    //assert
+    let successful_rows_after = RECONCILED_ROWS.with_label_values(&["Successful"]).get();
+    let failed_rows_after = RECONCILED_ROWS.with_label_values(&["Failed"]).get();
+    assert!(successful_rows_after - successful_rows_before >= 2);
+    assert!(failed_rows_after - failed_rows_before >= 1);
+}
+
+#[actix_web::test]
+async fn test_time_redis_read_given_failed_read_observes_it_as_failed_without_counting_an_error() {
+    //act
+    let actual: Result<(), AppError> = time_redis_read("test_failed_read", async {
+        return Err(AppError::new(
+            AppErrorKind::ConnectionError,
+            String::from("Connection refused"),
+        ));
+    })
+    .await;
+
+    //assert
+    assert_eq!(actual.unwrap_err().kind, AppErrorKind::ConnectionError);
+    assert_eq!(
+        REDIS_READ_DURATION
+            .with_label_values(&["test_failed_read", FAILED_OUTCOME])
+            .get_sample_count(),
+        1
+    );
+
+    //the error is counted once, by the request that fails because of it
+    let metrics = gather_metrics().unwrap();
+    assert!(!metrics.contains("operation=\"redis_read\""));
+}
+
+#[actix_web::test]
+async fn test_time_dapr_publish_given_successful_publish_observes_its_duration() {
+    //act
+    let actual = time_dapr_publish("test_successful_publish", async {
+        return Ok(true);
+    })
+    .await;
+
+    //assert
+    assert_eq!(actual.unwrap(), true);
+    assert_eq!(
+        DAPR_PUBLISH_DURATION
+            .with_label_values(&["test_successful_publish", SUCCEEDED_OUTCOME])
+            .get_sample_count(),
+        1
+    );
+}
+
+#[actix_web::test]
+async fn test_record_error_counts_the_error_by_its_operation_and_kind() {
+    //act
+    record_error(
+        "test_record_error",
+        &AppError::new(AppErrorKind::NotFound, String::from("stream not found")),
+    );
+
+    //assert
+    assert_eq!(
+        ERRORS
+            .with_label_values(&["test_record_error", "NotFound"])
+            .get(),
+        1
+    );
+}
+
+#[actix_web::test]
+async fn test_gather_metrics_returns_recorded_metrics_in_text_format() {
+    //setup
+    record_error(
+        "test_gather_metrics",
+        &AppError::new(AppErrorKind::InternalError, String::from("publish failed")),
+    );
+
+    //act
+    let actual = gather_metrics().unwrap();
+
+    //assert
+    assert!(actual.contains("# TYPE recon_file_chunk_processor_errors_total counter"));
+    assert!(actual.contains(
+        "recon_file_chunk_processor_errors_total{kind=\"InternalError\",operation=\"test_gather_metrics\"} 1"
+    ));
+}
+
+fn build_file_chunk(recon_results: Vec<ReconStatus>) -> FileUploadChunk {
+    let chunk_rows = recon_results
+        .into_iter()
+        .enumerate()
+        .map(|(index, recon_result)| FileUploadChunkRow {
+            row_number: index as u64 + 1,
+            raw_data: String::from(""),
+            parsed_columns_from_row: vec![],
+            recon_result,
+            recon_result_reasons: vec![],
+        })
+        .collect();
+
+    return FileUploadChunk {
+        id: String::from("file-1234"),
+        upload_request_id: String::from("file-1234"),
+        chunk_sequence_number: 1,
+        chunk_source: FileUploadChunkSource::PrimaryFileChunk,
+        chunk_rows,
+        date_created: 1710000000,
+        date_modified: 1710000000,
+        comparison_pairs: vec![],
+        column_headers: vec![],
+        recon_config: ReconciliationConfigs {
+            should_check_for_duplicate_records_in_comparison_file: false,
+            should_reconciliation_be_case_sensitive: true,
+            should_ignore_white_space: true,
+            should_do_reverse_reconciliation: false,
+        },
+        primary_file_chunks_queue: build_file_chunks_queue("primary"),
+        comparison_file_chunks_queue: build_file_chunks_queue("comparison"),
+        result_chunks_queue: build_file_chunks_queue("result"),
+        is_last_chunk: false,
+    };
+}
+
+fn build_file_chunks_queue(prefix: &str) -> FileChunkQueue {
+    FileChunkQueue {
+        topic_id: format!("{}-file-chunks-queue-1", prefix),
+        last_acknowledged_id: Some(String::from("1710000000000-0")),
+    }
+}
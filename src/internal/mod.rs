@@ -1,5 +1,7 @@
 pub mod interfaces;
+pub mod metrics;
 pub mod models;
 pub mod services;
 pub mod shared_reconciler_rust_libraries;
+pub mod telemetry;
 pub mod web_api;
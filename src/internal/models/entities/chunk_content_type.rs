@@ -0,0 +1,81 @@
+use crate::internal::shared_reconciler_rust_libraries::models::entities::app_errors::{
+    AppError, AppErrorKind,
+};
+
+//how file chunk payloads are encoded on the wire
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ChunkEncoding {
+    #[default]
+    Json,
+    MessagePack,
+    Cbor,
+}
+
+//the content type of a file chunk payload e.g application/msgpack+zstd.
+//a +zstd suffix means the encoded payload is also compressed using zstd
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ChunkContentType {
+    pub encoding: ChunkEncoding,
+    pub is_zstd_compressed: bool,
+}
+
+impl ChunkContentType {
+    //reads a content type header, ignoring parameters such as charset.
+    //an empty content type is taken to be json, since that is what chunks used to be sent as,
+    //as is any json subtype e.g application/cloudevents+json, the same way actix treats them.
+    //the binary encodings are only used when they are asked for by name
+    pub fn parse(content_type: &str) -> Result<ChunkContentType, AppError> {
+        let media_type = content_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_lowercase();
+
+        let (media_type, is_zstd_compressed) = match media_type.strip_suffix("+zstd") {
+            Some(uncompressed_media_type) => (uncompressed_media_type.to_string(), true),
+            None => (media_type, false),
+        };
+
+        let encoding = match media_type.as_str() {
+            "" | "json" => ChunkEncoding::Json,
+            _ if Self::is_json_media_type(&media_type) => ChunkEncoding::Json,
+            "application/msgpack" | "application/x-msgpack" | "application/vnd.msgpack" => {
+                ChunkEncoding::MessagePack
+            }
+            "application/cbor" => ChunkEncoding::Cbor,
+            _ => {
+                return Err(AppError::new(
+                    AppErrorKind::BadClientRequest,
+                    format!("unsupported file chunk content type [{}]", content_type),
+                ))
+            }
+        };
+
+        return Ok(ChunkContentType {
+            encoding,
+            is_zstd_compressed,
+        });
+    }
+
+    //a media type whose subtype is json or ends in +json e.g text/json or application/problem+json
+    fn is_json_media_type(media_type: &str) -> bool {
+        return match media_type.split_once('/') {
+            Some((_, subtype)) => subtype == "json" || subtype.ends_with("+json"),
+            None => false,
+        };
+    }
+
+    pub fn to_content_type_header(self) -> String {
+        let media_type = match self.encoding {
+            ChunkEncoding::Json => "application/json",
+            ChunkEncoding::MessagePack => "application/msgpack",
+            ChunkEncoding::Cbor => "application/cbor",
+        };
+
+        return match self.is_zstd_compressed {
+            true => format!("{}+zstd", media_type),
+            false => media_type.to_string(),
+        };
+    }
+}
@@ -0,0 +1,6 @@
+//how large a file chunk payload may get once it is decompressed. the request body limit
+//only applies to the compressed payload, which can inflate to many times its size
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ChunkPayloadLimits {
+    pub max_decompressed_bytes: usize,
+}
@@ -0,0 +1,107 @@
+use serde::{Deserialize, Serialize};
+use validator::ValidationError;
+
+use super::{
+    currency_conversion::{validate_currency_conversion, CurrencyConversion},
+    value_mapping::ValueMappingTable,
+    value_normalization::NormalizationStep,
+    value_transform::{validate_value_transform, ValueTransform},
+};
+
+//extra settings used when comparing the columns of a comparison pair
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct ComparisonPairOptions {
+    //how much a mismatch in this pair matters to the outcome of the row
+    #[serde(default)]
+    pub severity: MismatchSeverity,
+
+    //normalization steps used for this pair instead of the ones set for the chunk
+    #[serde(default)]
+    pub normalization_steps: Option<Vec<NormalizationStep>>,
+
+    //rewrites the primary file column value before it is normalized and compared
+    #[serde(default)]
+    pub primary_file_value_transform: Option<ValueTransform>,
+
+    //rewrites the comparison file column value before it is normalized and compared
+    #[serde(default)]
+    pub comparison_file_value_transform: Option<ValueTransform>,
+
+    //translates the transformed comparison file column value into
+    //the vocabulary of the primary file before it is normalized and compared
+    #[serde(default)]
+    pub comparison_file_value_mapping: Option<ValueMappingTable>,
+
+    //compares the paired amounts in a base currency instead of comparing their values
+    #[serde(default)]
+    pub currency_conversion: Option<CurrencyConversion>,
+
+    //compares the paired values as amounts, which match if they are no further apart
+    //than the tolerance, so that e.g "100.5" and "100.50" or "1,000" and "1000" match.
+    //pairs with a derived amount column are always compared as amounts,
+    //with no tolerance unless one is set here
+    #[serde(default)]
+    pub amount_tolerance: Option<f64>,
+}
+
+impl ComparisonPairOptions {
+    //checks the value transforms of both sides of the pair and the currency conversion
+    pub fn validate_pair_options(&self) -> Result<(), ValidationError> {
+        if let Some(currency_conversion) = &self.currency_conversion {
+            validate_currency_conversion(currency_conversion)?;
+        }
+
+        if let Some(amount_tolerance) = self.amount_tolerance {
+            if amount_tolerance < 0.0 || !amount_tolerance.is_finite() {
+                return Err(ValidationError::new(
+                    "amount tolerance must be a number that is not negative",
+                ));
+            }
+        }
+
+        for value_transform in [
+            &self.primary_file_value_transform,
+            &self.comparison_file_value_transform,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            validate_value_transform(value_transform)?;
+        }
+        return Ok(());
+    }
+}
+
+//comparison pair options for the index based comparison pair
+//with the same primary and comparison file column indexes
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ColumnIndexComparisonPairOptions {
+    pub primary_file_column_index: usize,
+
+    pub comparison_file_column_index: usize,
+
+    #[serde(flatten)]
+    pub options: ComparisonPairOptions,
+}
+
+pub fn validate_comparison_pair_options(
+    comparison_pair_options: &Vec<ColumnIndexComparisonPairOptions>,
+) -> Result<(), ValidationError> {
+    for pair_options in comparison_pair_options {
+        pair_options.options.validate_pair_options()?;
+    }
+    return Ok(());
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub enum MismatchSeverity {
+    //a mismatch fails the row
+    #[default]
+    Critical,
+
+    //a mismatch is reported and the row is matched with warnings
+    Warning,
+
+    //a mismatch is only reported
+    Informational,
+}
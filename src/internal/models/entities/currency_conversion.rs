@@ -0,0 +1,114 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+use validator::ValidationError;
+
+//compares the amounts of a comparison pair after converting both
+//into a base currency, using the currency column of each file
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct CurrencyConversion {
+    pub primary_file_currency_column_index: usize,
+
+    pub comparison_file_currency_column_index: usize,
+
+    //the currency both amounts are converted into e.g "USD"
+    pub base_currency: String,
+
+    pub fx_rate_table: FxRateTable,
+
+    //the largest difference between the converted amounts
+    //that is still treated as a match, in the base currency
+    #[serde(default)]
+    pub tolerance: f64,
+}
+
+//the rates used to convert amounts into the base currency, each rate
+//is the amount of the base currency that 1 unit of the currency is worth
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum FxRateTable {
+    //the rates are supplied with the reconciliation options
+    Inline { rates: HashMap<String, f64> },
+
+    //the rates are loaded from a redis hash, where each field
+    //is a currency code and its value is the rate
+    RedisHash { key: String },
+}
+
+impl FxRateTable {
+    //gets the rate of a currency, the base currency always has a rate of 1.
+    //returns None if the currency is not in the table or the table has not been loaded yet
+    pub fn get_rate(&self, currency: &str, base_currency: &str) -> Option<f64> {
+        if currency.trim().eq_ignore_ascii_case(base_currency.trim()) {
+            return Some(1.0);
+        }
+
+        match self {
+            FxRateTable::Inline { rates } => rates
+                .iter()
+                .find(|(rate_currency, _)| {
+                    rate_currency.trim().eq_ignore_ascii_case(currency.trim())
+                })
+                .map(|(_, rate)| *rate),
+            FxRateTable::RedisHash { .. } => None,
+        }
+    }
+}
+
+//how the amounts of a comparison pair were converted into the base currency
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct FxConversion {
+    pub base_currency: String,
+
+    pub primary_file_currency: String,
+
+    pub primary_file_rate: f64,
+
+    pub primary_file_base_currency_amount: f64,
+
+    pub comparison_file_currency: String,
+
+    pub comparison_file_rate: f64,
+
+    pub comparison_file_base_currency_amount: f64,
+
+    pub tolerance: f64,
+}
+
+impl FxConversion {
+    //checks that the converted amounts are no further apart than the tolerance
+    pub fn is_within_tolerance(&self) -> bool {
+        let difference = (self.primary_file_base_currency_amount
+            - self.comparison_file_base_currency_amount)
+            .abs();
+
+        //the amounts are floating point so a tiny difference is allowed for rounding
+        return difference <= self.tolerance + 1e-9;
+    }
+}
+
+//checks that a currency conversion has a base currency, usable rates and a tolerance
+pub fn validate_currency_conversion(
+    currency_conversion: &CurrencyConversion,
+) -> Result<(), ValidationError> {
+    if currency_conversion.base_currency.trim().is_empty() {
+        return Err(ValidationError::new(
+            "currency conversion requires a base currency",
+        ));
+    }
+
+    if currency_conversion.tolerance < 0.0 {
+        return Err(ValidationError::new(
+            "currency conversion tolerance cannot be negative",
+        ));
+    }
+
+    if let FxRateTable::Inline { rates } = &currency_conversion.fx_rate_table {
+        if rates.values().any(|rate| *rate <= 0.0 || !rate.is_finite()) {
+            return Err(ValidationError::new(
+                "currency conversion requires rates greater than zero",
+            ));
+        }
+    }
+
+    return Ok(());
+}
@@ -0,0 +1,74 @@
+use serde::{Deserialize, Serialize};
+use validator::ValidationError;
+
+//a column that is worked out from other columns of the row before reconciliation.
+//derived columns are added after the columns that have headers in the order they are listed
+//and their headers to the column headers, so they are easiest to pair by header name
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum DerivedColumn {
+    //a signed amount made from an amount column and, optionally,
+    //a debit/credit indicator column e.g "100.00" and "DR" become "-100.00"
+    SignedAmount(SignedAmountColumn),
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SignedAmountColumn {
+    //the header of the derived column
+    pub column_header: String,
+
+    pub amount_column_index: usize,
+
+    //when not set the amount column is expected to already carry its sign
+    #[serde(default)]
+    pub indicator_column_index: Option<usize>,
+
+    //indicator values that make the amount negative e.g "DR", "D"
+    #[serde(default)]
+    pub debit_indicators: Vec<String>,
+
+    //indicator values that keep the amount positive e.g "CR", "C"
+    #[serde(default)]
+    pub credit_indicators: Vec<String>,
+
+    //flips the sign of the derived amount, for files that
+    //are kept from the point of view of the other party
+    #[serde(default)]
+    pub should_invert_sign: bool,
+}
+
+impl DerivedColumn {
+    pub fn get_column_header(&self) -> &String {
+        match self {
+            DerivedColumn::SignedAmount(signed_amount_column) => {
+                &signed_amount_column.column_header
+            }
+        }
+    }
+}
+
+//checks that every derived column has a header and enough information to be worked out
+pub fn validate_derived_columns(
+    derived_columns: &Vec<DerivedColumn>,
+) -> Result<(), ValidationError> {
+    for derived_column in derived_columns {
+        if derived_column.get_column_header().trim().is_empty() {
+            return Err(ValidationError::new(
+                "derived columns require a column header",
+            ));
+        }
+
+        match derived_column {
+            DerivedColumn::SignedAmount(signed_amount_column) => {
+                if signed_amount_column.indicator_column_index.is_some()
+                    && (signed_amount_column.debit_indicators.is_empty()
+                        || signed_amount_column.credit_indicators.is_empty())
+                {
+                    return Err(ValidationError::new(
+                        "signed amount columns with an indicator column require debit and credit indicators",
+                    ));
+                }
+            }
+        }
+    }
+    return Ok(());
+}
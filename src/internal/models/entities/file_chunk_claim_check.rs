@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+//the content type a claim check is published with in place of the file chunk it stands for
+pub const FILE_CHUNK_CLAIM_CHECK_CONTENT_TYPE: &str = "application/vnd.file-chunk-claim-check+json";
+
+//published in place of a reconcile file chunk request that is too large for the pubsub.
+//the body of the request is kept in redis under the claim check key until the
+//chunk is next reconciled, or until it expires
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FileChunkClaimCheck {
+    pub claim_check_key: String,
+
+    //the content type the body of the request was encoded with
+    pub content_type: String,
+
+    //the primary file chunk the body holds, so the claim check can be traced without loading it
+    pub primary_file_chunk_id: String,
+}
+
+impl FileChunkClaimCheck {
+    pub fn is_claim_check_content_type(content_type: &str) -> bool {
+        return content_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .eq_ignore_ascii_case(FILE_CHUNK_CLAIM_CHECK_CONTENT_TYPE);
+    }
+}
@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+use validator::ValidationError;
+
+use super::comparison_pair_options::ComparisonPairOptions;
+
+//a comparison pair that addresses columns by their header name instead of their index
+//so that it keeps comparing the right columns when a partner reorders their file
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct HeaderNameComparisonPair {
+    pub primary_file_column_header: String,
+
+    pub comparison_file_column_header: String,
+
+    pub is_row_identifier: bool,
+
+    #[serde(flatten)]
+    pub options: ComparisonPairOptions,
+}
+
+pub fn validate_header_name_comparison_pairs(
+    header_name_comparison_pairs: &Vec<HeaderNameComparisonPair>,
+) -> Result<(), ValidationError> {
+    for header_name_pair in header_name_comparison_pairs {
+        header_name_pair.options.validate_pair_options()?;
+    }
+    return Ok(());
+}
@@ -0,0 +1,95 @@
+use serde::{Deserialize, Serialize};
+use validator::ValidationError;
+
+use crate::internal::shared_reconciler_rust_libraries::models::entities::recon_tasks_models::ComparisonPair;
+
+use super::{
+    comparison_pair_options::{validate_comparison_pair_options, ColumnIndexComparisonPairOptions},
+    header_name_comparison_pair::{
+        validate_header_name_comparison_pairs, HeaderNameComparisonPair,
+    },
+    match_rule::validate_match_rule,
+    reconciliation_options::ReconciliationOptions,
+};
+
+//one round of matching with its own row identifiers and comparators e.g
+//an exact reference pass followed by an amount and date pass for the rows the first one left over
+//the passes run against one comparison file chunk at a time, so a later pass can match a row
+//with a comparison row before an earlier pass has seen the comparison chunk it would match
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct MatchPass {
+    //recorded on every row this pass matched
+    pub name: String,
+
+    //used instead of the comparison pairs of the chunk
+    #[serde(default)]
+    pub comparison_pairs: Vec<ComparisonPair>,
+
+    #[serde(default)]
+    pub header_name_comparison_pairs: Vec<HeaderNameComparisonPair>,
+
+    #[serde(default)]
+    pub comparison_pair_options: Vec<ColumnIndexComparisonPairOptions>,
+
+    //when set, the pass matches rows using this rule instead of its non identifier comparison pairs
+    #[serde(default)]
+    pub match_rule: Option<String>,
+}
+
+impl MatchPass {
+    //the options of the chunk with the comparators of this pass swapped in
+    pub fn get_reconciliation_options(
+        &self,
+        reconciliation_options: &ReconciliationOptions,
+    ) -> ReconciliationOptions {
+        return ReconciliationOptions {
+            header_name_comparison_pairs: vec![],
+            comparison_pair_options: self.comparison_pair_options.clone(),
+            match_rule: self.match_rule.clone(),
+            match_passes: vec![],
+            ..reconciliation_options.clone()
+        };
+    }
+
+    fn has_row_identifiers(&self) -> bool {
+        return self
+            .comparison_pairs
+            .iter()
+            .any(|pair| pair.is_row_identifier)
+            || self
+                .header_name_comparison_pairs
+                .iter()
+                .any(|pair| pair.is_row_identifier);
+    }
+}
+
+pub fn validate_match_passes(match_passes: &Vec<MatchPass>) -> Result<(), ValidationError> {
+    for (index, match_pass) in match_passes.iter().enumerate() {
+        if match_pass.name.trim().is_empty() {
+            return Err(ValidationError::new("every match pass needs a name"));
+        }
+
+        //the name is how a row tells which pass matched it
+        if match_passes[..index]
+            .iter()
+            .any(|earlier_match_pass| earlier_match_pass.name == match_pass.name)
+        {
+            return Err(ValidationError::new("match pass names must be unique"));
+        }
+
+        //without identifiers or a rule every comparison row would match
+        if !match_pass.has_row_identifiers() && match_pass.match_rule.is_none() {
+            return Err(ValidationError::new(
+                "a match pass needs a row identifier comparison pair or a match rule",
+            ));
+        }
+
+        if let Some(match_rule) = &match_pass.match_rule {
+            validate_match_rule(match_rule)?;
+        }
+
+        validate_comparison_pair_options(&match_pass.comparison_pair_options)?;
+        validate_header_name_comparison_pairs(&match_pass.header_name_comparison_pairs)?;
+    }
+    return Ok(());
+}
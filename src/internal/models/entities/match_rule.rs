@@ -0,0 +1,403 @@
+use std::borrow::Cow;
+use validator::ValidationError;
+
+//how deeply parentheses and NOTs may be nested in a match rule.
+//rules come from clients and are parsed and evaluated recursively,
+//so a deeper rule could overflow the stack of the thread handling it
+pub const MAX_MATCH_RULE_NESTING_DEPTH: usize = 32;
+
+//how many predicates a match rule may have. AND and OR chains are evaluated
+//recursively as well, so their length is limited for the same reason
+pub const MAX_MATCH_RULE_PREDICATES: usize = 256;
+
+//a rule deciding whether a primary file row and a comparison file row are the same row e.g
+//amount_within_percent(primary[1], comparison[1], 0.5) AND (date_within_days(primary["Value Date"], comparison[3], 2) OR equals(primary[0], comparison[0]))
+#[derive(Debug, Clone, PartialEq)]
+pub enum MatchRule {
+    And(Box<MatchRule>, Box<MatchRule>),
+    Or(Box<MatchRule>, Box<MatchRule>),
+    Not(Box<MatchRule>),
+    Predicate(MatchRulePredicate),
+}
+
+//compares a primary file column with a comparison file column
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchRulePredicate {
+    pub function: MatchRuleFunction,
+    pub primary_file_column: ColumnReference,
+    pub comparison_file_column: ColumnReference,
+    pub argument: Option<f64>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MatchRuleFunction {
+    //equals(primary[0], comparison[0])
+    Equals,
+
+    //equals_ignoring_case(primary[0], comparison[0])
+    EqualsIgnoringCase,
+
+    //amount_within(primary[1], comparison[1], 10) the amounts differ by at most 10
+    AmountWithin,
+
+    //amount_within_percent(primary[1], comparison[1], 0.5) the amounts differ
+    //by at most 0.5% of the primary file amount
+    AmountWithinPercent,
+
+    //date_within_days(primary[2], comparison[2], 2) the dates are at most 2 days apart
+    DateWithinDays,
+
+    //similar_text(primary[3], comparison[3], 0.8) the texts are at least 80% alike,
+    //going by how many characters have to change to turn one into the other
+    SimilarText,
+}
+
+impl MatchRuleFunction {
+    fn from_name(name: &str) -> Option<MatchRuleFunction> {
+        match name.to_ascii_lowercase().as_str() {
+            "equals" => Some(MatchRuleFunction::Equals),
+            "equals_ignoring_case" => Some(MatchRuleFunction::EqualsIgnoringCase),
+            "amount_within" => Some(MatchRuleFunction::AmountWithin),
+            "amount_within_percent" => Some(MatchRuleFunction::AmountWithinPercent),
+            "date_within_days" => Some(MatchRuleFunction::DateWithinDays),
+            "similar_text" => Some(MatchRuleFunction::SimilarText),
+            _ => None,
+        }
+    }
+
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            MatchRuleFunction::Equals => "equals",
+            MatchRuleFunction::EqualsIgnoringCase => "equals_ignoring_case",
+            MatchRuleFunction::AmountWithin => "amount_within",
+            MatchRuleFunction::AmountWithinPercent => "amount_within_percent",
+            MatchRuleFunction::DateWithinDays => "date_within_days",
+            MatchRuleFunction::SimilarText => "similar_text",
+        }
+    }
+
+    fn requires_argument(&self) -> bool {
+        return !matches!(
+            self,
+            MatchRuleFunction::Equals | MatchRuleFunction::EqualsIgnoringCase
+        );
+    }
+}
+
+//a column of a row, addressed by its index e.g primary[1]
+//or by its header e.g primary["Value Date"]
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColumnReference {
+    Index(usize),
+    Header(String),
+}
+
+impl MatchRule {
+    //parses the text of a match rule
+    pub fn parse(match_rule: &str) -> Result<MatchRule, String> {
+        let tokens = tokenize(match_rule)?;
+        let mut parser = MatchRuleParser {
+            tokens,
+            position: 0,
+            nesting_depth: 0,
+            predicates_count: 0,
+        };
+
+        let parsed_match_rule = parser.parse_or()?;
+
+        if let Some(token) = parser.peek() {
+            return Err(format!("unexpected [{}] after the end of the rule", token));
+        }
+
+        return Ok(parsed_match_rule);
+    }
+}
+
+//checks that a match rule can be parsed. the error says what the parser stopped at,
+//so that whoever wrote the rule can tell which part of it is wrong
+pub fn validate_match_rule(match_rule: &String) -> Result<(), ValidationError> {
+    match MatchRule::parse(match_rule) {
+        Ok(_) => Ok(()),
+        Err(parse_error) => {
+            let mut validation_error =
+                ValidationError::new("match rule is not a valid match rule expression");
+            validation_error.message = Some(Cow::from(format!(
+                "match rule is not a valid match rule expression: {}",
+                parse_error
+            )));
+            Err(validation_error)
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Identifier(String),
+    Number(f64),
+    Text(String),
+    OpenParenthesis,
+    CloseParenthesis,
+    OpenBracket,
+    CloseBracket,
+    Comma,
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Identifier(identifier) => write!(f, "{}", identifier),
+            Token::Number(number) => write!(f, "{}", number),
+            Token::Text(text) => write!(f, "\"{}\"", text),
+            Token::OpenParenthesis => write!(f, "("),
+            Token::CloseParenthesis => write!(f, ")"),
+            Token::OpenBracket => write!(f, "["),
+            Token::CloseBracket => write!(f, "]"),
+            Token::Comma => write!(f, ","),
+        }
+    }
+}
+
+fn tokenize(match_rule: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = vec![];
+    let mut characters = match_rule.chars().peekable();
+
+    while let Some(&character) = characters.peek() {
+        if character.is_whitespace() {
+            characters.next();
+            continue;
+        }
+
+        let single_character_token = match character {
+            '(' => Some(Token::OpenParenthesis),
+            ')' => Some(Token::CloseParenthesis),
+            '[' => Some(Token::OpenBracket),
+            ']' => Some(Token::CloseBracket),
+            ',' => Some(Token::Comma),
+            _ => None,
+        };
+
+        if let Some(token) = single_character_token {
+            tokens.push(token);
+            characters.next();
+            continue;
+        }
+
+        //a column header in double quotes
+        if character == '"' {
+            characters.next();
+            let mut text = String::new();
+            loop {
+                match characters.next() {
+                    Some('"') => break,
+                    Some(text_character) => text.push(text_character),
+                    None => {
+                        return Err(String::from("a column header is missing its closing quote"))
+                    }
+                }
+            }
+            tokens.push(Token::Text(text));
+            continue;
+        }
+
+        if character.is_ascii_digit() || character == '.' || character == '-' {
+            let mut number = String::new();
+            while let Some(&number_character) = characters.peek() {
+                if !(number_character.is_ascii_digit()
+                    || number_character == '.'
+                    || (number_character == '-' && number.is_empty()))
+                {
+                    break;
+                }
+                number.push(number_character);
+                characters.next();
+            }
+            let parsed_number = number
+                .parse()
+                .map_err(|_| format!("[{}] is not a number", number))?;
+            tokens.push(Token::Number(parsed_number));
+            continue;
+        }
+
+        if character.is_alphabetic() || character == '_' {
+            let mut identifier = String::new();
+            while let Some(&identifier_character) = characters.peek() {
+                if !(identifier_character.is_alphanumeric() || identifier_character == '_') {
+                    break;
+                }
+                identifier.push(identifier_character);
+                characters.next();
+            }
+            tokens.push(Token::Identifier(identifier));
+            continue;
+        }
+
+        return Err(format!("unexpected character [{}]", character));
+    }
+
+    return Ok(tokens);
+}
+
+//a recursive descent parser, where OR binds looser than AND which binds looser than NOT
+struct MatchRuleParser {
+    tokens: Vec<Token>,
+    position: usize,
+
+    //how many parentheses and NOTs enclose the token being parsed
+    nesting_depth: usize,
+
+    predicates_count: usize,
+}
+
+impl MatchRuleParser {
+    fn peek(&self) -> Option<&Token> {
+        return self.tokens.get(self.position);
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        return token;
+    }
+
+    fn is_next_keyword(&self, keyword: &str) -> bool {
+        match self.peek() {
+            Some(Token::Identifier(identifier)) => identifier.eq_ignore_ascii_case(keyword),
+            _ => false,
+        }
+    }
+
+    fn expect(&mut self, expected_token: Token) -> Result<(), String> {
+        match self.next() {
+            Some(token) if token == expected_token => Ok(()),
+            Some(token) => Err(format!(
+                "expected [{}] but found [{}]",
+                expected_token, token
+            )),
+            None => Err(format!("expected [{}] but the rule ended", expected_token)),
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<MatchRule, String> {
+        let mut match_rule = self.parse_and()?;
+        while self.is_next_keyword("OR") {
+            self.next();
+            match_rule = MatchRule::Or(Box::new(match_rule), Box::new(self.parse_and()?));
+        }
+        return Ok(match_rule);
+    }
+
+    fn parse_and(&mut self) -> Result<MatchRule, String> {
+        let mut match_rule = self.parse_not()?;
+        while self.is_next_keyword("AND") {
+            self.next();
+            match_rule = MatchRule::And(Box::new(match_rule), Box::new(self.parse_not()?));
+        }
+        return Ok(match_rule);
+    }
+
+    fn parse_not(&mut self) -> Result<MatchRule, String> {
+        if self.is_next_keyword("NOT") {
+            self.next();
+            self.enter_nesting()?;
+            let match_rule = MatchRule::Not(Box::new(self.parse_not()?));
+            self.nesting_depth -= 1;
+            return Ok(match_rule);
+        }
+
+        if self.peek() == Some(&Token::OpenParenthesis) {
+            self.next();
+            self.enter_nesting()?;
+            let match_rule = self.parse_or()?;
+            self.expect(Token::CloseParenthesis)?;
+            self.nesting_depth -= 1;
+            return Ok(match_rule);
+        }
+
+        return self.parse_predicate();
+    }
+
+    //fails the rule before it is nested deeper than it may be
+    fn enter_nesting(&mut self) -> Result<(), String> {
+        self.nesting_depth += 1;
+        if self.nesting_depth > MAX_MATCH_RULE_NESTING_DEPTH {
+            return Err(format!(
+                "parentheses and NOTs are nested more than [{}] deep",
+                MAX_MATCH_RULE_NESTING_DEPTH
+            ));
+        }
+        return Ok(());
+    }
+
+    fn parse_predicate(&mut self) -> Result<MatchRule, String> {
+        self.predicates_count += 1;
+        if self.predicates_count > MAX_MATCH_RULE_PREDICATES {
+            return Err(format!(
+                "the rule has more than [{}] predicates",
+                MAX_MATCH_RULE_PREDICATES
+            ));
+        }
+
+        let function_name = match self.next() {
+            Some(Token::Identifier(identifier)) => identifier,
+            Some(token) => return Err(format!("expected a function but found [{}]", token)),
+            None => return Err(String::from("expected a function but the rule ended")),
+        };
+
+        let function = MatchRuleFunction::from_name(&function_name)
+            .ok_or(format!("unknown function [{}]", function_name))?;
+
+        self.expect(Token::OpenParenthesis)?;
+        let primary_file_column = self.parse_column_reference("primary")?;
+        self.expect(Token::Comma)?;
+        let comparison_file_column = self.parse_column_reference("comparison")?;
+
+        let mut argument = None;
+        if function.requires_argument() {
+            self.expect(Token::Comma)?;
+            argument = match self.next() {
+                Some(Token::Number(number)) if number >= 0.0 => Some(number),
+                _ => {
+                    return Err(format!(
+                        "[{}] requires a number that is not negative as its last argument",
+                        function.get_name()
+                    ))
+                }
+            };
+        }
+
+        self.expect(Token::CloseParenthesis)?;
+
+        return Ok(MatchRule::Predicate(MatchRulePredicate {
+            function,
+            primary_file_column,
+            comparison_file_column,
+            argument,
+        }));
+    }
+
+    //the primary file column always comes first and the comparison file column second
+    fn parse_column_reference(&mut self, file_name: &str) -> Result<ColumnReference, String> {
+        match self.next() {
+            Some(Token::Identifier(identifier)) if identifier.eq_ignore_ascii_case(file_name) => (),
+            _ => return Err(format!("expected a [{}] column", file_name)),
+        }
+
+        self.expect(Token::OpenBracket)?;
+
+        let column_reference = match self.next() {
+            Some(Token::Number(number)) if number >= 0.0 && number.fract() == 0.0 => {
+                ColumnReference::Index(number as usize)
+            }
+            Some(Token::Text(header)) => ColumnReference::Header(header),
+            _ => {
+                return Err(format!(
+                    "a [{}] column is addressed by its index or its header in double quotes",
+                    file_name
+                ))
+            }
+        };
+
+        self.expect(Token::CloseBracket)?;
+        return Ok(column_reference);
+    }
+}
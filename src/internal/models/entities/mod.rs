@@ -0,0 +1,17 @@
+pub mod chunk_content_type;
+pub mod chunk_payload_limits;
+pub mod comparison_pair_options;
+pub mod currency_conversion;
+pub mod derived_column;
+pub mod file_chunk_claim_check;
+pub mod header_name_comparison_pair;
+pub mod match_pass;
+pub mod match_rule;
+pub mod recon_result_reason;
+pub mod reconciliation_options;
+pub mod row_format;
+pub mod row_reconciliation_details;
+pub mod upload_progress;
+pub mod value_mapping;
+pub mod value_normalization;
+pub mod value_transform;
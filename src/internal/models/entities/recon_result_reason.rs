@@ -0,0 +1,105 @@
+use serde::{Deserialize, Serialize};
+
+use super::{comparison_pair_options::MismatchSeverity, currency_conversion::FxConversion};
+
+//a structured version of one of the recon_result_reasons of a row
+//so that consumers do not have to parse the reason text
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct ReconResultReason {
+    pub reason_code: ReconResultReasonCode,
+
+    #[serde(default)]
+    pub primary_file_column_header: String,
+
+    #[serde(default)]
+    pub primary_file_column_index: Option<usize>,
+
+    #[serde(default)]
+    pub primary_file_column_value: String,
+
+    #[serde(default)]
+    pub comparison_file_column_header: String,
+
+    #[serde(default)]
+    pub comparison_file_column_index: Option<usize>,
+
+    #[serde(default)]
+    pub comparison_file_column_value: String,
+
+    //how the 2 column values were compared
+    #[serde(default)]
+    pub comparator: Option<ColumnComparator>,
+
+    //how much the mismatch matters to the outcome of the row
+    #[serde(default)]
+    pub severity: Option<MismatchSeverity>,
+
+    //the row in the comparison file that the primary file row was compared with
+    #[serde(default)]
+    pub matched_comparison_row_number: Option<u64>,
+
+    //the id of the comparison file chunk the matched row came from
+    #[serde(default)]
+    pub comparison_file_chunk_id: String,
+
+    //the rates used when the amounts were compared in a base currency
+    #[serde(default)]
+    pub fx_conversion: Option<FxConversion>,
+
+    //the outcome of every predicate of the match rule that was evaluated
+    #[serde(default)]
+    pub match_rule_trace: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub enum ReconResultReasonCode {
+    //no reason code was set. every reason a row is given sets its own code,
+    //so this only stands in for one that was left out by mistake
+    #[default]
+    Unspecified,
+
+    //the row could not be read into columns using its row format
+    UnreadableRow,
+
+    //a column referenced by a comparison pair does not exist in one of the rows
+    ColumnMissing,
+
+    //the paired columns have different values
+    ColumnValueMismatch,
+
+    //a row that was already matched has the same identifiers as a row
+    //in a later comparison file chunk
+    DuplicateComparisonRow,
+
+    //the comparison file column value is not in the value mapping table of the pair
+    UnmappedValue,
+
+    //a derived column could not be worked out from the other columns of the row
+    UnderivableColumn,
+
+    //the paired amounts could not be converted into the base currency
+    CurrencyConversionFailed,
+
+    //the match rule matched the row with a comparison file row
+    MatchRuleSatisfied,
+
+    //the match rule rejected a comparison file row with the same identifiers
+    MatchRuleNotSatisfied,
+
+    //the row matches several comparison rows equally well, or a comparison row
+    //matches several primary file rows equally well, so it was not matched
+    AmbiguousMatch,
+}
+
+//how 2 column values are compared
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum ColumnComparator {
+    Equals,
+    EqualsIgnoringCase,
+
+    //the amounts are equal, within the tolerance, once converted into a base currency
+    EqualsInBaseCurrency,
+
+    //the amounts are equal, within the tolerance
+    EqualsWithinTolerance,
+}
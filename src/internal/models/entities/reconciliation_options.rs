@@ -0,0 +1,158 @@
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::internal::shared_reconciler_rust_libraries::models::entities::recon_tasks_models::ComparisonPair;
+
+use super::{
+    comparison_pair_options::{
+        validate_comparison_pair_options, ColumnIndexComparisonPairOptions, ComparisonPairOptions,
+    },
+    derived_column::{validate_derived_columns, DerivedColumn},
+    header_name_comparison_pair::{
+        validate_header_name_comparison_pairs, HeaderNameComparisonPair,
+    },
+    match_pass::{validate_match_passes, MatchPass},
+    match_rule::validate_match_rule,
+    row_format::{validate_row_format, RowFormat},
+    value_normalization::NormalizationStep,
+};
+
+//processor specific reconciliation settings that travel
+//with the primary file chunk through the primary file queue
+#[derive(Serialize, Deserialize, Validate, Debug, Clone, PartialEq, Default)]
+pub struct ReconciliationOptions {
+    //how the raw data in the primary file rows is laid out
+    #[serde(default)]
+    #[validate(custom = "validate_row_format")]
+    pub primary_file_row_format: RowFormat,
+
+    //how the raw data in the comparison file rows is laid out
+    #[serde(default)]
+    #[validate(custom = "validate_row_format")]
+    pub comparison_file_row_format: RowFormat,
+
+    //columns worked out from the other columns of each primary file row
+    #[serde(default)]
+    #[validate(custom = "validate_derived_columns")]
+    pub primary_file_derived_columns: Vec<DerivedColumn>,
+
+    //columns worked out from the other columns of each comparison file row
+    #[serde(default)]
+    #[validate(custom = "validate_derived_columns")]
+    pub comparison_file_derived_columns: Vec<DerivedColumn>,
+
+    //comparison pairs that are resolved to column indexes using the
+    //column headers of both chunks at the time of reconciliation
+    #[serde(default)]
+    #[validate(custom = "validate_header_name_comparison_pairs")]
+    pub header_name_comparison_pairs: Vec<HeaderNameComparisonPair>,
+
+    //extra settings for the index based comparison pairs of the chunk
+    #[serde(default)]
+    #[validate(custom = "validate_comparison_pair_options")]
+    pub comparison_pair_options: Vec<ColumnIndexComparisonPairOptions>,
+
+    //when set, a row stops being compared at the first column that does not match
+    //otherwise every mismatching column in the row is recorded
+    #[serde(default)]
+    pub should_stop_at_first_column_mismatch: bool,
+
+    //what happens to rows that were already successfully reconciled
+    //when the primary file chunk meets the next comparison file chunk
+    #[serde(default)]
+    pub successful_row_handling: SuccessfulRowHandling,
+
+    //what happens when several comparison rows have the same identifiers as a primary file row
+    #[serde(default)]
+    pub candidate_assignment: CandidateAssignment,
+
+    //normalization steps applied to both column values of every comparison pair,
+    //identifiers included, unless the pair has its own normalization steps
+    #[serde(default)]
+    pub normalization_steps: Vec<NormalizationStep>,
+
+    //when set, rows are matched using this rule instead of the non identifier
+    //comparison pairs e.g amount_within_percent(primary[1], comparison[1], 0.5)
+    #[serde(default)]
+    #[validate(custom = "validate_match_rule")]
+    pub match_rule: Option<String>,
+
+    //when set, rows are matched in several passes, in order, with the rows left pending
+    //by one pass falling through to the next e.g exact reference, then amount and date
+    #[serde(default)]
+    #[validate(custom = "validate_match_passes")]
+    pub match_passes: Vec<MatchPass>,
+}
+
+impl ReconciliationOptions {
+    //gets the extra settings of a comparison pair, or the defaults if it has none
+    pub fn get_comparison_pair_options(&self, pair: &ComparisonPair) -> ComparisonPairOptions {
+        return self
+            .comparison_pair_options
+            .iter()
+            .find(|pair_options| {
+                pair_options.primary_file_column_index == pair.primary_file_column_index
+                    && pair_options.comparison_file_column_index
+                        == pair.comparison_file_column_index
+            })
+            .map(|pair_options| pair_options.options.clone())
+            .unwrap_or_default();
+    }
+
+    //gets the normalization steps of a comparison pair, falling back to the ones set for the chunk
+    pub fn get_normalization_steps(
+        &self,
+        pair_options: &ComparisonPairOptions,
+    ) -> Vec<NormalizationStep> {
+        return pair_options
+            .normalization_steps
+            .clone()
+            .unwrap_or(self.normalization_steps.clone());
+    }
+
+    //the extra settings of every comparison pair, including the ones of the match passes
+    pub fn get_all_comparison_pair_options_mut(&mut self) -> Vec<&mut ComparisonPairOptions> {
+        let mut all_pair_options: Vec<&mut ComparisonPairOptions> = self
+            .comparison_pair_options
+            .iter_mut()
+            .map(|pair_options| &mut pair_options.options)
+            .collect();
+
+        for match_pass in self.match_passes.iter_mut() {
+            all_pair_options.extend(
+                match_pass
+                    .comparison_pair_options
+                    .iter_mut()
+                    .map(|pair_options| &mut pair_options.options),
+            );
+        }
+
+        return all_pair_options;
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub enum SuccessfulRowHandling {
+    //the first comparison row a primary row is matched with is final
+    //and the row is not compared with any later comparison file chunks
+    #[default]
+    FirstMatchWins,
+
+    //the row is checked against every comparison file chunk and fails
+    //if another comparison file chunk has a row with the same identifiers
+    CheckAgainstAllComparisonChunks,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub enum CandidateAssignment {
+    //the row is compared with every comparison row that has the same identifiers
+    #[default]
+    CompareEveryCandidate,
+
+    //every candidate is scored by its mismatching columns and the distance between the
+    //pairs compared as amounts, and each comparison row is assigned to at most one row of
+    //the primary file chunk. chunks are reconciled independently, so rows in different
+    //primary file chunks can still be assigned the same comparison row.
+    //rows whose best candidates tie are failed as ambiguous
+    BestMatch,
+}
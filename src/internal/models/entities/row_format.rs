@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+use validator::ValidationError;
+
+//describes how the raw data of each row in a file chunk
+//is laid out, so that it can be projected into comparable columns
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub enum RowFormat {
+    //the columns are separated by a delimiter and have already
+    //been parsed into parsed_columns_from_row by the uploader
+    #[default]
+    Delimited,
+
+    //each column occupies a fixed range of characters in the row
+    FixedWidth {
+        columns: Vec<FixedWidthColumn>,
+    },
+
+    //each row is a json document and each column is read
+    //from it using a json pointer e.g /payment/amount.
+    //a pointer to an explicit null reads as an empty column, while a pointer to a value
+    //that is not in the document, including one under a null parent, fails the row
+    JsonLines {
+        column_paths: Vec<String>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct FixedWidthColumn {
+    //zero based index of the first character of the column
+    pub start: usize,
+
+    //number of characters in the column
+    pub length: usize,
+}
+
+//checks that a row format has enough information to project a row into columns
+pub fn validate_row_format(row_format: &RowFormat) -> Result<(), ValidationError> {
+    match row_format {
+        RowFormat::Delimited => Ok(()),
+
+        RowFormat::FixedWidth { columns } => {
+            if columns.is_empty() || columns.iter().any(|column| column.length == 0) {
+                return Err(ValidationError::new(
+                    "fixed width row format requires columns with a length greater than zero",
+                ));
+            }
+            Ok(())
+        }
+
+        RowFormat::JsonLines { column_paths } => {
+            //a json pointer is either empty (the whole document) or starts with a slash
+            if column_paths.is_empty()
+                || column_paths
+                    .iter()
+                    .any(|path| !path.is_empty() && !path.starts_with('/'))
+            {
+                return Err(ValidationError::new(
+                    "json lines row format requires column paths that are valid json pointers",
+                ));
+            }
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::recon_result_reason::ReconResultReason;
+
+//structured reconciliation results for a single row in the primary file chunk
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct RowReconciliationDetails {
+    pub row_number: u64,
+
+    //a finer grained outcome than the recon_result of the row
+    #[serde(default)]
+    pub recon_outcome: ReconOutcome,
+
+    //the row in the comparison file that this row was successfully reconciled against
+    #[serde(default)]
+    pub matched_comparison_row_number: Option<u64>,
+
+    //the redis stream id of the comparison file chunk the matched row came from
+    #[serde(default)]
+    pub matched_comparison_file_chunk_id: Option<String>,
+
+    //the upload request of the comparison file the matched row came from
+    #[serde(default)]
+    pub matched_comparison_upload_request_id: Option<String>,
+
+    //the name of the match pass that matched the row, when matching in passes.
+    //a row that is failed names the first pass that failed it
+    #[serde(default)]
+    pub match_pass_name: Option<String>,
+
+    //structured versions of the recon_result_reasons of the row
+    #[serde(default)]
+    pub recon_result_reasons: Vec<ReconResultReason>,
+
+    //whether the columns of the row were read out of its raw data using the row format
+    //of its file, so that a requeued row is not read again
+    #[serde(default)]
+    pub is_projected: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub enum ReconOutcome {
+    #[default]
+    Pending,
+
+    //every paired column matched, or only informational columns did not
+    Matched,
+
+    //the row matched but columns with a warning severity did not
+    MatchedWithWarnings,
+
+    Failed,
+}
+
+impl RowReconciliationDetails {
+    //gets the details of the row with this row number,
+    //adding empty details for the row if there are none yet
+    pub fn find_or_insert(
+        rows_reconciliation_details: &mut Vec<RowReconciliationDetails>,
+        row_number: u64,
+    ) -> &mut RowReconciliationDetails {
+        let position = rows_reconciliation_details
+            .iter()
+            .position(|row_details| row_details.row_number == row_number);
+
+        match position {
+            Some(index) => &mut rows_reconciliation_details[index],
+            None => {
+                rows_reconciliation_details.push(RowReconciliationDetails {
+                    row_number,
+                    ..RowReconciliationDetails::default()
+                });
+                rows_reconciliation_details.last_mut().unwrap()
+            }
+        }
+    }
+}
+
+//the details of the rows of a chunk by row number, so that the details of a row
+//are found without searching through the details of every other row
+pub struct RowsReconciliationDetailsIndex<'a> {
+    rows_reconciliation_details: &'a mut Vec<RowReconciliationDetails>,
+    positions_by_row_number: HashMap<u64, usize>,
+}
+
+impl<'a> RowsReconciliationDetailsIndex<'a> {
+    pub fn new(
+        rows_reconciliation_details: &'a mut Vec<RowReconciliationDetails>,
+    ) -> RowsReconciliationDetailsIndex<'a> {
+        let mut positions_by_row_number = HashMap::with_capacity(rows_reconciliation_details.len());
+
+        //a row with more than one set of details is found by its first one
+        for (position, row_details) in rows_reconciliation_details.iter().enumerate() {
+            positions_by_row_number
+                .entry(row_details.row_number)
+                .or_insert(position);
+        }
+
+        return RowsReconciliationDetailsIndex {
+            rows_reconciliation_details,
+            positions_by_row_number,
+        };
+    }
+
+    //gets the details of the row with this row number, if it has any
+    pub fn get(&self, row_number: u64) -> Option<&RowReconciliationDetails> {
+        return self
+            .positions_by_row_number
+            .get(&row_number)
+            .map(|position| &self.rows_reconciliation_details[*position]);
+    }
+
+    //gets the details of the row with this row number,
+    //adding empty details for the row if there are none yet
+    pub fn find_or_insert(&mut self, row_number: u64) -> &mut RowReconciliationDetails {
+        let rows_reconciliation_details = &mut *self.rows_reconciliation_details;
+
+        let position = *self
+            .positions_by_row_number
+            .entry(row_number)
+            .or_insert_with(|| {
+                rows_reconciliation_details.push(RowReconciliationDetails {
+                    row_number,
+                    ..RowReconciliationDetails::default()
+                });
+                rows_reconciliation_details.len() - 1
+            });
+
+        return &mut rows_reconciliation_details[position];
+    }
+}
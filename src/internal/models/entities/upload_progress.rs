@@ -0,0 +1,122 @@
+use std::collections::HashMap;
+
+use crate::internal::shared_reconciler_rust_libraries::models::entities::file_upload_chunk::FileUploadChunk;
+
+//what is kept in redis about how far reconciliation of an upload request has got.
+//chunks are keyed by their sequence number, which unlike their id stays the same
+//every time a primary file chunk is requeued
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UploadProgress {
+    //the sequence number of the last comparison file chunk
+    //each primary file chunk has been reconciled against
+    pub primary_file_chunks: HashMap<i64, i64>,
+
+    //the rows of each primary file chunk sent to the recon results queue, by recon status
+    pub completed_primary_file_chunks: HashMap<i64, HashMap<String, u64>>,
+
+    //the sequence number of the last primary file chunk of the upload request,
+    //which is only known once that chunk has been seen
+    pub last_primary_chunk_sequence_number: Option<i64>,
+
+    //the sequence number the file splitter gives the first chunk of both files.
+    //it comes from configuration rather than from the chunks recorded so far,
+    //since those are recorded in whatever order they happen to be reconciled
+    pub first_chunk_sequence_number: i64,
+}
+
+impl UploadProgress {
+    //how many primary file chunks the upload request has, once its last one has been seen
+    pub fn get_primary_file_chunks_total(&self) -> Option<u64> {
+        return self
+            .last_primary_chunk_sequence_number
+            .map(|last_primary_chunk_sequence_number| {
+                count_sequence_numbers_up_to(
+                    self.first_chunk_sequence_number,
+                    last_primary_chunk_sequence_number,
+                )
+            });
+    }
+
+    //comparison file chunks are consumed in order, so every chunk up to
+    //the last one a primary file chunk got to has been consumed by it
+    pub fn count_comparison_file_chunks_consumed(&self) -> u64 {
+        return self
+            .primary_file_chunks
+            .values()
+            .map(|comparison_chunk_sequence_number| {
+                count_sequence_numbers_up_to(
+                    self.first_chunk_sequence_number,
+                    *comparison_chunk_sequence_number,
+                )
+            })
+            .sum();
+    }
+
+    //the rows of the completed primary file chunks by recon status
+    pub fn count_completed_rows_by_recon_status(&self) -> HashMap<String, u64> {
+        let mut rows_by_recon_status: HashMap<String, u64> = HashMap::new();
+
+        for completed_primary_file_chunk in self.completed_primary_file_chunks.values() {
+            for (recon_status, row_count) in completed_primary_file_chunk {
+                *rows_by_recon_status
+                    .entry(recon_status.clone())
+                    .or_insert(0) += row_count;
+            }
+        }
+
+        return rows_by_recon_status;
+    }
+
+    //whether every primary file chunk of the upload request has been sent to the recon results queue,
+    //going by the completed chunks numbered from the first sequence number up to the last one
+    pub fn is_completed(&self) -> bool {
+        let last_primary_chunk_sequence_number = match self.last_primary_chunk_sequence_number {
+            Some(last_primary_chunk_sequence_number) => last_primary_chunk_sequence_number,
+            None => return false,
+        };
+        let first_chunk_sequence_number = self.first_chunk_sequence_number;
+
+        let completed_primary_file_chunks_count = self
+            .completed_primary_file_chunks
+            .keys()
+            .filter(|primary_chunk_sequence_number| {
+                (first_chunk_sequence_number..=last_primary_chunk_sequence_number)
+                    .contains(*primary_chunk_sequence_number)
+            })
+            .count() as u64;
+
+        return completed_primary_file_chunks_count
+            == count_sequence_numbers_up_to(
+                first_chunk_sequence_number,
+                last_primary_chunk_sequence_number,
+            );
+    }
+}
+
+fn count_sequence_numbers_up_to(
+    first_chunk_sequence_number: i64,
+    last_chunk_sequence_number: i64,
+) -> u64 {
+    return (last_chunk_sequence_number - first_chunk_sequence_number + 1).max(0) as u64;
+}
+
+//counts the rows of a file chunk by their recon status e.g Successful or Failed
+pub fn count_rows_by_recon_status(file_chunk: &FileUploadChunk) -> HashMap<String, u64> {
+    let mut rows_by_recon_status: HashMap<String, u64> = HashMap::new();
+
+    for row in file_chunk.chunk_rows.iter() {
+        *rows_by_recon_status
+            .entry(format!("{:?}", row.recon_result))
+            .or_insert(0) += 1;
+    }
+
+    return rows_by_recon_status;
+}
+
+//the sequence number of the last primary file chunk of an upload request, if this is that chunk
+pub fn get_last_primary_chunk_sequence_number(primary_file_chunk: &FileUploadChunk) -> Option<i64> {
+    return match primary_file_chunk.is_last_chunk {
+        true => Some(primary_file_chunk.chunk_sequence_number),
+        false => None,
+    };
+}
@@ -0,0 +1,29 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+//a lookup table that translates values from the vocabulary of
+//the comparison file into the vocabulary of the primary file e.g "D" => "DR"
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum ValueMappingTable {
+    //the entries are supplied with the reconciliation options
+    Inline { entries: HashMap<String, String> },
+
+    //the entries are loaded from a redis hash, where each field
+    //is a comparison file value and its value is the primary file value
+    RedisHash { key: String },
+}
+
+impl ValueMappingTable {
+    //gets the primary file value of a comparison file value, the value is trimmed
+    //first since delimited files usually keep the white space around columns.
+    //returns None if the value is not in the table or the table has not been loaded yet
+    pub fn get_mapped_value(&self, comparison_file_column_value: &String) -> Option<String> {
+        match self {
+            ValueMappingTable::Inline { entries } => entries
+                .get(comparison_file_column_value.trim())
+                .map(|s| s.to_owned()),
+            ValueMappingTable::RedisHash { .. } => None,
+        }
+    }
+}
@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+
+//a step applied to a column value before it is compared,
+//the steps are applied to both values in the order they are listed
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum NormalizationStep {
+    //full unicode case folding e.g. "Straße" and "STRASSE" become the same
+    UnicodeCaseFolding,
+
+    //unicode NFKC normalization e.g. full width digits become ascii digits
+    Nfkc,
+
+    //trims the value and replaces every run of white space (including
+    //non breaking spaces) with a single space
+    CollapseWhiteSpace,
+
+    //removes every character that is neither alphanumeric nor white space
+    StripPunctuation,
+
+    //removes the zeros at the start of the value, keeping a single zero
+    //if the value is made up of only zeros
+    RemoveLeadingZeros,
+}
@@ -0,0 +1,60 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use validator::ValidationError;
+
+//rewrites a column value using a regular expression before it is compared
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum ValueTransform {
+    //replaces the value with a capture group of the first match of the pattern
+    //e.g the pattern "^TXN/\d+/0*(\d+)$" turns "TXN/2022/000123" into "123",
+    //the value is kept as is if the pattern does not match it
+    RegexCapture {
+        pattern: String,
+
+        #[serde(default = "default_capture_group")]
+        capture_group: usize,
+    },
+
+    //replaces every match of the pattern in the value, the replacement
+    //can reference capture groups e.g "$1"
+    RegexReplace {
+        pattern: String,
+        replacement: String,
+    },
+}
+
+fn default_capture_group() -> usize {
+    return 1;
+}
+
+impl ValueTransform {
+    pub fn get_pattern(&self) -> &String {
+        match self {
+            ValueTransform::RegexCapture { pattern, .. } => pattern,
+            ValueTransform::RegexReplace { pattern, .. } => pattern,
+        }
+    }
+}
+
+//checks that the pattern of a value transform compiles and has the capture group it refers to
+pub fn validate_value_transform(value_transform: &ValueTransform) -> Result<(), ValidationError> {
+    let regex = match Regex::new(value_transform.get_pattern()) {
+        Ok(regex) => regex,
+        Err(_) => {
+            return Err(ValidationError::new(
+                "value transform requires a valid regular expression pattern",
+            ))
+        }
+    };
+
+    if let ValueTransform::RegexCapture { capture_group, .. } = value_transform {
+        //captures_len includes the implicit group 0 that is the whole match
+        if *capture_group >= regex.captures_len() {
+            return Err(ValidationError::new(
+                "value transform capture group does not exist in the pattern",
+            ));
+        }
+    }
+
+    return Ok(());
+}
@@ -1,9 +1,24 @@
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
-use crate::internal::shared_reconciler_rust_libraries::models::entities::file_upload_chunk::FileUploadChunk;
+use crate::internal::{
+    models::entities::{
+        reconciliation_options::ReconciliationOptions,
+        row_reconciliation_details::RowReconciliationDetails,
+    },
+    shared_reconciler_rust_libraries::models::entities::file_upload_chunk::FileUploadChunk,
+};
 
-#[derive(Serialize, Deserialize, Validate, Debug)]
+#[derive(Serialize, Deserialize, Validate, Debug, Clone)]
 pub struct ReconcileFileChunkRequest {
     pub primary_file_chunk: FileUploadChunk,
+
+    #[serde(default)]
+    #[validate]
+    pub reconciliation_options: ReconciliationOptions,
+
+    //structured results for the rows of the primary file chunk
+    //gathered from the comparison file chunks it has met so far
+    #[serde(default)]
+    pub rows_reconciliation_details: Vec<RowReconciliationDetails>,
 }
@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct LivenessResponse {
+    pub is_alive: bool,
+}
+
+//the processor is only ready to take file chunks when it can reach every dependency
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ReadinessResponse {
+    pub is_ready: bool,
+    pub dependencies: Vec<DependencyStatus>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct DependencyStatus {
+    //e.g redis or dapr
+    pub name: String,
+
+    pub is_ready: bool,
+
+    //what was found when checking the dependency, or why it could not be reached
+    pub details: String,
+
+    pub response_time_in_milliseconds: u64,
+}
@@ -1,2 +1,6 @@
+pub mod health_check_response;
 pub mod reconcile_file_chunk_response;
+pub mod reconciled_file_chunk;
 pub mod redis_stream_message;
+pub mod upload_progress_response;
+pub mod upload_reconciliation_completed_event;
@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+
+use crate::internal::{
+    models::entities::row_reconciliation_details::RowReconciliationDetails,
+    shared_reconciler_rust_libraries::models::entities::file_upload_chunk::FileUploadChunk,
+};
+
+//a fully reconciled primary file chunk as published to the recon results queue
+//the chunk fields are flattened so consumers that only know about
+//the file upload chunk can keep reading it as before
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ReconciledFileChunk {
+    #[serde(flatten)]
+    pub file_chunk: FileUploadChunk,
+
+    #[serde(default)]
+    pub rows_reconciliation_details: Vec<RowReconciliationDetails>,
+}
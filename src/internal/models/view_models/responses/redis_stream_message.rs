@@ -9,7 +9,17 @@ pub struct RedisStreamMessage {
     #[serde(rename = "type")]
     pub type_field: String,
     pub source: String,
-    pub data: FileUploadChunk,
+
+    //chunks published as json
+    #[serde(default)]
+    pub data: Option<FileUploadChunk>,
+
+    //chunks published in a binary encoding such as messagepack
+    #[serde(default, rename = "data_base64")]
+    pub data_base64: Option<String>,
+
+    #[serde(default)]
+    pub datacontenttype: String,
     pub pubsubname: String,
     pub specversion: String,
     pub traceid: String,
@@ -0,0 +1,25 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UploadProgressResponse {
+    pub upload_request_id: String,
+
+    //primary file chunks that have been reconciled against at least one comparison file chunk
+    pub primary_file_chunks_seen: u64,
+
+    //primary file chunks that have been sent to the recon results queue
+    pub primary_file_chunks_completed: u64,
+
+    //how many primary file chunks the upload request has, once its last one has been seen
+    pub primary_file_chunks_total: Option<u64>,
+
+    //whether every primary file chunk has been sent to the recon results queue
+    pub is_completed: bool,
+
+    //comparison file chunks reconciled against, summed across the primary file chunks
+    pub comparison_file_chunks_consumed: u64,
+
+    //rows of the completed primary file chunks by recon status e.g Successful or Failed
+    pub rows_by_recon_status: HashMap<String, u64>,
+}
@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+//published once every primary file chunk of an upload request is in the recon results queue
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct UploadReconciliationCompletedEvent {
+    pub upload_request_id: String,
+
+    pub primary_file_chunks_total: u64,
+
+    //comparison file chunks reconciled against, summed across the primary file chunks
+    pub comparison_file_chunks_consumed: u64,
+
+    pub rows_total: u64,
+
+    //rows of the upload request by recon status e.g Successful or Failed
+    pub rows_by_recon_status: HashMap<String, u64>,
+
+    pub date_completed: i64,
+}
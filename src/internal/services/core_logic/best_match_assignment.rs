@@ -0,0 +1,133 @@
+use std::collections::{HashMap, HashSet};
+
+//how well a comparison file row matches a primary file row, where a smaller score is a better match
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct MatchCandidateScore {
+    pub has_critical_mismatch: bool,
+    pub mismatching_column_count: usize,
+
+    //the total difference between the paired amounts, in millionths,
+    //so that amounts that only differ in floating point noise tie
+    pub amount_distance: u64,
+}
+
+impl MatchCandidateScore {
+    pub fn new(
+        has_critical_mismatch: bool,
+        mismatching_column_count: usize,
+        amount_distance: f64,
+    ) -> MatchCandidateScore {
+        return MatchCandidateScore {
+            has_critical_mismatch,
+            mismatching_column_count,
+            amount_distance: (amount_distance.abs() * 1_000_000.0).round() as u64,
+        };
+    }
+}
+
+//a comparison file row with the same identifiers as a primary file row
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatchCandidate {
+    pub primary_file_row_index: usize,
+    pub comparison_file_row_index: usize,
+    pub score: MatchCandidateScore,
+}
+
+//a primary file row whose best candidates scored the same, so it cannot be matched without guessing
+#[derive(Debug, Clone, PartialEq)]
+pub struct AmbiguousMatch {
+    pub primary_file_row_index: usize,
+
+    //the comparison rows the primary file row matches equally well
+    pub comparison_file_row_indexes: Vec<usize>,
+
+    //the primary file rows that match the comparison row equally well,
+    //when the tie is for a single comparison row
+    pub competing_primary_file_row_indexes: Vec<usize>,
+}
+
+//assigns each primary file row its best candidate, so that every comparison row is assigned
+//to at most one primary file row. the best scoring candidates are assigned first and a tie
+//for the best score, on either side, is reported instead of being resolved arbitrarily.
+//primary file rows whose candidates all went to better matching rows are left unassigned
+pub fn assign_best_matches(
+    candidates: Vec<MatchCandidate>,
+) -> (Vec<MatchCandidate>, Vec<AmbiguousMatch>) {
+    //the candidates are sorted once, best score first. the sort is stable
+    //so candidates with the same score are dealt with in the order they came in
+    let mut sorted_candidates = candidates;
+    sorted_candidates.sort_by(|candidate, other| candidate.score.cmp(&other.score));
+
+    let mut resolved_primary_file_row_indexes: HashSet<usize> = HashSet::new();
+    let mut assigned_comparison_file_row_indexes: HashSet<usize> = HashSet::new();
+    let mut assigned_candidates: Vec<MatchCandidate> = vec![];
+    let mut ambiguous_matches: Vec<AmbiguousMatch> = vec![];
+
+    let mut same_score_start = 0;
+
+    while same_score_start < sorted_candidates.len() {
+        let best_score = &sorted_candidates[same_score_start].score;
+        let same_score_end = sorted_candidates[same_score_start..]
+            .iter()
+            .position(|candidate| candidate.score != *best_score)
+            .map(|same_score_count| same_score_start + same_score_count)
+            .unwrap_or(sorted_candidates.len());
+
+        //the rows dealt with at a better score drop out, and so do the comparison rows they were given
+        let best_candidates: Vec<&MatchCandidate> = sorted_candidates
+            [same_score_start..same_score_end]
+            .iter()
+            .filter(|candidate| {
+                !resolved_primary_file_row_indexes.contains(&candidate.primary_file_row_index)
+                    && !assigned_comparison_file_row_indexes
+                        .contains(&candidate.comparison_file_row_index)
+            })
+            .collect();
+
+        let mut comparison_file_row_indexes_by_primary: HashMap<usize, Vec<usize>> = HashMap::new();
+        let mut primary_file_row_indexes_by_comparison: HashMap<usize, Vec<usize>> = HashMap::new();
+
+        for candidate in &best_candidates {
+            comparison_file_row_indexes_by_primary
+                .entry(candidate.primary_file_row_index)
+                .or_default()
+                .push(candidate.comparison_file_row_index);
+            primary_file_row_indexes_by_comparison
+                .entry(candidate.comparison_file_row_index)
+                .or_default()
+                .push(candidate.primary_file_row_index);
+        }
+
+        for candidate in &best_candidates {
+            if !resolved_primary_file_row_indexes.insert(candidate.primary_file_row_index) {
+                continue;
+            }
+
+            let tied_comparison_file_row_indexes =
+                &comparison_file_row_indexes_by_primary[&candidate.primary_file_row_index];
+            let tied_primary_file_row_indexes =
+                &primary_file_row_indexes_by_comparison[&candidate.comparison_file_row_index];
+
+            if tied_comparison_file_row_indexes.len() == 1
+                && tied_primary_file_row_indexes.len() == 1
+            {
+                assigned_comparison_file_row_indexes.insert(candidate.comparison_file_row_index);
+                assigned_candidates.push((*candidate).clone());
+                continue;
+            }
+
+            ambiguous_matches.push(AmbiguousMatch {
+                primary_file_row_index: candidate.primary_file_row_index,
+                competing_primary_file_row_indexes: match tied_comparison_file_row_indexes.len() {
+                    1 => tied_primary_file_row_indexes.clone(),
+                    _ => vec![],
+                },
+                comparison_file_row_indexes: tied_comparison_file_row_indexes.clone(),
+            });
+        }
+
+        same_score_start = same_score_end;
+    }
+
+    return (assigned_candidates, ambiguous_matches);
+}
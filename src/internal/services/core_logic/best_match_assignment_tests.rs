@@ -0,0 +1,118 @@
+use super::best_match_assignment::{
+    assign_best_matches, AmbiguousMatch, MatchCandidate, MatchCandidateScore,
+};
+
+#[actix_web::test]
+async fn test_assign_best_matches_gives_a_shared_comparison_row_to_the_better_matching_row() {
+    //setup
+    let candidates = vec![
+        build_candidate(0, 0, 5.0),
+        build_candidate(0, 1, 7.0),
+        build_candidate(1, 0, 1.0),
+    ];
+
+    //act
+    let (assigned_candidates, ambiguous_matches) = assign_best_matches(candidates);
+
+    //assert
+    assert_eq!(
+        assigned_candidates,
+        vec![build_candidate(1, 0, 1.0), build_candidate(0, 1, 7.0)]
+    );
+    assert_eq!(ambiguous_matches, vec![]);
+}
+
+#[actix_web::test]
+async fn test_assign_best_matches_reports_a_row_with_tied_best_candidates() {
+    //setup
+    let candidates = vec![
+        build_candidate(0, 0, 2.0),
+        build_candidate(0, 1, 2.0),
+        build_candidate(0, 2, 9.0),
+    ];
+
+    //act
+    let (assigned_candidates, ambiguous_matches) = assign_best_matches(candidates);
+
+    //assert
+    assert_eq!(assigned_candidates, vec![]);
+    assert_eq!(
+        ambiguous_matches,
+        vec![AmbiguousMatch {
+            primary_file_row_index: 0,
+            comparison_file_row_indexes: vec![0, 1],
+            competing_primary_file_row_indexes: vec![],
+        }]
+    );
+}
+
+#[actix_web::test]
+async fn test_assign_best_matches_reports_rows_tied_for_the_same_comparison_row() {
+    //setup
+    let candidates = vec![build_candidate(0, 0, 0.0), build_candidate(1, 0, 0.0)];
+
+    //act
+    let (assigned_candidates, ambiguous_matches) = assign_best_matches(candidates);
+
+    //assert
+    assert_eq!(assigned_candidates, vec![]);
+    assert_eq!(ambiguous_matches.len(), 2);
+    assert_eq!(
+        ambiguous_matches[1],
+        AmbiguousMatch {
+            primary_file_row_index: 1,
+            comparison_file_row_indexes: vec![0],
+            competing_primary_file_row_indexes: vec![0, 1],
+        }
+    );
+}
+
+#[actix_web::test]
+async fn test_assign_best_matches_given_candidates_in_any_order_assigns_best_scores_first() {
+    //setup
+    let candidates = vec![
+        build_candidate(2, 2, 9.0),
+        build_candidate(1, 1, 4.0),
+        build_candidate(2, 1, 3.0),
+        build_candidate(0, 0, 1.0),
+        build_candidate(1, 2, 6.0),
+    ];
+
+    //act
+    let (assigned_candidates, ambiguous_matches) = assign_best_matches(candidates);
+
+    //assert
+    assert_eq!(
+        assigned_candidates,
+        vec![
+            build_candidate(0, 0, 1.0),
+            build_candidate(2, 1, 3.0),
+            build_candidate(1, 2, 6.0),
+        ]
+    );
+    assert_eq!(ambiguous_matches, vec![]);
+}
+
+#[actix_web::test]
+async fn test_match_candidate_score_prefers_fewer_mismatches_over_a_smaller_amount_distance() {
+    //act
+    let fewer_mismatches = MatchCandidateScore::new(false, 0, 50.0);
+    let more_mismatches = MatchCandidateScore::new(false, 1, 0.0);
+    let critical_mismatch = MatchCandidateScore::new(true, 0, 0.0);
+
+    //assert
+    assert_eq!(fewer_mismatches < more_mismatches, true);
+    assert_eq!(more_mismatches < critical_mismatch, true);
+}
+
+fn build_candidate(
+    primary_file_row_index: usize,
+    comparison_file_row_index: usize,
+    amount_distance: f64,
+) -> MatchCandidate {
+    MatchCandidate {
+        primary_file_row_index,
+        comparison_file_row_index,
+        score: MatchCandidateScore::new(false, 0, amount_distance),
+    }
+}
@@ -0,0 +1,122 @@
+use serde::{de::DeserializeOwned, Serialize};
+use std::{borrow::Cow, io::Read};
+
+use crate::internal::{
+    models::entities::chunk_content_type::{ChunkContentType, ChunkEncoding},
+    shared_reconciler_rust_libraries::models::entities::app_errors::{AppError, AppErrorKind},
+};
+
+//the default level of zstd, which compresses chunks well without slowing down the requeue
+const ZSTD_COMPRESSION_LEVEL: i32 = 3;
+
+//encodes a file chunk payload using the given content type
+pub fn serialize_chunk_payload<T: Serialize>(
+    payload: &T,
+    content_type: &ChunkContentType,
+) -> Result<Vec<u8>, AppError> {
+    let serialize_result = match content_type.encoding {
+        ChunkEncoding::Json => serde_json::to_vec(payload).map_err(|e| e.to_string()),
+
+        //fields are written with their names so that, like json, a payload can be read
+        //by a service built against an older or newer version of the models
+        ChunkEncoding::MessagePack => rmp_serde::to_vec_named(payload).map_err(|e| e.to_string()),
+
+        ChunkEncoding::Cbor => {
+            let mut encoded_payload = vec![];
+            ciborium::ser::into_writer(payload, &mut encoded_payload)
+                .map(|_| encoded_payload)
+                .map_err(|e| e.to_string())
+        }
+    };
+
+    let encoded_payload = serialize_result.map_err(|e| {
+        AppError::new(
+            AppErrorKind::InternalError,
+            format!(
+                "unable to encode the file chunk as [{}]: {}",
+                content_type.to_content_type_header(),
+                e
+            ),
+        )
+    })?;
+
+    if !content_type.is_zstd_compressed {
+        return Ok(encoded_payload);
+    }
+
+    return zstd::stream::encode_all(encoded_payload.as_slice(), ZSTD_COMPRESSION_LEVEL).map_err(
+        |e| {
+            AppError::new(
+                AppErrorKind::InternalError,
+                format!("unable to compress the file chunk: {}", e),
+            )
+        },
+    );
+}
+
+//decodes a file chunk payload that was encoded using the given content type.
+//a compressed payload is rejected once it decompresses to more than the given number of
+//bytes, so that a small body cannot be inflated into more memory than any chunk needs
+pub fn deserialize_chunk_payload<T: DeserializeOwned>(
+    payload: &[u8],
+    content_type: &ChunkContentType,
+    max_decompressed_bytes: usize,
+) -> Result<T, AppError> {
+    let encoded_payload = match content_type.is_zstd_compressed {
+        false => Cow::Borrowed(payload),
+        true => Cow::Owned(decompress_chunk_payload(payload, max_decompressed_bytes)?),
+    };
+
+    let deserialize_result = match content_type.encoding {
+        ChunkEncoding::Json => serde_json::from_slice(&encoded_payload).map_err(|e| e.to_string()),
+        ChunkEncoding::MessagePack => {
+            rmp_serde::from_slice(&encoded_payload).map_err(|e| e.to_string())
+        }
+        ChunkEncoding::Cbor => {
+            ciborium::de::from_reader(encoded_payload.as_ref()).map_err(|e| e.to_string())
+        }
+    };
+
+    return deserialize_result.map_err(|e| {
+        AppError::new(
+            AppErrorKind::ResponseUnmarshalError,
+            format!(
+                "unable to decode the file chunk as [{}]: {}",
+                content_type.to_content_type_header(),
+                e
+            ),
+        )
+    });
+}
+
+fn decompress_chunk_payload(
+    payload: &[u8],
+    max_decompressed_bytes: usize,
+) -> Result<Vec<u8>, AppError> {
+    let to_unmarshal_error = |e: std::io::Error| {
+        AppError::new(
+            AppErrorKind::ResponseUnmarshalError,
+            format!("unable to decompress the file chunk: {}", e),
+        )
+    };
+
+    //one byte more than the limit is read, so a payload that goes over it can be told apart
+    let mut decompressed_payload = vec![];
+    zstd::stream::read::Decoder::new(payload)
+        .map_err(to_unmarshal_error)?
+        .take(max_decompressed_bytes as u64 + 1)
+        .read_to_end(&mut decompressed_payload)
+        .map_err(to_unmarshal_error)?;
+
+    if decompressed_payload.len() > max_decompressed_bytes {
+        return Err(AppError::new(
+            AppErrorKind::BadClientRequest,
+            format!(
+                "the file chunk decompresses to more than the limit of [{}] bytes",
+                max_decompressed_bytes
+            ),
+        ));
+    }
+
+    return Ok(decompressed_payload);
+}
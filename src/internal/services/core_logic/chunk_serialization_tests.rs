@@ -0,0 +1,298 @@
+use crate::internal::{
+    models::{
+        entities::{
+            chunk_content_type::{ChunkContentType, ChunkEncoding},
+            reconciliation_options::ReconciliationOptions,
+            row_reconciliation_details::{ReconOutcome, RowReconciliationDetails},
+        },
+        view_models::requests::reconcile_file_chunk_request::ReconcileFileChunkRequest,
+    },
+    shared_reconciler_rust_libraries::models::entities::{
+        app_errors::AppErrorKind,
+        file_chunk_queue::FileChunkQueue,
+        file_upload_chunk::{
+            FileUploadChunk, FileUploadChunkRow, FileUploadChunkSource, ReconStatus,
+        },
+        recon_tasks_models::{ComparisonPair, ReconciliationConfigs},
+    },
+};
+
+use super::chunk_serialization::{deserialize_chunk_payload, serialize_chunk_payload};
+
+//large enough for any of the requests the tests build
+const MAX_DECOMPRESSED_BYTES: usize = 16_777_216;
+
+#[actix_web::test]
+async fn test_parse_given_supported_content_types_returns_their_encoding() {
+    //setup
+    let content_types = vec![
+        ("", ChunkEncoding::Json, false),
+        ("json", ChunkEncoding::Json, false),
+        (
+            "application/json; charset=utf-8",
+            ChunkEncoding::Json,
+            false,
+        ),
+        ("text/json", ChunkEncoding::Json, false),
+        ("application/cloudevents+json", ChunkEncoding::Json, false),
+        (
+            "application/cloudevents+json; charset=utf-8",
+            ChunkEncoding::Json,
+            false,
+        ),
+        ("application/problem+json+zstd", ChunkEncoding::Json, true),
+        ("application/msgpack", ChunkEncoding::MessagePack, false),
+        ("application/x-msgpack", ChunkEncoding::MessagePack, false),
+        (
+            "application/vnd.msgpack+zstd",
+            ChunkEncoding::MessagePack,
+            true,
+        ),
+        ("Application/CBOR+zstd", ChunkEncoding::Cbor, true),
+    ];
+
+    for (content_type, encoding, is_zstd_compressed) in content_types {
+        //act
+        let actual = ChunkContentType::parse(content_type).unwrap();
+
+        //assert
+        let expected = ChunkContentType {
+            encoding,
+            is_zstd_compressed,
+        };
+        assert_eq!(actual, expected, "content type [{}]", content_type);
+    }
+}
+
+#[actix_web::test]
+async fn test_parse_given_unsupported_content_type_returns_bad_client_request() {
+    for content_type in [
+        "application/xml",
+        "application/octet-stream",
+        "application/jsonl",
+    ] {
+        //act
+        let actual = ChunkContentType::parse(content_type);
+
+        //assert
+        assert_eq!(
+            actual.unwrap_err().kind,
+            AppErrorKind::BadClientRequest,
+            "content type [{}]",
+            content_type
+        );
+    }
+}
+
+#[actix_web::test]
+async fn test_to_content_type_header_given_compressed_cbor_returns_zstd_suffix() {
+    //setup
+    let chunk_content_type = ChunkContentType {
+        encoding: ChunkEncoding::Cbor,
+        is_zstd_compressed: true,
+    };
+
+    //act
+    let actual = chunk_content_type.to_content_type_header();
+
+    //assert
+    assert_eq!(actual, "application/cbor+zstd");
+}
+
+#[actix_web::test]
+async fn test_deserialize_chunk_payload_given_every_content_type_returns_the_serialized_request() {
+    //setup
+    let request = build_request(50);
+
+    for encoding in [
+        ChunkEncoding::Json,
+        ChunkEncoding::MessagePack,
+        ChunkEncoding::Cbor,
+    ] {
+        for is_zstd_compressed in [false, true] {
+            let chunk_content_type = ChunkContentType {
+                encoding,
+                is_zstd_compressed,
+            };
+            let payload = serialize_chunk_payload(&request, &chunk_content_type).unwrap();
+
+            //act
+            let actual: ReconcileFileChunkRequest =
+                deserialize_chunk_payload(&payload, &chunk_content_type, MAX_DECOMPRESSED_BYTES)
+                    .unwrap();
+
+            //assert
+            assert_eq!(
+                actual.primary_file_chunk,
+                request.primary_file_chunk,
+                "content type [{}]",
+                chunk_content_type.to_content_type_header()
+            );
+            assert_eq!(
+                actual.rows_reconciliation_details,
+                request.rows_reconciliation_details
+            );
+        }
+    }
+}
+
+#[actix_web::test]
+async fn test_serialize_chunk_payload_given_json_stays_readable_as_plain_json() {
+    //setup
+    let request = build_request(1);
+
+    //act
+    let payload = serialize_chunk_payload(&request, &ChunkContentType::default()).unwrap();
+
+    //assert
+    let actual: serde_json::Value = serde_json::from_slice(&payload).unwrap();
+    assert_eq!(actual["primary_file_chunk"]["id"], "file-1234");
+}
+
+#[actix_web::test]
+async fn test_serialize_chunk_payload_given_compressed_message_pack_is_smaller_than_json() {
+    //setup
+    let request = build_request(500);
+    let chunk_content_type = ChunkContentType {
+        encoding: ChunkEncoding::MessagePack,
+        is_zstd_compressed: true,
+    };
+
+    //act
+    let actual = serialize_chunk_payload(&request, &chunk_content_type).unwrap();
+
+    //assert
+    let json_payload = serialize_chunk_payload(&request, &ChunkContentType::default()).unwrap();
+    assert!(actual.len() * 4 < json_payload.len());
+}
+
+#[actix_web::test]
+async fn test_deserialize_chunk_payload_given_payload_in_another_encoding_returns_unmarshal_error()
+{
+    //setup
+    let payload = serialize_chunk_payload(&build_request(1), &ChunkContentType::default()).unwrap();
+    let chunk_content_type = ChunkContentType {
+        encoding: ChunkEncoding::MessagePack,
+        is_zstd_compressed: true,
+    };
+
+    //act
+    let actual: Result<ReconcileFileChunkRequest, _> =
+        deserialize_chunk_payload(&payload, &chunk_content_type, MAX_DECOMPRESSED_BYTES);
+
+    //assert
+    assert_eq!(
+        actual.unwrap_err().kind,
+        AppErrorKind::ResponseUnmarshalError
+    );
+}
+
+#[actix_web::test]
+async fn test_deserialize_chunk_payload_given_payload_over_the_decompressed_limit_returns_bad_client_request(
+) {
+    //setup
+    let chunk_content_type = ChunkContentType {
+        encoding: ChunkEncoding::Json,
+        is_zstd_compressed: true,
+    };
+    //a few kilobytes that decompress to 64 megabytes
+    let payload = zstd::stream::encode_all(vec![b' '; 67_108_864].as_slice(), 3).unwrap();
+
+    //act
+    let actual: Result<ReconcileFileChunkRequest, _> =
+        deserialize_chunk_payload(&payload, &chunk_content_type, 1_048_576);
+
+    //assert
+    assert!(payload.len() < 1_048_576);
+    assert_eq!(actual.unwrap_err().kind, AppErrorKind::BadClientRequest);
+}
+
+#[actix_web::test]
+async fn test_deserialize_chunk_payload_given_payload_at_the_decompressed_limit_returns_the_request(
+) {
+    //setup
+    let chunk_content_type = ChunkContentType {
+        encoding: ChunkEncoding::Json,
+        is_zstd_compressed: true,
+    };
+    let payload = serialize_chunk_payload(&build_request(500), &chunk_content_type).unwrap();
+    let uncompressed_payload =
+        serialize_chunk_payload(&build_request(500), &ChunkContentType::default()).unwrap();
+
+    //act
+    let actual: Result<ReconcileFileChunkRequest, _> =
+        deserialize_chunk_payload(&payload, &chunk_content_type, uncompressed_payload.len());
+
+    //assert
+    assert_eq!(actual.unwrap().primary_file_chunk.chunk_rows.len(), 500);
+}
+
+fn build_request(row_count: u64) -> ReconcileFileChunkRequest {
+    let chunk_rows: Vec<FileUploadChunkRow> = (1..=row_count)
+        .map(|row_number| FileUploadChunkRow {
+            row_number,
+            raw_data: format!("REF-{},100.00,2024-03-01,Acme Ltd", row_number),
+            parsed_columns_from_row: vec![
+                format!("REF-{}", row_number),
+                String::from("100.00"),
+                String::from("2024-03-01"),
+                String::from("Acme Ltd"),
+            ],
+            recon_result: ReconStatus::Failed,
+            recon_result_reasons: vec![format!(
+                "RowNumber: [{}], Column [Amount]: PrimaryFile has value [100.00] while ComparisonFile has value [120.00] in Column [Amount]",
+                row_number
+            )],
+        })
+        .collect();
+
+    let rows_reconciliation_details = (1..=row_count)
+        .map(|row_number| RowReconciliationDetails {
+            row_number,
+            recon_outcome: ReconOutcome::Failed,
+            ..RowReconciliationDetails::default()
+        })
+        .collect();
+
+    return ReconcileFileChunkRequest {
+        primary_file_chunk: FileUploadChunk {
+            id: String::from("file-1234"),
+            upload_request_id: String::from("file-1234"),
+            chunk_sequence_number: 1,
+            chunk_source: FileUploadChunkSource::ComparisonFileChunk,
+            chunk_rows,
+            date_created: 1710000000,
+            date_modified: 1710000000,
+            comparison_pairs: vec![ComparisonPair {
+                primary_file_column_index: 0,
+                comparison_file_column_index: 0,
+                is_row_identifier: true,
+            }],
+            column_headers: vec![
+                String::from("Reference"),
+                String::from("Amount"),
+                String::from("Date"),
+                String::from("Name"),
+            ],
+            recon_config: ReconciliationConfigs {
+                should_check_for_duplicate_records_in_comparison_file: false,
+                should_reconciliation_be_case_sensitive: true,
+                should_ignore_white_space: true,
+                should_do_reverse_reconciliation: false,
+            },
+            primary_file_chunks_queue: build_file_chunks_queue("primary"),
+            comparison_file_chunks_queue: build_file_chunks_queue("comparison"),
+            result_chunks_queue: build_file_chunks_queue("result"),
+            is_last_chunk: false,
+        },
+        reconciliation_options: ReconciliationOptions::default(),
+        rows_reconciliation_details,
+    };
+}
+
+fn build_file_chunks_queue(prefix: &str) -> FileChunkQueue {
+    FileChunkQueue {
+        topic_id: format!("{}-file-chunks-queue-1", prefix),
+        last_acknowledged_id: Some(String::from("1710000000000-0")),
+    }
+}
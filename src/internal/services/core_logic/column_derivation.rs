@@ -0,0 +1,227 @@
+use crate::internal::{
+    models::entities::{
+        derived_column::{DerivedColumn, SignedAmountColumn},
+        recon_result_reason::{ReconResultReason, ReconResultReasonCode},
+        row_reconciliation_details::RowReconciliationDetails,
+    },
+    services::core_logic::currency_conversion::parse_amount,
+    shared_reconciler_rust_libraries::models::entities::file_upload_chunk::{
+        FileUploadChunk, ReconStatus,
+    },
+};
+
+//adds the derived columns to every row of a file chunk and their headers to its column headers.
+//every row gets a value for every derived column, even if it is empty,
+//so that the derived columns can be removed again using their count
+pub fn derive_file_chunk_columns(
+    file_chunk: &mut FileUploadChunk,
+    derived_columns: &Vec<DerivedColumn>,
+    rows_reconciliation_details: &mut Vec<RowReconciliationDetails>,
+) {
+    if derived_columns.is_empty() {
+        return;
+    }
+
+    let other_column_headers_count = file_chunk.column_headers.len();
+
+    for derived_column in derived_columns {
+        file_chunk
+            .column_headers
+            .push(derived_column.get_column_header().clone());
+    }
+
+    for chunk_row in file_chunk.chunk_rows.iter_mut() {
+        let mut derived_values = vec![];
+
+        for derived_column in derived_columns {
+            let derivation_result = match derived_column {
+                DerivedColumn::SignedAmount(signed_amount_column) => {
+                    derive_signed_amount(&chunk_row.parsed_columns_from_row, signed_amount_column)
+                }
+            };
+
+            match derivation_result {
+                Ok(derived_value) => derived_values.push(derived_value),
+
+                Err(error) => {
+                    derived_values.push(String::from(""));
+
+                    //rows that already failed, e.g because they could not be read,
+                    //cannot have their columns derived either
+                    if chunk_row.recon_result == ReconStatus::Failed {
+                        continue;
+                    }
+
+                    let reason = format!(
+                        "RowNumber: [{}], unable to derive Column [{}]: {}",
+                        chunk_row.row_number,
+                        derived_column.get_column_header(),
+                        error
+                    );
+                    chunk_row.recon_result = ReconStatus::Failed;
+                    chunk_row.recon_result_reasons.push(reason);
+
+                    RowReconciliationDetails::find_or_insert(
+                        rows_reconciliation_details,
+                        chunk_row.row_number,
+                    )
+                    .recon_result_reasons
+                    .push(ReconResultReason {
+                        reason_code: ReconResultReasonCode::UnderivableColumn,
+                        primary_file_column_header: derived_column.get_column_header().clone(),
+                        ..ReconResultReason::default()
+                    });
+                }
+            }
+        }
+
+        //a row with fewer columns than there are headers is padded with empty columns,
+        //so that its derived values end up under their headers like those of every other row
+        let derived_columns_start = get_derived_columns_start(
+            other_column_headers_count,
+            chunk_row.parsed_columns_from_row.len(),
+        );
+        if chunk_row.parsed_columns_from_row.len() < derived_columns_start {
+            chunk_row
+                .parsed_columns_from_row
+                .resize(derived_columns_start, String::from(""));
+        }
+        chunk_row
+            .parsed_columns_from_row
+            .splice(derived_columns_start..derived_columns_start, derived_values);
+    }
+}
+
+//removes the derived columns added by derive_file_chunk_columns
+//so that they are not derived a second time when the chunk is requeued
+pub fn remove_derived_columns(file_chunk: &mut FileUploadChunk, derived_columns_count: usize) {
+    if derived_columns_count == 0 {
+        return;
+    }
+
+    let other_column_headers_count = file_chunk
+        .column_headers
+        .len()
+        .saturating_sub(derived_columns_count);
+    file_chunk
+        .column_headers
+        .truncate(other_column_headers_count);
+
+    for chunk_row in file_chunk.chunk_rows.iter_mut() {
+        let derived_columns_start = get_derived_columns_start(
+            other_column_headers_count,
+            chunk_row
+                .parsed_columns_from_row
+                .len()
+                .saturating_sub(derived_columns_count),
+        );
+        let derived_columns_end = chunk_row
+            .parsed_columns_from_row
+            .len()
+            .min(derived_columns_start + derived_columns_count);
+
+        if derived_columns_start < derived_columns_end {
+            chunk_row
+                .parsed_columns_from_row
+                .drain(derived_columns_start..derived_columns_end);
+        }
+    }
+}
+
+//gets the derived column at an index of a row of a chunk whose derived columns have been added
+pub fn get_derived_column<'a>(
+    file_chunk: &FileUploadChunk,
+    row_columns_count: usize,
+    derived_columns: &'a Vec<DerivedColumn>,
+    column_index: usize,
+) -> Option<&'a DerivedColumn> {
+    let other_column_headers_count = file_chunk
+        .column_headers
+        .len()
+        .saturating_sub(derived_columns.len());
+
+    let derived_columns_start = get_derived_columns_start(
+        other_column_headers_count,
+        row_columns_count.saturating_sub(derived_columns.len()),
+    );
+
+    return column_index
+        .checked_sub(derived_columns_start)
+        .and_then(|derived_column_index| derived_columns.get(derived_column_index));
+}
+
+//derived columns come right after the columns that have headers, so that their index
+//is the same in every row. a chunk without other headers has them at the end of each row
+fn get_derived_columns_start(
+    other_column_headers_count: usize,
+    other_row_columns_count: usize,
+) -> usize {
+    if other_column_headers_count == 0 {
+        return other_row_columns_count;
+    }
+    return other_column_headers_count;
+}
+
+//combines the amount and the debit/credit indicator of a row into a signed amount.
+//the amount is written in a canonical form e.g "1,250.50" becomes "1250.5",
+//so that derived amounts written differently in each file are still the same value
+pub fn derive_signed_amount(
+    row_columns: &Vec<String>,
+    signed_amount_column: &SignedAmountColumn,
+) -> Result<String, String> {
+    let amount = match row_columns.get(signed_amount_column.amount_column_index) {
+        Some(amount) => amount.trim(),
+        None => {
+            return Err(format!(
+                "amount column index [{}] does not exist in the row",
+                signed_amount_column.amount_column_index
+            ))
+        }
+    };
+
+    //we split the sign from the digits of the amount
+    let (mut is_negative, unsigned_amount) = match amount.strip_prefix('-') {
+        Some(unsigned_amount) => (true, unsigned_amount.trim()),
+        None => (false, amount.trim_start_matches('+').trim()),
+    };
+
+    let parsed_amount = parse_amount(unsigned_amount)?;
+
+    if let Some(indicator_column_index) = signed_amount_column.indicator_column_index {
+        let indicator = match row_columns.get(indicator_column_index) {
+            Some(indicator) => indicator.trim(),
+            None => {
+                return Err(format!(
+                    "indicator column index [{}] does not exist in the row",
+                    indicator_column_index
+                ))
+            }
+        };
+
+        let is_indicator_in = |indicators: &Vec<String>| {
+            indicators
+                .iter()
+                .any(|known_indicator| known_indicator.trim().eq_ignore_ascii_case(indicator))
+        };
+
+        if is_indicator_in(&signed_amount_column.debit_indicators) {
+            is_negative = !is_negative;
+        } else if !is_indicator_in(&signed_amount_column.credit_indicators) {
+            return Err(format!(
+                "indicator [{}] is neither a debit nor a credit indicator",
+                indicator
+            ));
+        }
+    }
+
+    if signed_amount_column.should_invert_sign {
+        is_negative = !is_negative;
+    }
+
+    //zero has no sign, so that "-0.00" and "0.00" are the same amount
+    if is_negative && parsed_amount != 0.0 {
+        return Ok((-parsed_amount).to_string());
+    }
+
+    return Ok(parsed_amount.abs().to_string());
+}
@@ -0,0 +1,262 @@
+use crate::internal::{
+    models::entities::{
+        derived_column::{DerivedColumn, SignedAmountColumn},
+        recon_result_reason::ReconResultReasonCode,
+    },
+    shared_reconciler_rust_libraries::models::entities::{
+        file_chunk_queue::FileChunkQueue,
+        file_upload_chunk::{
+            FileUploadChunk, FileUploadChunkRow, FileUploadChunkSource, ReconStatus,
+        },
+        recon_tasks_models::ReconciliationConfigs,
+    },
+};
+
+use super::column_derivation::{
+    derive_file_chunk_columns, derive_signed_amount, remove_derived_columns,
+};
+
+#[actix_web::test]
+async fn test_derive_signed_amount_given_debit_indicator_returns_negative_amount() {
+    //setup
+    let row_columns = build_row_columns("1001, 1250.50, DR");
+
+    //act
+    let actual = derive_signed_amount(&row_columns, &debit_credit_signed_amount_column(false));
+
+    //assert
+    assert_eq!(actual.unwrap(), String::from("-1250.5"));
+}
+
+#[actix_web::test]
+async fn test_derive_signed_amount_given_credit_indicator_returns_positive_amount() {
+    //setup
+    let row_columns = build_row_columns("1001, 1250.50, c");
+
+    //act
+    let actual = derive_signed_amount(&row_columns, &debit_credit_signed_amount_column(false));
+
+    //assert
+    assert_eq!(actual.unwrap(), String::from("1250.5"));
+}
+
+#[actix_web::test]
+async fn test_derive_signed_amount_given_invert_sign_flips_the_sign() {
+    //setup
+    let row_columns = build_row_columns("1001, 1250.50, DR");
+
+    //act
+    let actual = derive_signed_amount(&row_columns, &debit_credit_signed_amount_column(true));
+
+    //assert
+    assert_eq!(actual.unwrap(), String::from("1250.5"));
+}
+
+#[actix_web::test]
+async fn test_derive_signed_amount_given_no_indicator_column_only_inverts_signed_amounts() {
+    //setup
+    let row_columns = build_row_columns("1001, -300");
+    let signed_amount_column = SignedAmountColumn {
+        indicator_column_index: None,
+        ..debit_credit_signed_amount_column(true)
+    };
+
+    //act
+    let actual = derive_signed_amount(&row_columns, &signed_amount_column);
+
+    //assert
+    assert_eq!(actual.unwrap(), String::from("300"));
+}
+
+#[actix_web::test]
+async fn test_derive_signed_amount_given_zero_debit_amount_returns_unsigned_zero() {
+    //setup
+    let row_columns = build_row_columns("1001, 0.00, DR");
+
+    //act
+    let actual = derive_signed_amount(&row_columns, &debit_credit_signed_amount_column(false));
+
+    //assert
+    assert_eq!(actual.unwrap(), String::from("0"));
+}
+
+#[actix_web::test]
+async fn test_derive_signed_amount_given_thousands_separators_returns_canonical_amount() {
+    //setup
+    let row_columns = vec![
+        String::from("1001"),
+        String::from("1,000.50"),
+        String::from("DR"),
+    ];
+
+    //act
+    let actual = derive_signed_amount(&row_columns, &debit_credit_signed_amount_column(false));
+
+    //assert
+    assert_eq!(actual.unwrap(), String::from("-1000.5"));
+}
+
+#[actix_web::test]
+async fn test_derive_signed_amount_given_unknown_indicator_returns_error() {
+    //setup
+    let row_columns = build_row_columns("1001, 1250.50, XX");
+
+    //act
+    let actual = derive_signed_amount(&row_columns, &debit_credit_signed_amount_column(false));
+
+    //assert
+    assert_eq!(actual.is_err(), true);
+}
+
+#[actix_web::test]
+async fn test_derive_file_chunk_columns_appends_the_derived_columns_and_can_remove_them() {
+    //setup
+    let mut file_chunk = build_file_chunk(vec![
+        build_chunk_row(1, "1001, 10, DR"),
+        build_chunk_row(2, "1002, 20, CR"),
+    ]);
+    let derived_columns = vec![DerivedColumn::SignedAmount(
+        debit_credit_signed_amount_column(false),
+    )];
+
+    //act
+    derive_file_chunk_columns(&mut file_chunk, &derived_columns, &mut vec![]);
+
+    //assert
+    assert_eq!(file_chunk.column_headers[3], String::from("SignedAmount"));
+    assert_eq!(file_chunk.chunk_rows[0].parsed_columns_from_row[3], "-10");
+    assert_eq!(file_chunk.chunk_rows[1].parsed_columns_from_row[3], "20");
+
+    remove_derived_columns(&mut file_chunk, derived_columns.len());
+    assert_eq!(file_chunk.column_headers.len(), 3);
+    assert_eq!(file_chunk.chunk_rows[0].parsed_columns_from_row.len(), 3);
+}
+
+#[actix_web::test]
+async fn test_derive_file_chunk_columns_given_row_shorter_than_the_headers_keeps_derived_columns_under_their_headers(
+) {
+    //setup
+    let mut file_chunk = build_file_chunk(vec![build_chunk_row(1, "1001, 10, DR")]);
+    file_chunk.column_headers = build_row_columns("ID, Amount, Indicator, Memo");
+    let derived_columns = vec![DerivedColumn::SignedAmount(
+        debit_credit_signed_amount_column(false),
+    )];
+
+    //act
+    derive_file_chunk_columns(&mut file_chunk, &derived_columns, &mut vec![]);
+
+    //assert
+    assert_eq!(file_chunk.column_headers[4], String::from("SignedAmount"));
+    assert_eq!(file_chunk.chunk_rows[0].parsed_columns_from_row[3], "");
+    assert_eq!(file_chunk.chunk_rows[0].parsed_columns_from_row[4], "-10");
+
+    remove_derived_columns(&mut file_chunk, derived_columns.len());
+    assert_eq!(file_chunk.column_headers.len(), 4);
+    assert_eq!(
+        file_chunk.chunk_rows[0].parsed_columns_from_row,
+        vec!["1001", " 10", " DR", ""]
+    );
+}
+
+#[actix_web::test]
+async fn test_derive_file_chunk_columns_given_row_longer_than_the_headers_keeps_derived_columns_under_their_headers(
+) {
+    //setup
+    let mut file_chunk = build_file_chunk(vec![build_chunk_row(1, "1001, 10, DR, extra")]);
+    let derived_columns = vec![DerivedColumn::SignedAmount(
+        debit_credit_signed_amount_column(false),
+    )];
+
+    //act
+    derive_file_chunk_columns(&mut file_chunk, &derived_columns, &mut vec![]);
+
+    //assert
+    assert_eq!(file_chunk.chunk_rows[0].parsed_columns_from_row[3], "-10");
+
+    remove_derived_columns(&mut file_chunk, derived_columns.len());
+    assert_eq!(
+        file_chunk.chunk_rows[0].parsed_columns_from_row,
+        vec!["1001", " 10", " DR", " extra"]
+    );
+}
+
+#[actix_web::test]
+async fn test_derive_file_chunk_columns_given_underivable_column_fails_the_row() {
+    //setup
+    let mut file_chunk = build_file_chunk(vec![build_chunk_row(1, "1001, ten, DR")]);
+    let derived_columns = vec![DerivedColumn::SignedAmount(
+        debit_credit_signed_amount_column(false),
+    )];
+    let mut rows_reconciliation_details = vec![];
+
+    //act
+    derive_file_chunk_columns(
+        &mut file_chunk,
+        &derived_columns,
+        &mut rows_reconciliation_details,
+    );
+
+    //assert
+    assert_eq!(file_chunk.chunk_rows[0].recon_result, ReconStatus::Failed);
+    assert_eq!(file_chunk.chunk_rows[0].parsed_columns_from_row[3], "");
+    assert_eq!(
+        rows_reconciliation_details[0].recon_result_reasons[0].reason_code,
+        ReconResultReasonCode::UnderivableColumn
+    );
+}
+
+fn debit_credit_signed_amount_column(should_invert_sign: bool) -> SignedAmountColumn {
+    SignedAmountColumn {
+        column_header: String::from("SignedAmount"),
+        amount_column_index: 1,
+        indicator_column_index: Some(2),
+        debit_indicators: vec![String::from("DR"), String::from("D")],
+        credit_indicators: vec![String::from("CR"), String::from("C")],
+        should_invert_sign,
+    }
+}
+
+fn build_row_columns(raw_data: &str) -> Vec<String> {
+    raw_data.split(',').map(|s| s.to_string()).collect()
+}
+
+fn build_chunk_row(row_number: u64, raw_data: &str) -> FileUploadChunkRow {
+    FileUploadChunkRow {
+        row_number,
+        raw_data: raw_data.to_string(),
+        parsed_columns_from_row: build_row_columns(raw_data),
+        recon_result: ReconStatus::Pending,
+        recon_result_reasons: vec![],
+    }
+}
+
+fn build_file_chunk(chunk_rows: Vec<FileUploadChunkRow>) -> FileUploadChunk {
+    FileUploadChunk {
+        id: String::from("file-1234"),
+        upload_request_id: String::from("file-1234"),
+        chunk_sequence_number: 1,
+        chunk_source: FileUploadChunkSource::ComparisonFileChunk,
+        chunk_rows,
+        date_created: chrono::Utc::now().timestamp(),
+        date_modified: chrono::Utc::now().timestamp(),
+        comparison_pairs: vec![],
+        column_headers: build_row_columns("ID, Amount, Indicator"),
+        recon_config: ReconciliationConfigs {
+            should_check_for_duplicate_records_in_comparison_file: false,
+            should_reconciliation_be_case_sensitive: true,
+            should_ignore_white_space: true,
+            should_do_reverse_reconciliation: false,
+        },
+        primary_file_chunks_queue: build_file_chunks_queue("primary"),
+        comparison_file_chunks_queue: build_file_chunks_queue("comparison"),
+        result_chunks_queue: build_file_chunks_queue("result"),
+        is_last_chunk: false,
+    }
+}
+
+fn build_file_chunks_queue(prefix: &str) -> FileChunkQueue {
+    FileChunkQueue {
+        topic_id: format!("{}-file-chunks-queue-1", prefix),
+        last_acknowledged_id: None,
+    }
+}
@@ -0,0 +1,178 @@
+use regex::Regex;
+
+use crate::internal::{
+    models::entities::{
+        reconciliation_options::ReconciliationOptions, value_mapping::ValueMappingTable,
+        value_normalization::NormalizationStep, value_transform::ValueTransform,
+    },
+    services::core_logic::value_normalization::normalize_column_value,
+    shared_reconciler_rust_libraries::models::entities::{
+        app_errors::{AppError, AppErrorKind},
+        recon_tasks_models::ComparisonPair,
+    },
+};
+
+//a value transform whose pattern has already been compiled
+pub struct CompiledValueTransform {
+    regex: Regex,
+    value_transform: ValueTransform,
+}
+
+//the value transforms, value mapping and normalization steps used for the columns of a comparison pair
+pub struct PreparedComparisonPair {
+    primary_file_column_index: usize,
+    comparison_file_column_index: usize,
+    primary_file_value_transform: Option<CompiledValueTransform>,
+    comparison_file_value_transform: Option<CompiledValueTransform>,
+    comparison_file_value_mapping: Option<ValueMappingTable>,
+    normalization_steps: Vec<NormalizationStep>,
+}
+
+//gets column values ready to be compared, the value transforms are compiled once
+//per chunk so that the patterns are not recompiled for every row
+#[derive(Default)]
+pub struct ColumnValuePreparer {
+    prepared_comparison_pairs: Vec<PreparedComparisonPair>,
+    default_normalization_steps: Vec<NormalizationStep>,
+}
+
+impl ColumnValuePreparer {
+    pub fn new(
+        comparison_pairs: &Vec<ComparisonPair>,
+        reconciliation_options: &ReconciliationOptions,
+    ) -> Result<ColumnValuePreparer, AppError> {
+        let mut prepared_comparison_pairs = vec![];
+
+        for pair in comparison_pairs {
+            let pair_options = reconciliation_options.get_comparison_pair_options(pair);
+
+            prepared_comparison_pairs.push(PreparedComparisonPair {
+                primary_file_column_index: pair.primary_file_column_index,
+                comparison_file_column_index: pair.comparison_file_column_index,
+                primary_file_value_transform: compile_value_transform(
+                    &pair_options.primary_file_value_transform,
+                )?,
+                comparison_file_value_transform: compile_value_transform(
+                    &pair_options.comparison_file_value_transform,
+                )?,
+                comparison_file_value_mapping: pair_options.comparison_file_value_mapping.clone(),
+                normalization_steps: reconciliation_options.get_normalization_steps(&pair_options),
+            });
+        }
+
+        return Ok(ColumnValuePreparer {
+            prepared_comparison_pairs,
+            default_normalization_steps: reconciliation_options.normalization_steps.clone(),
+        });
+    }
+
+    //transforms and then normalizes a column value from the primary file
+    pub fn prepare_primary_file_column_value(
+        &self,
+        pair: &ComparisonPair,
+        column_value: &String,
+    ) -> String {
+        return match self.find_prepared_comparison_pair(pair) {
+            Some(prepared_pair) => normalize_column_value(
+                &apply_value_transform(&prepared_pair.primary_file_value_transform, column_value),
+                &prepared_pair.normalization_steps,
+            ),
+            None => normalize_column_value(column_value, &self.default_normalization_steps),
+        };
+    }
+
+    //transforms, maps and then normalizes a column value from the comparison file.
+    //returns None if the pair has a value mapping table that does not contain the value
+    pub fn prepare_comparison_file_column_value(
+        &self,
+        pair: &ComparisonPair,
+        column_value: &String,
+    ) -> Option<String> {
+        let prepared_pair = match self.find_prepared_comparison_pair(pair) {
+            Some(prepared_pair) => prepared_pair,
+            None => {
+                return Some(normalize_column_value(
+                    column_value,
+                    &self.default_normalization_steps,
+                ))
+            }
+        };
+
+        let mut transformed_column_value =
+            apply_value_transform(&prepared_pair.comparison_file_value_transform, column_value);
+
+        if let Some(value_mapping_table) = &prepared_pair.comparison_file_value_mapping {
+            transformed_column_value =
+                value_mapping_table.get_mapped_value(&transformed_column_value)?;
+        }
+
+        return Some(normalize_column_value(
+            &transformed_column_value,
+            &prepared_pair.normalization_steps,
+        ));
+    }
+
+    fn find_prepared_comparison_pair(
+        &self,
+        pair: &ComparisonPair,
+    ) -> Option<&PreparedComparisonPair> {
+        return self.prepared_comparison_pairs.iter().find(|prepared_pair| {
+            prepared_pair.primary_file_column_index == pair.primary_file_column_index
+                && prepared_pair.comparison_file_column_index == pair.comparison_file_column_index
+        });
+    }
+}
+
+pub fn compile_value_transform(
+    value_transform: &Option<ValueTransform>,
+) -> Result<Option<CompiledValueTransform>, AppError> {
+    let value_transform = match value_transform {
+        Some(value_transform) => value_transform,
+        None => return Ok(None),
+    };
+
+    //patterns are validated when the request arrives
+    //so this only fails for requests that skipped validation
+    return match Regex::new(value_transform.get_pattern()) {
+        Ok(regex) => Ok(Some(CompiledValueTransform {
+            regex,
+            value_transform: value_transform.clone(),
+        })),
+        Err(e) => Err(AppError::new(
+            AppErrorKind::BadClientRequest,
+            format!(
+                "invalid value transform pattern [{}]: {}",
+                value_transform.get_pattern(),
+                e
+            ),
+        )),
+    };
+}
+
+pub fn apply_value_transform(
+    compiled_value_transform: &Option<CompiledValueTransform>,
+    column_value: &String,
+) -> String {
+    let compiled_value_transform = match compiled_value_transform {
+        Some(compiled_value_transform) => compiled_value_transform,
+        None => return column_value.clone(),
+    };
+
+    match &compiled_value_transform.value_transform {
+        ValueTransform::RegexCapture { capture_group, .. } => {
+            return compiled_value_transform
+                .regex
+                .captures(column_value)
+                .and_then(|captures| captures.get(*capture_group))
+                .map(|capture| capture.as_str().to_string())
+                .unwrap_or(column_value.clone());
+        }
+
+        ValueTransform::RegexReplace { replacement, .. } => {
+            return compiled_value_transform
+                .regex
+                .replace_all(column_value, replacement.as_str())
+                .to_string();
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use crate::internal::{
+    models::entities::{
+        comparison_pair_options::{ColumnIndexComparisonPairOptions, ComparisonPairOptions},
+        reconciliation_options::ReconciliationOptions,
+        value_normalization::NormalizationStep,
+        value_transform::{validate_value_transform, ValueTransform},
+    },
+    shared_reconciler_rust_libraries::models::entities::{
+        app_errors::AppErrorKind, recon_tasks_models::ComparisonPair,
+    },
+};
+
+use super::column_value_preparation::{
+    apply_value_transform, compile_value_transform, ColumnValuePreparer,
+};
+
+#[actix_web::test]
+async fn test_apply_value_transform_given_regex_capture_returns_the_capture_group() {
+    //setup
+    let value_transform = Some(ValueTransform::RegexCapture {
+        pattern: String::from(r"^TXN/\d+/0*(\d+)$"),
+        capture_group: 1,
+    });
+    let compiled_value_transform = compile_value_transform(&value_transform).unwrap();
+
+    //act
+    let actual = apply_value_transform(&compiled_value_transform, &String::from("TXN/2022/000123"));
+
+    //assert
+    assert_eq!(actual, String::from("123"));
+}
+
+#[actix_web::test]
+async fn test_apply_value_transform_given_regex_capture_that_does_not_match_keeps_the_value() {
+    //setup
+    let value_transform = Some(ValueTransform::RegexCapture {
+        pattern: String::from(r"^TXN/\d+/0*(\d+)$"),
+        capture_group: 1,
+    });
+    let compiled_value_transform = compile_value_transform(&value_transform).unwrap();
+
+    //act
+    let actual = apply_value_transform(&compiled_value_transform, &String::from("REF-77"));
+
+    //assert
+    assert_eq!(actual, String::from("REF-77"));
+}
+
+#[actix_web::test]
+async fn test_apply_value_transform_given_regex_replace_replaces_every_match() {
+    //setup
+    let value_transform = Some(ValueTransform::RegexReplace {
+        pattern: String::from(r"[/\-]"),
+        replacement: String::from(""),
+    });
+    let compiled_value_transform = compile_value_transform(&value_transform).unwrap();
+
+    //act
+    let actual = apply_value_transform(&compiled_value_transform, &String::from("TXN/2022-000123"));
+
+    //assert
+    assert_eq!(actual, String::from("TXN2022000123"));
+}
+
+#[actix_web::test]
+async fn test_compile_value_transform_given_invalid_pattern_returns_bad_client_request() {
+    //setup
+    let value_transform = Some(ValueTransform::RegexReplace {
+        pattern: String::from("(unclosed"),
+        replacement: String::from(""),
+    });
+
+    //act
+    let actual = compile_value_transform(&value_transform);
+
+    //assert
+    assert_eq!(actual.err().unwrap().kind, AppErrorKind::BadClientRequest);
+}
+
+#[actix_web::test]
+async fn test_validate_value_transform_given_missing_capture_group_returns_error() {
+    //setup
+    let value_transform = ValueTransform::RegexCapture {
+        pattern: String::from(r"^TXN/(\d+)$"),
+        capture_group: 2,
+    };
+
+    //act
+    let actual = validate_value_transform(&value_transform);
+
+    //assert
+    assert_eq!(actual.is_err(), true);
+}
+
+#[actix_web::test]
+async fn test_column_value_preparer_transforms_each_side_then_normalizes() {
+    //setup
+    let pair = ComparisonPair {
+        primary_file_column_index: 0,
+        comparison_file_column_index: 2,
+        is_row_identifier: true,
+    };
+    let reconciliation_options = ReconciliationOptions {
+        normalization_steps: vec![NormalizationStep::RemoveLeadingZeros],
+        comparison_pair_options: vec![ColumnIndexComparisonPairOptions {
+            primary_file_column_index: 0,
+            comparison_file_column_index: 2,
+            options: ComparisonPairOptions {
+                primary_file_value_transform: Some(ValueTransform::RegexCapture {
+                    pattern: String::from(r"^TXN/\d+/(\d+)$"),
+                    capture_group: 1,
+                }),
+                ..ComparisonPairOptions::default()
+            },
+        }],
+        ..ReconciliationOptions::default()
+    };
+
+    //act
+    let sut = ColumnValuePreparer::new(&vec![pair.clone()], &reconciliation_options).unwrap();
+    let actual_primary =
+        sut.prepare_primary_file_column_value(&pair, &String::from("TXN/2022/000123"));
+    let actual_comparison = sut
+        .prepare_comparison_file_column_value(&pair, &String::from("0123"))
+        .unwrap();
+
+    //assert
+    assert_eq!(actual_primary, String::from("123"));
+    assert_eq!(actual_comparison, String::from("123"));
+}
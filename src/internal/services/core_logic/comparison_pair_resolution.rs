@@ -0,0 +1,176 @@
+use std::collections::HashMap;
+
+use crate::internal::{
+    models::entities::{
+        comparison_pair_options::ColumnIndexComparisonPairOptions,
+        header_name_comparison_pair::HeaderNameComparisonPair,
+        match_rule::{ColumnReference, MatchRule},
+    },
+    shared_reconciler_rust_libraries::models::entities::{
+        app_errors::{AppError, AppErrorKind},
+        recon_tasks_models::ComparisonPair,
+    },
+};
+
+//turns comparison pairs that reference columns by header name into
+//index based comparison pairs using the column headers of both chunks
+pub fn resolve_header_name_comparison_pairs(
+    header_name_comparison_pairs: &Vec<HeaderNameComparisonPair>,
+    primary_file_column_headers: &Vec<String>,
+    comparison_file_column_headers: &Vec<String>,
+) -> Result<Vec<ComparisonPair>, AppError> {
+    let mut resolved_comparison_pairs = vec![];
+
+    for pair in header_name_comparison_pairs {
+        let primary_file_column_index = find_column_index(
+            &pair.primary_file_column_header,
+            primary_file_column_headers,
+            "PrimaryFile",
+            "comparison pair",
+        )?;
+
+        let comparison_file_column_index = find_column_index(
+            &pair.comparison_file_column_header,
+            comparison_file_column_headers,
+            "ComparisonFile",
+            "comparison pair",
+        )?;
+
+        resolved_comparison_pairs.push(ComparisonPair {
+            primary_file_column_index,
+            comparison_file_column_index,
+            is_row_identifier: pair.is_row_identifier,
+        });
+    }
+
+    return Ok(resolved_comparison_pairs);
+}
+
+//carries the options of each header name comparison pair
+//over to the index based comparison pair it was resolved to
+pub fn resolve_header_name_comparison_pair_options(
+    header_name_comparison_pairs: &Vec<HeaderNameComparisonPair>,
+    resolved_comparison_pairs: &Vec<ComparisonPair>,
+) -> Vec<ColumnIndexComparisonPairOptions> {
+    header_name_comparison_pairs
+        .iter()
+        .zip(resolved_comparison_pairs.iter())
+        .map(
+            |(header_name_pair, resolved_pair)| ColumnIndexComparisonPairOptions {
+                primary_file_column_index: resolved_pair.primary_file_column_index,
+                comparison_file_column_index: resolved_pair.comparison_file_column_index,
+                options: header_name_pair.options.clone(),
+            },
+        )
+        .collect()
+}
+
+//the column indexes of the headers a match rule references, so that the headers
+//are looked up once per chunk instead of once for every pair of rows
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MatchRuleColumnIndexes {
+    pub primary_file_column_indexes: HashMap<String, usize>,
+    pub comparison_file_column_indexes: HashMap<String, usize>,
+}
+
+//finds the column of every header the match rule references, rejecting
+//headers that are missing or ambiguous the same way comparison pairs do
+pub fn resolve_match_rule_column_headers(
+    match_rule: &MatchRule,
+    primary_file_column_headers: &Vec<String>,
+    comparison_file_column_headers: &Vec<String>,
+) -> Result<MatchRuleColumnIndexes, AppError> {
+    let mut match_rule_column_indexes = MatchRuleColumnIndexes::default();
+
+    match match_rule {
+        MatchRule::And(left, right) | MatchRule::Or(left, right) => {
+            for inner in [left, right] {
+                let inner_column_indexes = resolve_match_rule_column_headers(
+                    inner,
+                    primary_file_column_headers,
+                    comparison_file_column_headers,
+                )?;
+                match_rule_column_indexes
+                    .primary_file_column_indexes
+                    .extend(inner_column_indexes.primary_file_column_indexes);
+                match_rule_column_indexes
+                    .comparison_file_column_indexes
+                    .extend(inner_column_indexes.comparison_file_column_indexes);
+            }
+        }
+        MatchRule::Not(inner) => {
+            return resolve_match_rule_column_headers(
+                inner,
+                primary_file_column_headers,
+                comparison_file_column_headers,
+            )
+        }
+        MatchRule::Predicate(predicate) => {
+            if let ColumnReference::Header(header) = &predicate.primary_file_column {
+                let column_index = find_column_index(
+                    header,
+                    primary_file_column_headers,
+                    "PrimaryFile",
+                    "match rule",
+                )?;
+                match_rule_column_indexes
+                    .primary_file_column_indexes
+                    .insert(header.clone(), column_index);
+            }
+
+            if let ColumnReference::Header(header) = &predicate.comparison_file_column {
+                let column_index = find_column_index(
+                    header,
+                    comparison_file_column_headers,
+                    "ComparisonFile",
+                    "match rule",
+                )?;
+                match_rule_column_indexes
+                    .comparison_file_column_indexes
+                    .insert(header.clone(), column_index);
+            }
+        }
+    }
+
+    return Ok(match_rule_column_indexes);
+}
+
+//finds the index of the only column whose header matches the header name
+fn find_column_index(
+    header_name: &String,
+    column_headers: &Vec<String>,
+    file_name: &str,
+    referenced_in: &str,
+) -> Result<usize, AppError> {
+    //headers are usually split straight out of the file
+    //so we ignore any white space around them
+    let matching_column_indexes: Vec<usize> = column_headers
+        .iter()
+        .enumerate()
+        .filter(|(_, column_header)| column_header.trim() == header_name.trim())
+        .map(|(index, _)| index)
+        .collect();
+
+    match matching_column_indexes.len() {
+        1 => Ok(matching_column_indexes[0]),
+
+        0 => Err(AppError::new(
+            AppErrorKind::BadClientRequest,
+            format!(
+                "Column [{}] referenced in {} was not found in {} column headers",
+                header_name, referenced_in, file_name
+            ),
+        )),
+
+        _ => Err(AppError::new(
+            AppErrorKind::BadClientRequest,
+            format!(
+                "Column [{}] referenced in {} is ambiguous. {} has [{}] columns with that header",
+                header_name,
+                referenced_in,
+                file_name,
+                matching_column_indexes.len()
+            ),
+        )),
+    }
+}
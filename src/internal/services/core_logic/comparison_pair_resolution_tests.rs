@@ -0,0 +1,174 @@
+use crate::internal::{
+    models::entities::{
+        comparison_pair_options::{ComparisonPairOptions, MismatchSeverity},
+        header_name_comparison_pair::HeaderNameComparisonPair,
+        match_rule::MatchRule,
+    },
+    shared_reconciler_rust_libraries::models::entities::{
+        app_errors::AppErrorKind, recon_tasks_models::ComparisonPair,
+    },
+};
+
+use super::comparison_pair_resolution::{
+    resolve_header_name_comparison_pair_options, resolve_header_name_comparison_pairs,
+    resolve_match_rule_column_headers,
+};
+
+#[actix_web::test]
+async fn test_resolve_header_name_comparison_pairs_given_reordered_columns_returns_correct_indexes()
+{
+    //setup
+    let pairs = vec![
+        header_name_comparison_pair("ID", "TransactionId", true),
+        header_name_comparison_pair("Amount", "Amount", false),
+    ];
+    let primary_file_column_headers = build_column_headers("ID, Narrative, Amount");
+    let comparison_file_column_headers = build_column_headers("Amount, TransactionId");
+
+    //act
+    let actual = resolve_header_name_comparison_pairs(
+        &pairs,
+        &primary_file_column_headers,
+        &comparison_file_column_headers,
+    );
+
+    //assert
+    let expected = vec![comparison_pair(0, 1, true), comparison_pair(2, 0, false)];
+    assert_eq!(actual.unwrap(), expected);
+}
+
+#[actix_web::test]
+async fn test_resolve_header_name_comparison_pairs_given_missing_header_returns_bad_client_request()
+{
+    //setup
+    let pairs = vec![header_name_comparison_pair("Amount", "Value", false)];
+    let primary_file_column_headers = build_column_headers("ID, Amount");
+    let comparison_file_column_headers = build_column_headers("ID, Amount");
+
+    //act
+    let actual = resolve_header_name_comparison_pairs(
+        &pairs,
+        &primary_file_column_headers,
+        &comparison_file_column_headers,
+    );
+
+    //assert
+    assert_eq!(actual.unwrap_err().kind, AppErrorKind::BadClientRequest);
+}
+
+#[actix_web::test]
+async fn test_resolve_header_name_comparison_pairs_given_ambiguous_header_returns_bad_client_request(
+) {
+    //setup
+    let pairs = vec![header_name_comparison_pair("Amount", "Amount", false)];
+    let primary_file_column_headers = build_column_headers("Amount, ID, Amount");
+    let comparison_file_column_headers = build_column_headers("ID, Amount");
+
+    //act
+    let actual = resolve_header_name_comparison_pairs(
+        &pairs,
+        &primary_file_column_headers,
+        &comparison_file_column_headers,
+    );
+
+    //assert
+    assert_eq!(actual.unwrap_err().kind, AppErrorKind::BadClientRequest);
+}
+
+#[actix_web::test]
+async fn test_resolve_header_name_comparison_pair_options_keeps_the_options_of_each_pair() {
+    //setup
+    let mut pair = header_name_comparison_pair("Narrative", "Description", false);
+    pair.options.severity = MismatchSeverity::Informational;
+    let resolved_comparison_pairs = vec![comparison_pair(3, 1, false)];
+
+    //act
+    let actual =
+        resolve_header_name_comparison_pair_options(&vec![pair], &resolved_comparison_pairs);
+
+    //assert
+    assert_eq!(actual.len(), 1);
+    assert_eq!(actual[0].primary_file_column_index, 3);
+    assert_eq!(actual[0].comparison_file_column_index, 1);
+    assert_eq!(actual[0].options.severity, MismatchSeverity::Informational);
+}
+
+#[actix_web::test]
+async fn test_resolve_match_rule_column_headers_returns_the_column_of_every_referenced_header() {
+    //setup
+    let match_rule = MatchRule::parse(
+        r#"equals(primary["ID"], comparison["TransactionId"]) AND NOT equals(primary["Amount"], comparison[0])"#,
+    )
+    .unwrap();
+    let primary_file_column_headers = build_column_headers("ID, Narrative, Amount");
+    let comparison_file_column_headers = build_column_headers("Amount, TransactionId");
+
+    //act
+    let actual = resolve_match_rule_column_headers(
+        &match_rule,
+        &primary_file_column_headers,
+        &comparison_file_column_headers,
+    )
+    .unwrap();
+
+    //assert
+    assert_eq!(actual.primary_file_column_indexes.get("ID"), Some(&0));
+    assert_eq!(actual.primary_file_column_indexes.get("Amount"), Some(&2));
+    assert_eq!(
+        actual.comparison_file_column_indexes.get("TransactionId"),
+        Some(&1)
+    );
+}
+
+#[actix_web::test]
+async fn test_resolve_match_rule_column_headers_given_missing_or_ambiguous_header_returns_bad_client_request(
+) {
+    //setup
+    let match_rules = vec![
+        r#"equals(primary["Value"], comparison[0])"#,
+        r#"equals(primary[0], comparison["Amount"])"#,
+    ];
+    let primary_file_column_headers = build_column_headers("ID, Amount");
+    let comparison_file_column_headers = build_column_headers("Amount, ID, Amount");
+
+    for match_rule in match_rules {
+        //act
+        let actual = resolve_match_rule_column_headers(
+            &MatchRule::parse(match_rule).unwrap(),
+            &primary_file_column_headers,
+            &comparison_file_column_headers,
+        );
+
+        //assert
+        assert_eq!(actual.unwrap_err().kind, AppErrorKind::BadClientRequest);
+    }
+}
+
+fn build_column_headers(header: &str) -> Vec<String> {
+    header.split(',').map(|s| s.to_string()).collect()
+}
+
+fn header_name_comparison_pair(
+    primary_file_column_header: &str,
+    comparison_file_column_header: &str,
+    is_row_identifier: bool,
+) -> HeaderNameComparisonPair {
+    HeaderNameComparisonPair {
+        primary_file_column_header: primary_file_column_header.to_string(),
+        comparison_file_column_header: comparison_file_column_header.to_string(),
+        is_row_identifier,
+        options: ComparisonPairOptions::default(),
+    }
+}
+
+fn comparison_pair(
+    primary_file_column_index: usize,
+    comparison_file_column_index: usize,
+    is_row_identifier: bool,
+) -> ComparisonPair {
+    ComparisonPair {
+        primary_file_column_index,
+        comparison_file_column_index,
+        is_row_identifier,
+    }
+}
@@ -0,0 +1,67 @@
+use crate::internal::models::entities::currency_conversion::{CurrencyConversion, FxConversion};
+
+//converts the amounts of both files into the base currency so that they can be compared
+pub fn convert_amounts_to_base_currency(
+    primary_file_amount: &str,
+    primary_file_currency: &String,
+    comparison_file_amount: &str,
+    comparison_file_currency: &String,
+    currency_conversion: &CurrencyConversion,
+) -> Result<FxConversion, String> {
+    let primary_file_rate = get_rate(primary_file_currency, currency_conversion)?;
+    let comparison_file_rate = get_rate(comparison_file_currency, currency_conversion)?;
+
+    return Ok(FxConversion {
+        base_currency: currency_conversion.base_currency.trim().to_string(),
+        primary_file_currency: primary_file_currency.trim().to_string(),
+        primary_file_rate,
+        primary_file_base_currency_amount: parse_amount(primary_file_amount)? * primary_file_rate,
+        comparison_file_currency: comparison_file_currency.trim().to_string(),
+        comparison_file_rate,
+        comparison_file_base_currency_amount: parse_amount(comparison_file_amount)?
+            * comparison_file_rate,
+        tolerance: currency_conversion.tolerance,
+    });
+}
+
+//gets the rate of a currency, rates loaded from a redis hash are not validated
+//with the reconciliation options so an unusable rate is rejected here
+fn get_rate(currency: &String, currency_conversion: &CurrencyConversion) -> Result<f64, String> {
+    let rate = currency_conversion
+        .fx_rate_table
+        .get_rate(currency, &currency_conversion.base_currency)
+        .ok_or(format!(
+            "no rate to convert [{}] into [{}]",
+            currency.trim(),
+            currency_conversion.base_currency.trim()
+        ))?;
+
+    if rate <= 0.0 || !rate.is_finite() {
+        return Err(format!(
+            "rate [{}] for [{}] is not a number greater than zero",
+            rate,
+            currency.trim()
+        ));
+    }
+
+    return Ok(rate);
+}
+
+//parses an amount, ignoring white space and thousands separators.
+//"NaN" and "inf" parse as floats but are not amounts so they are rejected
+pub fn parse_amount(amount: &str) -> Result<f64, String> {
+    let amount = amount.trim();
+
+    //amounts are parsed for every candidate row, so they are only copied to drop separators
+    let parse_result = match amount.contains(',') {
+        true => amount.replace(',', "").parse::<f64>(),
+        false => amount.parse::<f64>(),
+    };
+    let parsed_amount = parse_result.map_err(|_| format!("amount [{}] is not a number", amount))?;
+
+    if !parsed_amount.is_finite() {
+        return Err(format!("amount [{}] is not a number", amount));
+    }
+
+    return Ok(parsed_amount);
+}
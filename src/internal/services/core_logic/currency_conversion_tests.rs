@@ -0,0 +1,147 @@
+use std::collections::HashMap;
+
+use crate::internal::models::entities::currency_conversion::{
+    validate_currency_conversion, CurrencyConversion, FxRateTable,
+};
+
+use super::currency_conversion::{convert_amounts_to_base_currency, parse_amount};
+
+#[actix_web::test]
+async fn test_convert_amounts_to_base_currency_converts_both_amounts_using_their_rates() {
+    //setup
+    let currency_conversion = usd_currency_conversion(0.0);
+
+    //act
+    let actual = convert_amounts_to_base_currency(
+        &String::from("100"),
+        &String::from("EUR"),
+        &String::from("1,100.00"),
+        &String::from(" usd"),
+        &currency_conversion,
+    )
+    .unwrap();
+
+    //assert
+    assert_eq!(actual.primary_file_rate, 1.1);
+    assert_eq!(actual.comparison_file_rate, 1.0);
+    assert_eq!(actual.comparison_file_base_currency_amount, 1100.0);
+    assert_eq!(actual.is_within_tolerance(), false);
+}
+
+#[actix_web::test]
+async fn test_convert_amounts_to_base_currency_given_difference_within_tolerance_is_within_tolerance(
+) {
+    //setup
+    let currency_conversion = usd_currency_conversion(0.5);
+
+    //act
+    let actual = convert_amounts_to_base_currency(
+        &String::from("370000"),
+        &String::from("UGX"),
+        &String::from("99.6"),
+        &String::from("USD"),
+        &currency_conversion,
+    )
+    .unwrap();
+
+    //assert
+    assert_eq!(actual.is_within_tolerance(), true);
+}
+
+#[actix_web::test]
+async fn test_convert_amounts_to_base_currency_given_currency_without_rate_returns_error() {
+    //setup
+    let currency_conversion = usd_currency_conversion(0.0);
+
+    //act
+    let actual = convert_amounts_to_base_currency(
+        &String::from("100"),
+        &String::from("KES"),
+        &String::from("100"),
+        &String::from("USD"),
+        &currency_conversion,
+    );
+
+    //assert
+    assert_eq!(actual.is_err(), true);
+}
+
+#[actix_web::test]
+async fn test_parse_amount_given_non_numeric_amount_returns_error() {
+    //act
+    let actual = parse_amount(&String::from("1O0"));
+
+    //assert
+    assert_eq!(actual.is_err(), true);
+}
+
+#[actix_web::test]
+async fn test_parse_amount_given_non_finite_amount_returns_error() {
+    for amount in ["NaN", "inf", "-inf", "infinity"] {
+        //act
+        let actual = parse_amount(&String::from(amount));
+
+        //assert
+        assert_eq!(actual.is_err(), true, "amount [{}] was parsed", amount);
+    }
+}
+
+#[actix_web::test]
+async fn test_convert_amounts_to_base_currency_given_loaded_rate_that_is_not_positive_returns_error(
+) {
+    for rate in [0.0, -1.1, f64::NAN, f64::INFINITY] {
+        //setup
+        //rates loaded from a redis hash replace the table without being validated
+        let currency_conversion = CurrencyConversion {
+            fx_rate_table: FxRateTable::Inline {
+                rates: HashMap::from([(String::from("EUR"), rate)]),
+            },
+            ..usd_currency_conversion(0.0)
+        };
+
+        //act
+        let actual = convert_amounts_to_base_currency(
+            &String::from("100"),
+            &String::from("EUR"),
+            &String::from("110"),
+            &String::from("USD"),
+            &currency_conversion,
+        );
+
+        //assert
+        assert_eq!(
+            actual,
+            Err(format!(
+                "rate [{}] for [EUR] is not a number greater than zero",
+                rate
+            ))
+        );
+    }
+}
+
+#[actix_web::test]
+async fn test_validate_currency_conversion_given_zero_rate_returns_error() {
+    //setup
+    let mut currency_conversion = usd_currency_conversion(0.0);
+    currency_conversion.fx_rate_table = FxRateTable::Inline {
+        rates: HashMap::from([(String::from("EUR"), 0.0)]),
+    };
+
+    //act
+    let actual = validate_currency_conversion(&currency_conversion);
+
+    //assert
+    assert_eq!(actual.is_err(), true);
+}
+
+fn usd_currency_conversion(tolerance: f64) -> CurrencyConversion {
+    CurrencyConversion {
+        primary_file_currency_column_index: 2,
+        comparison_file_currency_column_index: 2,
+        base_currency: String::from("USD"),
+        fx_rate_table: FxRateTable::Inline {
+            rates: HashMap::from([(String::from("EUR"), 1.1), (String::from("UGX"), 0.00027)]),
+        },
+        tolerance,
+    }
+}
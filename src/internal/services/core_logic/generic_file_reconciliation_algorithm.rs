@@ -1,207 +1,1120 @@
 use async_trait::async_trait;
+use rayon::prelude::*;
+use std::{borrow::Cow, sync::Arc};
 
 use crate::internal::{
     interfaces::file_reconciliation_algorithm::FileReconciliationAlgorithmInterface,
+    models::entities::{
+        comparison_pair_options::{ComparisonPairOptions, MismatchSeverity},
+        currency_conversion::{CurrencyConversion, FxConversion},
+        derived_column::DerivedColumn,
+        recon_result_reason::{ColumnComparator, ReconResultReason, ReconResultReasonCode},
+        reconciliation_options::{
+            CandidateAssignment, ReconciliationOptions, SuccessfulRowHandling,
+        },
+        row_reconciliation_details::{
+            ReconOutcome, RowReconciliationDetails, RowsReconciliationDetailsIndex,
+        },
+    },
+    services::core_logic::{
+        best_match_assignment::{
+            assign_best_matches, AmbiguousMatch, MatchCandidate, MatchCandidateScore,
+        },
+        column_derivation::get_derived_column,
+        column_value_preparation::ColumnValuePreparer,
+        currency_conversion::{convert_amounts_to_base_currency, parse_amount},
+        parallel_row_reconciliation::{
+            reconcile_rows_in_parallel, reconcile_rows_on_blocking_pool,
+        },
+        row_identifier_index::{get_row_identifiers, RowIdentifierIndex},
+    },
     shared_reconciler_rust_libraries::models::entities::{
         app_errors::AppError,
-        file_upload_chunk::{FileUploadChunk, ReconStatus},
+        file_upload_chunk::{FileUploadChunk, FileUploadChunkRow, ReconStatus},
         recon_tasks_models::{ComparisonPair, ReconciliationConfigs},
     },
 };
 
 pub struct GenericFileReconciliationAlgorithm {}
 
+//the outcome of comparing the columns of a primary file row with a comparison file row
+struct RowComparison {
+    has_critical_mismatch: bool,
+
+    //the mismatches as they are appended to the recon result reasons of the row
+    mismatch_descriptions: Vec<String>,
+
+    recon_result_reasons: Vec<ReconResultReason>,
+
+    //the total difference between the pairs compared as amounts
+    amount_distance: f64,
+}
+
+//what is worked out once for a pair of chunks, so that comparing a primary file row
+//with each of its candidates does not look up, prepare or copy anything again
+struct PreparedChunks<'a> {
+    column_value_preparer: ColumnValuePreparer,
+
+    row_identifier_index: RowIdentifierIndex,
+
+    //the comparison pairs that are not row identifiers, along with their options
+    compared_pairs: Vec<(ComparisonPair, ComparisonPairOptions)>,
+
+    //the prepared value of every compared pair in each comparison file row, by row position.
+    //a value is None if the row does not have the column or the value has no mapping,
+    //and is only copied if preparing it changed it
+    comparison_file_rows_values: Vec<Vec<Option<Cow<'a, str>>>>,
+}
+
 #[async_trait]
 impl FileReconciliationAlgorithmInterface for GenericFileReconciliationAlgorithm {
     async fn reconcile_primary_file_chunk(
+        &self,
+        primary_file_chunk: &mut FileUploadChunk,
+        comparison_file_chunk: &Arc<FileUploadChunk>,
+        reconciliation_options: &ReconciliationOptions,
+        rows_reconciliation_details: &mut Vec<RowReconciliationDetails>,
+    ) -> Result<(), AppError> {
+        return reconcile_rows_on_blocking_pool(
+            primary_file_chunk,
+            comparison_file_chunk,
+            reconciliation_options,
+            rows_reconciliation_details,
+            |primary_file_chunk,
+             comparison_file_chunk,
+             reconciliation_options,
+             rows_reconciliation_details| {
+                GenericFileReconciliationAlgorithm {}.reconcile_rows(
+                    primary_file_chunk,
+                    comparison_file_chunk,
+                    reconciliation_options,
+                    rows_reconciliation_details,
+                )
+            },
+        )
+        .await;
+    }
+}
+
+impl GenericFileReconciliationAlgorithm {
+    //reconciles the rows of the primary file chunk in parallel on the rayon thread pool.
+    //every row only touches its own details, and the rows are merged back in
+    //their original order, so the outcome does not depend on how the rows were scheduled
+    pub fn reconcile_rows(
         &self,
         primary_file_chunk: &mut FileUploadChunk,
         comparison_file_chunk: &FileUploadChunk,
-    ) -> Result<FileUploadChunk, AppError> {
-        //for each row in the primary file chunk
-        for (index, primary_chunk_row) in primary_file_chunk
-            .clone()
-            .chunk_rows
-            .into_iter()
-            .enumerate()
-        {
-            //if the row has already failed reconciliation, we can skip it
-            if primary_chunk_row.recon_result == ReconStatus::Failed {
+        reconciliation_options: &ReconciliationOptions,
+        rows_reconciliation_details: &mut Vec<RowReconciliationDetails>,
+    ) -> Result<(), AppError> {
+        //the value transforms of the comparison pairs are compiled, and the identifiers
+        //and compared values of the comparison rows prepared, once for the whole chunk
+        let prepared_chunks = self.prepare_chunks(
+            primary_file_chunk,
+            comparison_file_chunk,
+            reconciliation_options,
+        )?;
+
+        //the rows are moved out while they are reconciled, since reconciling a row
+        //reads the comparison pairs and headers of the chunk it belongs to
+        let mut chunk_rows = std::mem::take(&mut primary_file_chunk.chunk_rows);
+
+        reconcile_rows_in_parallel(
+            &mut chunk_rows,
+            rows_reconciliation_details,
+            |primary_file_row_index, primary_chunk_row, row_reconciliation_details| {
+                self.reconcile_row(
+                    primary_file_chunk,
+                    primary_file_row_index,
+                    primary_chunk_row,
+                    row_reconciliation_details,
+                    comparison_file_chunk,
+                    reconciliation_options,
+                    &prepared_chunks,
+                )
+            },
+        );
+
+        primary_file_chunk.chunk_rows = chunk_rows;
+
+        //the details of the rows are looked up by row number from here on
+        let mut rows_details_index =
+            RowsReconciliationDetailsIndex::new(rows_reconciliation_details);
+
+        if reconciliation_options.candidate_assignment == CandidateAssignment::BestMatch {
+            self.assign_best_matches(
+                primary_file_chunk,
+                comparison_file_chunk,
+                reconciliation_options,
+                &prepared_chunks,
+                &mut rows_details_index,
+            );
+        }
+
+        //we work out the finer grained outcome of each row
+        //from its status and the severity of its mismatches
+        for chunk_row in &primary_file_chunk.chunk_rows {
+            if chunk_row.recon_result == ReconStatus::Pending {
                 continue;
             }
 
-            //we get all the columns in the primary chunk row
-            let primary_file_row_parts = primary_chunk_row.parsed_columns_from_row.clone();
-
-            //for each row in the comparison file chnk
-            for comparison_chunk_row in comparison_file_chunk.chunk_rows.clone() {
-                //we get all the columns in the comparison chunk row
-                let comparison_file_row_parts =
-                    comparison_chunk_row.parsed_columns_from_row.clone();
-
-                //get the comparison pairs that are used to uniquely identify the same row in both files
-                let row_id_comparison_pairs = primary_file_chunk
-                    .clone()
-                    .get_row_identifier_comparison_pairs();
-
-                //we then check if this is supposed to be the same row in both files
-                //by checking the identity columns in the comparison comparison_pairs
-                if !self.are_same_row_identifiers(
-                    &primary_file_row_parts,
-                    &comparison_file_row_parts,
-                    &row_id_comparison_pairs,
-                    &primary_file_chunk.recon_config,
-                ) {
-                    continue;
+            let row_reconciliation_details =
+                rows_details_index.find_or_insert(chunk_row.row_number);
+            row_reconciliation_details.recon_outcome =
+                self.get_recon_outcome(&chunk_row.recon_result, row_reconciliation_details);
+        }
+
+        return Ok(());
+    }
+
+    //prepares what the rows of the primary file chunk are compared with once for the pair
+    //of chunks: the compiled value transforms, the identifiers of the rows of both chunks
+    //and the compared values of every comparison file row
+    fn prepare_chunks<'a>(
+        &self,
+        primary_file_chunk: &FileUploadChunk,
+        comparison_file_chunk: &'a FileUploadChunk,
+        reconciliation_options: &ReconciliationOptions,
+    ) -> Result<PreparedChunks<'a>, AppError> {
+        let column_value_preparer =
+            ColumnValuePreparer::new(&primary_file_chunk.comparison_pairs, reconciliation_options)?;
+
+        let row_identifier_index = RowIdentifierIndex::new(
+            primary_file_chunk,
+            comparison_file_chunk,
+            &column_value_preparer,
+        );
+
+        let compared_pairs: Vec<(ComparisonPair, ComparisonPairOptions)> = primary_file_chunk
+            .get_comparison_pairs_that_are_not_row_identifiers()
+            .into_iter()
+            .map(|pair| {
+                let pair_options = reconciliation_options.get_comparison_pair_options(&pair);
+                (pair, pair_options)
+            })
+            .collect();
+
+        let comparison_file_rows_values = comparison_file_chunk
+            .chunk_rows
+            .par_iter()
+            .map(|comparison_chunk_row| {
+                compared_pairs
+                    .iter()
+                    .map(|(pair, _)| {
+                        let column_value = comparison_chunk_row
+                            .parsed_columns_from_row
+                            .get(pair.comparison_file_column_index)?;
+
+                        let prepared_column_value = column_value_preparer
+                            .prepare_comparison_file_column_value(pair, column_value)?;
+
+                        //most values are compared as they are, so the row keeps them borrowed
+                        if prepared_column_value == *column_value {
+                            return Some(Cow::Borrowed(column_value.as_str()));
+                        }
+                        return Some(Cow::Owned(prepared_column_value));
+                    })
+                    .collect()
+            })
+            .collect();
+
+        return Ok(PreparedChunks {
+            column_value_preparer,
+            row_identifier_index,
+            compared_pairs,
+            comparison_file_rows_values,
+        });
+    }
+
+    //prepares the compared values of a primary file row, by the position of their comparison pair.
+    //a value is None if the row does not have the column
+    fn prepare_primary_file_row_values(
+        &self,
+        primary_chunk_row: &FileUploadChunkRow,
+        prepared_chunks: &PreparedChunks,
+    ) -> Vec<Option<String>> {
+        return prepared_chunks
+            .compared_pairs
+            .iter()
+            .map(|(pair, _)| {
+                primary_chunk_row
+                    .parsed_columns_from_row
+                    .get(pair.primary_file_column_index)
+                    .map(|column_value| {
+                        prepared_chunks
+                            .column_value_preparer
+                            .prepare_primary_file_column_value(pair, column_value)
+                    })
+            })
+            .collect();
+    }
+
+    //reconciles a single primary file row against the comparison file chunk
+    fn reconcile_row(
+        &self,
+        primary_file_chunk: &FileUploadChunk,
+        primary_file_row_index: usize,
+        primary_chunk_row: &mut FileUploadChunkRow,
+        row_reconciliation_details: &mut RowReconciliationDetails,
+        comparison_file_chunk: &FileUploadChunk,
+        reconciliation_options: &ReconciliationOptions,
+        prepared_chunks: &PreparedChunks,
+    ) {
+        //if the row has already failed reconciliation, we can skip it
+        if primary_chunk_row.recon_result == ReconStatus::Failed {
+            return;
+        }
+
+        //the row was already matched against an earlier comparison file chunk
+        if primary_chunk_row.recon_result == ReconStatus::Successful {
+            if reconciliation_options.successful_row_handling
+                == SuccessfulRowHandling::CheckAgainstAllComparisonChunks
+            {
+                self.check_for_cross_chunk_duplicate(
+                    primary_file_row_index,
+                    primary_chunk_row,
+                    row_reconciliation_details,
+                    comparison_file_chunk,
+                    &prepared_chunks.row_identifier_index,
+                );
+            }
+            return;
+        }
+
+        //when every comparison row may only be matched once, pending rows are
+        //assigned their best candidate once every row has been scored
+        if reconciliation_options.candidate_assignment == CandidateAssignment::BestMatch {
+            return;
+        }
+
+        //the comparison rows that are supposed to be the same row in both files,
+        //going by the identity columns in the comparison pairs
+        let comparison_file_row_indexes = prepared_chunks
+            .row_identifier_index
+            .get_comparison_file_row_indexes(primary_file_row_index);
+
+        if comparison_file_row_indexes.is_empty() {
+            return;
+        }
+
+        //the values of the row are prepared once for all of its candidates
+        let primary_file_row_values =
+            self.prepare_primary_file_row_values(primary_chunk_row, prepared_chunks);
+
+        for comparison_file_row_index in comparison_file_row_indexes {
+            let comparison_chunk_row =
+                &comparison_file_chunk.chunk_rows[*comparison_file_row_index];
+
+            let row_comparison = self.compare_rows(
+                primary_file_chunk,
+                primary_chunk_row,
+                &primary_file_row_values,
+                comparison_file_chunk,
+                *comparison_file_row_index,
+                reconciliation_options,
+                prepared_chunks,
+            );
+
+            self.apply_row_comparison(
+                primary_chunk_row,
+                row_reconciliation_details,
+                comparison_file_chunk,
+                comparison_chunk_row,
+                row_comparison,
+            );
+        }
+    }
+
+    //applies the comparison of a primary file row with a comparison file row that has the same
+    //identifiers, marking the primary file row as successful if every critical column matches
+    fn apply_row_comparison(
+        &self,
+        primary_chunk_row: &mut FileUploadChunkRow,
+        row_reconciliation_details: &mut RowReconciliationDetails,
+        comparison_file_chunk: &FileUploadChunk,
+        comparison_chunk_row: &FileUploadChunkRow,
+        row_comparison: RowComparison,
+    ) {
+        if row_comparison.has_critical_mismatch {
+            primary_chunk_row.recon_result = ReconStatus::Failed;
+        }
+        primary_chunk_row
+            .recon_result_reasons
+            .extend(row_comparison.mismatch_descriptions);
+
+        row_reconciliation_details
+            .recon_result_reasons
+            .extend(row_comparison.recon_result_reasons);
+
+        //record is still pending reconciliation but all the column values in the row are a perfect match
+        if primary_chunk_row.recon_result == ReconStatus::Pending {
+            primary_chunk_row.recon_result = ReconStatus::Successful;
+
+            //we keep track of the row it was matched with so the pairing can be audited
+            row_reconciliation_details.matched_comparison_row_number =
+                Some(comparison_chunk_row.row_number);
+            row_reconciliation_details.matched_comparison_file_chunk_id =
+                Some(comparison_file_chunk.id.clone());
+            row_reconciliation_details.matched_comparison_upload_request_id =
+                Some(comparison_file_chunk.upload_request_id.clone());
+        }
+    }
+
+    //scores every pending primary file row against each comparison row with the same
+    //identifiers, then reconciles each row with the candidate it was assigned.
+    //a comparison row is only assigned once between this pair of chunks, the rows of
+    //another primary file chunk are reconciled separately and can be assigned it again
+    fn assign_best_matches(
+        &self,
+        primary_file_chunk: &mut FileUploadChunk,
+        comparison_file_chunk: &FileUploadChunk,
+        reconciliation_options: &ReconciliationOptions,
+        prepared_chunks: &PreparedChunks,
+        rows_details_index: &mut RowsReconciliationDetailsIndex,
+    ) {
+        //the rows are scored in parallel, but the assignment itself
+        //looks at every candidate at once so it runs on its own
+        let candidates: Vec<MatchCandidate> = primary_file_chunk
+            .chunk_rows
+            .par_iter()
+            .enumerate()
+            .filter(|(_, primary_chunk_row)| primary_chunk_row.recon_result == ReconStatus::Pending)
+            .flat_map_iter(|(index, primary_chunk_row)| {
+                let comparison_file_row_indexes = prepared_chunks
+                    .row_identifier_index
+                    .get_comparison_file_row_indexes(index);
+
+                if comparison_file_row_indexes.is_empty() {
+                    return vec![];
                 }
 
-                //if the columns to compare doesnt even match
-                //we shouldnt even try reconciling the rows
-                if primary_file_row_parts.len() != comparison_file_row_parts.len() {
-                    //we mark the row as failed reconciliation
-                    let reason = format!(
-                        "Count of columns to compare does not match. RowNumber: [{}], PrimaryFile Has [{}] columns to compare, ComparisonFile has [{}] columns to compare",
-                        primary_chunk_row.row_number,
-                        primary_file_row_parts.len(),
-                        comparison_file_row_parts.len()
-                    );
-                    primary_file_chunk.chunk_rows[index].recon_result = ReconStatus::Failed;
-                    primary_file_chunk.chunk_rows[index]
-                        .recon_result_reasons
-                        .push(reason);
+                let primary_file_row_values =
+                    self.prepare_primary_file_row_values(primary_chunk_row, prepared_chunks);
+
+                return comparison_file_row_indexes
+                    .iter()
+                    .map(|comparison_index| MatchCandidate {
+                        primary_file_row_index: index,
+                        comparison_file_row_index: *comparison_index,
+                        score: self.score_match_candidate(
+                            primary_file_chunk,
+                            primary_chunk_row,
+                            &primary_file_row_values,
+                            comparison_file_chunk,
+                            *comparison_index,
+                            reconciliation_options,
+                            prepared_chunks,
+                        ),
+                    })
+                    .collect();
+            })
+            .collect();
+
+        let (assigned_candidates, ambiguous_matches) = assign_best_matches(candidates);
+
+        //the rows are moved out while they are reconciled, since reconciling a row
+        //reads the comparison pairs and headers of the chunk it belongs to
+        let mut primary_chunk_rows = std::mem::take(&mut primary_file_chunk.chunk_rows);
+
+        for assigned_candidate in assigned_candidates {
+            let primary_chunk_row =
+                &mut primary_chunk_rows[assigned_candidate.primary_file_row_index];
+            let row_reconciliation_details =
+                rows_details_index.find_or_insert(primary_chunk_row.row_number);
+
+            let comparison_chunk_row =
+                &comparison_file_chunk.chunk_rows[assigned_candidate.comparison_file_row_index];
+
+            let primary_file_row_values =
+                self.prepare_primary_file_row_values(primary_chunk_row, prepared_chunks);
+
+            let row_comparison = self.compare_rows(
+                primary_file_chunk,
+                primary_chunk_row,
+                &primary_file_row_values,
+                comparison_file_chunk,
+                assigned_candidate.comparison_file_row_index,
+                reconciliation_options,
+                prepared_chunks,
+            );
+
+            self.apply_row_comparison(
+                primary_chunk_row,
+                row_reconciliation_details,
+                comparison_file_chunk,
+                comparison_chunk_row,
+                row_comparison,
+            );
+        }
+
+        primary_file_chunk.chunk_rows = primary_chunk_rows;
+
+        for ambiguous_match in ambiguous_matches {
+            self.fail_ambiguous_match(
+                primary_file_chunk,
+                comparison_file_chunk,
+                &ambiguous_match,
+                rows_details_index,
+            );
+        }
+    }
+
+    //compares the rows without touching the primary file row, scoring the comparison row
+    //by its mismatching columns and by how far apart the pairs compared as amounts are
+    fn score_match_candidate(
+        &self,
+        primary_file_chunk: &FileUploadChunk,
+        primary_chunk_row: &FileUploadChunkRow,
+        primary_file_row_values: &Vec<Option<String>>,
+        comparison_file_chunk: &FileUploadChunk,
+        comparison_file_row_index: usize,
+        reconciliation_options: &ReconciliationOptions,
+        prepared_chunks: &PreparedChunks,
+    ) -> MatchCandidateScore {
+        let row_comparison = self.compare_rows(
+            primary_file_chunk,
+            primary_chunk_row,
+            primary_file_row_values,
+            comparison_file_chunk,
+            comparison_file_row_index,
+            reconciliation_options,
+            prepared_chunks,
+        );
+
+        return MatchCandidateScore::new(
+            row_comparison.has_critical_mismatch,
+            row_comparison.recon_result_reasons.len(),
+            row_comparison.amount_distance,
+        );
+    }
+
+    //fails a primary file row that cannot be matched without picking between equally good candidates
+    fn fail_ambiguous_match(
+        &self,
+        primary_file_chunk: &mut FileUploadChunk,
+        comparison_file_chunk: &FileUploadChunk,
+        ambiguous_match: &AmbiguousMatch,
+        rows_details_index: &mut RowsReconciliationDetailsIndex,
+    ) {
+        let row_number =
+            primary_file_chunk.chunk_rows[ambiguous_match.primary_file_row_index].row_number;
+
+        let comparison_file_row_numbers: Vec<u64> = ambiguous_match
+            .comparison_file_row_indexes
+            .iter()
+            .map(|comparison_index| comparison_file_chunk.chunk_rows[*comparison_index].row_number)
+            .collect();
+
+        let reason = match ambiguous_match.competing_primary_file_row_indexes.is_empty() {
+            true => format!(
+                "RowNumber: [{}], ComparisonFile rows {:?} match the row equally well",
+                row_number, comparison_file_row_numbers
+            ),
+            false => format!(
+                "RowNumber: [{}], ComparisonFile row {:?} matches PrimaryFile rows {:?} equally well",
+                row_number,
+                comparison_file_row_numbers,
+                ambiguous_match
+                    .competing_primary_file_row_indexes
+                    .iter()
+                    .map(|index| primary_file_chunk.chunk_rows[*index].row_number)
+                    .collect::<Vec<u64>>()
+            ),
+        };
+
+        rows_details_index
+            .find_or_insert(row_number)
+            .recon_result_reasons
+            .extend(
+                comparison_file_row_numbers
+                    .iter()
+                    .map(|comparison_file_row_number| ReconResultReason {
+                        reason_code: ReconResultReasonCode::AmbiguousMatch,
+                        matched_comparison_row_number: Some(*comparison_file_row_number),
+                        comparison_file_chunk_id: comparison_file_chunk.id.clone(),
+                        ..ReconResultReason::default()
+                    }),
+            );
+
+        let primary_chunk_row =
+            &mut primary_file_chunk.chunk_rows[ambiguous_match.primary_file_row_index];
+        primary_chunk_row.recon_result = ReconStatus::Failed;
+        primary_chunk_row.recon_result_reasons.push(reason);
+    }
+
+    //compares every non identifier column of the 2 rows without touching either of them,
+    //so the same primary file row can be compared with many candidates
+    fn compare_rows(
+        &self,
+        primary_file_chunk: &FileUploadChunk,
+        primary_chunk_row: &FileUploadChunkRow,
+        primary_file_row_values: &Vec<Option<String>>,
+        comparison_file_chunk: &FileUploadChunk,
+        comparison_file_row_index: usize,
+        reconciliation_options: &ReconciliationOptions,
+        prepared_chunks: &PreparedChunks,
+    ) -> RowComparison {
+        let comparison_chunk_row = &comparison_file_chunk.chunk_rows[comparison_file_row_index];
+        let comparison_file_row_values =
+            &prepared_chunks.comparison_file_rows_values[comparison_file_row_index];
+
+        //we get all the columns in both rows
+        let primary_file_row_parts = &primary_chunk_row.parsed_columns_from_row;
+        let comparison_file_row_parts = &comparison_chunk_row.parsed_columns_from_row;
+
+        //we keep track of every column that does not match
+        //so the row can be fixed in one go
+        let mut row_comparison = RowComparison {
+            has_critical_mismatch: false,
+            mismatch_descriptions: vec![],
+            recon_result_reasons: vec![],
+            amount_distance: 0.0,
+        };
+
+        //if its supposed to be the same row, we can begin checking each column in the primary file row
+        //vs each column in the comparison file row using the comparison pairs
+        for (pair_index, (pair, pair_options)) in prepared_chunks.compared_pairs.iter().enumerate()
+        {
+            //only critical mismatches fail the row, the rest are just reported
+            let is_critical = pair_options.severity == MismatchSeverity::Critical;
+
+            //the two files can have different column sets, so we only
+            //check that the columns referenced by this pair exist in both rows
+            if !self.are_pair_columns_present(
+                primary_file_row_parts,
+                comparison_file_row_parts,
+                pair,
+            ) {
+                let reason = format!(
+                    "RowNumber: [{}], Comparison pair cannot be compared. PrimaryFile column index [{}] (row has [{}] columns), ComparisonFile column index [{}] (row has [{}] columns){}",
+                    primary_chunk_row.row_number,
+                    pair.primary_file_column_index,
+                    primary_file_row_parts.len(),
+                    pair.comparison_file_column_index,
+                    comparison_file_row_parts.len(),
+                    self.describe_severity(&pair_options.severity)
+                );
+
+                row_comparison.has_critical_mismatch |= is_critical;
+                row_comparison.mismatch_descriptions.push(reason);
+
+                row_comparison.recon_result_reasons.push(ReconResultReason {
+                    reason_code: ReconResultReasonCode::ColumnMissing,
+                    primary_file_column_index: Some(pair.primary_file_column_index),
+                    comparison_file_column_index: Some(pair.comparison_file_column_index),
+                    severity: Some(pair_options.severity.clone()),
+                    matched_comparison_row_number: Some(comparison_chunk_row.row_number),
+                    comparison_file_chunk_id: comparison_file_chunk.id.clone(),
+                    ..ReconResultReason::default()
+                });
+
+                if reconciliation_options.should_stop_at_first_column_mismatch && is_critical {
                     break;
                 }
+                continue;
+            }
 
-                //if its supposed to be the same row, we can begin checking each column in the primary file row
-                //vs each column in the comparison file row using the comparison pairs
-                let comparison_pairs =
-                    primary_file_chunk.get_comparison_pairs_that_are_not_row_identifiers();
-
-                for pair in comparison_pairs {
-                    //ok its time to compare actual values in the row
-                    //so we read the column value from the primary file
-                    let primary_file_row_column_value = primary_file_row_parts
-                        .get(pair.primary_file_column_index)
-                        .map(|s| s.to_owned())
-                        .unwrap_or(String::from(""));
-
-                    //and we also read the column value from the comparison file
-                    let comparison_file_row_column_value = comparison_file_row_parts
-                        .get(pair.comparison_file_column_index)
-                        .map(|s| s.to_owned())
-                        .unwrap_or(String::from(""));
-
-                    //we check if the values match
-                    if !self.are_column_values_the_same(
-                        &primary_file_row_column_value,
-                        &comparison_file_row_column_value,
-                        &primary_file_chunk.recon_config,
-                    ) {
-                        //if they dont match, then we
-                        // mark the row as failed reconciliation
-
-                        let primary_file_column_header = primary_file_chunk
-                            .column_headers
-                            .get(pair.primary_file_column_index)
-                            .map(|s| s.to_owned())
-                            .unwrap_or(String::from(""));
-
-                        let comparison_file_column_header = comparison_file_chunk
-                            .column_headers
-                            .get(pair.comparison_file_column_index)
-                            .map(|s| s.to_owned())
-                            .unwrap_or(String::from(""));
+            //ok its time to compare actual values in the row
+            //so we read the column value from the primary file
+            let primary_file_row_column_value =
+                &primary_file_row_parts[pair.primary_file_column_index];
 
-                        let reason = format!(
-                            "RowNumber: [{}], Column [{}]: PrimaryFile has value [{}] while ComparisonFile has value [{}] in Column [{}]",
+            //and we also read the column value from the comparison file
+            let comparison_file_row_column_value =
+                &comparison_file_row_parts[pair.comparison_file_column_index];
+
+            //a comparison file value that has no mapping in the vocabulary
+            //of the primary file cannot be compared
+            let prepared_comparison_file_row_column_value = match comparison_file_row_values
+                [pair_index]
+                .as_deref()
+            {
+                Some(prepared_column_value) => prepared_column_value,
+                None => {
+                    let comparison_file_column_header = self.get_column_header(
+                        comparison_file_chunk,
+                        pair.comparison_file_column_index,
+                    );
+
+                    let reason = format!(
+                            "RowNumber: [{}], ComparisonFile value [{}] in Column [{}] has no mapping{}",
                             primary_chunk_row.row_number,
-                            primary_file_column_header,
-                            primary_file_row_column_value,
                             comparison_file_row_column_value,
                             comparison_file_column_header,
+                            self.describe_severity(&pair_options.severity)
                         );
 
-                        //a record can fail reconciliation for many reasons
-                        //which is why we just append
-                        primary_file_chunk.chunk_rows[index].recon_result = ReconStatus::Failed;
-                        primary_file_chunk.chunk_rows[index]
-                            .recon_result_reasons
-                            .push(reason);
+                    row_comparison.has_critical_mismatch |= is_critical;
+                    row_comparison.mismatch_descriptions.push(reason);
+
+                    row_comparison.recon_result_reasons.push(ReconResultReason {
+                        reason_code: ReconResultReasonCode::UnmappedValue,
+                        primary_file_column_header: self
+                            .get_column_header(primary_file_chunk, pair.primary_file_column_index),
+                        primary_file_column_index: Some(pair.primary_file_column_index),
+                        primary_file_column_value: primary_file_row_column_value.clone(),
+                        comparison_file_column_header,
+                        comparison_file_column_index: Some(pair.comparison_file_column_index),
+                        comparison_file_column_value: comparison_file_row_column_value.clone(),
+                        severity: Some(pair_options.severity.clone()),
+                        matched_comparison_row_number: Some(comparison_chunk_row.row_number),
+                        comparison_file_chunk_id: comparison_file_chunk.id.clone(),
+                        ..ReconResultReason::default()
+                    });
+
+                    if reconciliation_options.should_stop_at_first_column_mismatch && is_critical {
                         break;
                     }
+                    continue;
                 }
+            };
 
-                //record is still pending reconciliation but all the column values in the row are a perfect match
-                if primary_file_chunk.chunk_rows[index].recon_result == ReconStatus::Pending {
-                    primary_file_chunk.chunk_rows[index].recon_result = ReconStatus::Successful;
-                    continue;
+            let prepared_primary_file_row_column_value = primary_file_row_values[pair_index]
+                .as_deref()
+                .unwrap_or_default();
+
+            //amounts in different currencies are compared once converted into a base currency
+            let fx_conversion = match &pair_options.currency_conversion {
+                None => None,
+                Some(currency_conversion) => match self.convert_pair_amounts_to_base_currency(
+                    primary_file_row_parts,
+                    comparison_file_row_parts,
+                    prepared_primary_file_row_column_value,
+                    prepared_comparison_file_row_column_value,
+                    currency_conversion,
+                ) {
+                    Ok(fx_conversion) => Some(fx_conversion),
+                    Err(error) => {
+                        let reason = format!(
+                            "RowNumber: [{}], unable to compare the amounts in [{}]: {}{}",
+                            primary_chunk_row.row_number,
+                            currency_conversion.base_currency,
+                            error,
+                            self.describe_severity(&pair_options.severity)
+                        );
+
+                        row_comparison.has_critical_mismatch |= is_critical;
+                        row_comparison.mismatch_descriptions.push(reason);
+
+                        row_comparison.recon_result_reasons.push(ReconResultReason {
+                            reason_code: ReconResultReasonCode::CurrencyConversionFailed,
+                            primary_file_column_index: Some(pair.primary_file_column_index),
+                            primary_file_column_value: primary_file_row_column_value.clone(),
+                            comparison_file_column_index: Some(pair.comparison_file_column_index),
+                            comparison_file_column_value: comparison_file_row_column_value.clone(),
+                            severity: Some(pair_options.severity.clone()),
+                            matched_comparison_row_number: Some(comparison_chunk_row.row_number),
+                            comparison_file_chunk_id: comparison_file_chunk.id.clone(),
+                            ..ReconResultReason::default()
+                        });
+
+                        if reconciliation_options.should_stop_at_first_column_mismatch
+                            && is_critical
+                        {
+                            break;
+                        }
+                        continue;
+                    }
+                },
+            };
+
+            //derived amounts are compared as numbers, as are the pairs given an amount tolerance
+            let is_derived_amount_pair = matches!(
+                get_derived_column(
+                    primary_file_chunk,
+                    primary_file_row_parts.len(),
+                    &reconciliation_options.primary_file_derived_columns,
+                    pair.primary_file_column_index,
+                ),
+                Some(DerivedColumn::SignedAmount(_))
+            ) || matches!(
+                get_derived_column(
+                    comparison_file_chunk,
+                    comparison_file_row_parts.len(),
+                    &reconciliation_options.comparison_file_derived_columns,
+                    pair.comparison_file_column_index,
+                ),
+                Some(DerivedColumn::SignedAmount(_))
+            );
+            let amount_tolerance = match is_derived_amount_pair {
+                true => Some(pair_options.amount_tolerance.unwrap_or(0.0)),
+                false => pair_options.amount_tolerance,
+            };
+
+            //we check if the values match once both are transformed and normalized
+            let are_column_values_the_same = match (&fx_conversion, amount_tolerance) {
+                (Some(fx_conversion), _) => fx_conversion.is_within_tolerance(),
+                (None, Some(amount_tolerance)) => self.are_amounts_the_same(
+                    prepared_primary_file_row_column_value,
+                    prepared_comparison_file_row_column_value,
+                    amount_tolerance,
+                ),
+                (None, None) => self.are_column_values_the_same(
+                    prepared_primary_file_row_column_value,
+                    prepared_comparison_file_row_column_value,
+                    &primary_file_chunk.recon_config,
+                ),
+            };
+
+            //only the pairs compared as amounts count towards how far apart the rows are,
+            //an identifier or reference that happens to be numeric says nothing about that
+            row_comparison.amount_distance += match (&fx_conversion, amount_tolerance) {
+                (Some(fx_conversion), _) => (fx_conversion.primary_file_base_currency_amount
+                    - fx_conversion.comparison_file_base_currency_amount)
+                    .abs(),
+                (None, Some(_)) => self.get_amount_distance(
+                    prepared_primary_file_row_column_value,
+                    prepared_comparison_file_row_column_value,
+                ),
+                (None, None) => 0.0,
+            };
+
+            if !are_column_values_the_same {
+                //if they dont match, then we mark the row as failed
+                //reconciliation, unless the pair is not critical
+
+                let primary_file_column_header =
+                    self.get_column_header(primary_file_chunk, pair.primary_file_column_index);
+
+                let comparison_file_column_header = self
+                    .get_column_header(comparison_file_chunk, pair.comparison_file_column_index);
+
+                let reason = format!(
+                    "RowNumber: [{}], Column [{}]: PrimaryFile has value [{}] while ComparisonFile has value [{}] in Column [{}]{}{}{}",
+                    primary_chunk_row.row_number,
+                    primary_file_column_header,
+                    primary_file_row_column_value,
+                    comparison_file_row_column_value,
+                    comparison_file_column_header,
+                    self.describe_fx_conversion(&fx_conversion),
+                    self.describe_amount_tolerance(&fx_conversion, amount_tolerance),
+                    self.describe_severity(&pair_options.severity)
+                );
+
+                //a record can fail reconciliation for many reasons
+                //which is why we just append
+                row_comparison.has_critical_mismatch |= is_critical;
+                row_comparison.mismatch_descriptions.push(reason);
+
+                row_comparison.recon_result_reasons.push(ReconResultReason {
+                    reason_code: ReconResultReasonCode::ColumnValueMismatch,
+                    primary_file_column_header,
+                    primary_file_column_index: Some(pair.primary_file_column_index),
+                    primary_file_column_value: primary_file_row_column_value.clone(),
+                    comparison_file_column_header,
+                    comparison_file_column_index: Some(pair.comparison_file_column_index),
+                    comparison_file_column_value: comparison_file_row_column_value.clone(),
+                    comparator: match (&fx_conversion, amount_tolerance) {
+                        (Some(_), _) => Some(ColumnComparator::EqualsInBaseCurrency),
+                        (None, Some(_)) => Some(ColumnComparator::EqualsWithinTolerance),
+                        (None, None) => {
+                            Some(self.get_column_comparator(&primary_file_chunk.recon_config))
+                        }
+                    },
+                    severity: Some(pair_options.severity.clone()),
+                    matched_comparison_row_number: Some(comparison_chunk_row.row_number),
+                    comparison_file_chunk_id: comparison_file_chunk.id.clone(),
+                    fx_conversion,
+                    match_rule_trace: vec![],
+                });
+
+                if reconciliation_options.should_stop_at_first_column_mismatch && is_critical {
+                    break;
                 }
             }
         }
 
-        return Ok(primary_file_chunk.clone());
+        return row_comparison;
     }
-}
 
-impl GenericFileReconciliationAlgorithm {
-    //checks to see if 2 string column values from a row in 2 different files are the same
+    //fails an already matched primary file row if a row in this comparison file chunk
+    //has the same identifiers, since it means the row is duplicated across comparison chunks
+    pub fn check_for_cross_chunk_duplicate(
+        &self,
+        primary_file_row_index: usize,
+        primary_chunk_row: &mut FileUploadChunkRow,
+        row_reconciliation_details: &mut RowReconciliationDetails,
+        comparison_file_chunk: &FileUploadChunk,
+        row_identifier_index: &RowIdentifierIndex,
+    ) {
+        let duplicate_comparison_row = row_identifier_index
+            .get_comparison_file_row_indexes(primary_file_row_index)
+            .first()
+            .map(|comparison_index| &comparison_file_chunk.chunk_rows[*comparison_index]);
+
+        if let Some(duplicate_comparison_row) = duplicate_comparison_row {
+            self.fail_cross_chunk_duplicate(
+                primary_chunk_row,
+                row_reconciliation_details,
+                comparison_file_chunk,
+                duplicate_comparison_row,
+            );
+        }
+    }
+
+    //fails a row that was already matched because another comparison file chunk
+    //has a row it would also have been matched with
+    pub fn fail_cross_chunk_duplicate(
+        &self,
+        primary_chunk_row: &mut FileUploadChunkRow,
+        row_reconciliation_details: &mut RowReconciliationDetails,
+        comparison_file_chunk: &FileUploadChunk,
+        duplicate_comparison_row: &FileUploadChunkRow,
+    ) {
+        let row_number = primary_chunk_row.row_number;
+
+        let reason = format!(
+            "RowNumber: [{}], row was matched with ComparisonFile row [{}] in chunk [{}] but ComparisonFile row [{}] in chunk [{}] has the same identifiers",
+            row_number,
+            row_reconciliation_details
+                .matched_comparison_row_number
+                .unwrap_or_default(),
+            row_reconciliation_details
+                .matched_comparison_file_chunk_id
+                .clone()
+                .unwrap_or_default(),
+            duplicate_comparison_row.row_number,
+            comparison_file_chunk.id,
+        );
+
+        row_reconciliation_details
+            .recon_result_reasons
+            .push(ReconResultReason {
+                reason_code: ReconResultReasonCode::DuplicateComparisonRow,
+                matched_comparison_row_number: Some(duplicate_comparison_row.row_number),
+                comparison_file_chunk_id: comparison_file_chunk.id.clone(),
+                ..ReconResultReason::default()
+            });
+
+        primary_chunk_row.recon_result = ReconStatus::Failed;
+        primary_chunk_row.recon_result_reasons.push(reason);
+    }
+
+    //checks to see if 2 rows from 2 different files have the same identifiers. the identifiers
+    //are prepared the same way as by the RowIdentifierIndex the chunks are reconciled with
     pub fn are_same_row_identifiers(
         &self,
         primary_file_row_parts: &Vec<String>,
         comparison_file_row_parts: &Vec<String>,
         row_id_comparison_pairs: &Vec<ComparisonPair>,
         recon_configs: &ReconciliationConfigs,
+        column_value_preparer: &ColumnValuePreparer,
     ) -> bool {
-        for pair in row_id_comparison_pairs {
-            //so we read the column value from the primary file
-            let primary_file_row_column_value = primary_file_row_parts
-                .get(pair.primary_file_column_index)
-                .map(|s| s.to_owned())
-                .unwrap_or(String::from(""));
+        let primary_file_row_identifiers = get_row_identifiers(
+            primary_file_row_parts,
+            row_id_comparison_pairs,
+            recon_configs,
+            |pair| pair.primary_file_column_index,
+            |pair, column_value| {
+                Some(column_value_preparer.prepare_primary_file_column_value(pair, column_value))
+            },
+        );
 
-            //and we also read the column value from the comparison file
-            let comparison_file_row_column_value = comparison_file_row_parts
-                .get(pair.comparison_file_column_index)
-                .map(|s| s.to_owned())
-                .unwrap_or(String::from(""));
-
-            //we check if the values match
-            if !self.are_column_values_the_same(
-                &primary_file_row_column_value,
-                &comparison_file_row_column_value,
-                &recon_configs,
-            ) {
-                return false;
+        //an identifier without a mapping cannot identify the row
+        let comparison_file_row_identifiers = get_row_identifiers(
+            comparison_file_row_parts,
+            row_id_comparison_pairs,
+            recon_configs,
+            |pair| pair.comparison_file_column_index,
+            |pair, column_value| {
+                column_value_preparer.prepare_comparison_file_column_value(pair, column_value)
+            },
+        );
+
+        //a row whose identifier column is missing cannot be identified
+        return primary_file_row_identifiers.is_some()
+            && primary_file_row_identifiers == comparison_file_row_identifiers;
+    }
+
+    //checks to see if the columns referenced by a comparison pair exist in both rows
+    pub fn are_pair_columns_present(
+        &self,
+        primary_file_row_parts: &Vec<String>,
+        comparison_file_row_parts: &Vec<String>,
+        pair: &ComparisonPair,
+    ) -> bool {
+        return pair.primary_file_column_index < primary_file_row_parts.len()
+            && pair.comparison_file_column_index < comparison_file_row_parts.len();
+    }
+
+    //works out the outcome of a row from its recon status and the severity of its mismatches
+    pub fn get_recon_outcome(
+        &self,
+        recon_status: &ReconStatus,
+        row_reconciliation_details: &RowReconciliationDetails,
+    ) -> ReconOutcome {
+        if *recon_status == ReconStatus::Failed {
+            return ReconOutcome::Failed;
+        }
+
+        if *recon_status != ReconStatus::Successful {
+            return ReconOutcome::Pending;
+        }
+
+        let has_warnings = row_reconciliation_details
+            .recon_result_reasons
+            .iter()
+            .any(|reason| reason.severity == Some(MismatchSeverity::Warning));
+
+        if has_warnings {
+            return ReconOutcome::MatchedWithWarnings;
+        }
+
+        return ReconOutcome::Matched;
+    }
+
+    //gets the header of a column, or an empty string if the chunk has no header for it
+    pub fn get_column_header(&self, file_chunk: &FileUploadChunk, column_index: usize) -> String {
+        return file_chunk
+            .column_headers
+            .get(column_index)
+            .map(|s| s.to_owned())
+            .unwrap_or(String::from(""));
+    }
+
+    //reads the currency of each amount from its row and converts both amounts into the base currency
+    pub fn convert_pair_amounts_to_base_currency(
+        &self,
+        primary_file_row_parts: &Vec<String>,
+        comparison_file_row_parts: &Vec<String>,
+        primary_file_amount: &str,
+        comparison_file_amount: &str,
+        currency_conversion: &CurrencyConversion,
+    ) -> Result<FxConversion, String> {
+        let primary_file_currency = primary_file_row_parts
+            .get(currency_conversion.primary_file_currency_column_index)
+            .ok_or(format!(
+                "PrimaryFile currency column index [{}] does not exist in the row",
+                currency_conversion.primary_file_currency_column_index
+            ))?;
+
+        let comparison_file_currency = comparison_file_row_parts
+            .get(currency_conversion.comparison_file_currency_column_index)
+            .ok_or(format!(
+                "ComparisonFile currency column index [{}] does not exist in the row",
+                currency_conversion.comparison_file_currency_column_index
+            ))?;
+
+        return convert_amounts_to_base_currency(
+            primary_file_amount,
+            primary_file_currency,
+            comparison_file_amount,
+            comparison_file_currency,
+            currency_conversion,
+        );
+    }
+
+    //describes the rates used to compare 2 amounts in a base currency
+    fn describe_fx_conversion(&self, fx_conversion: &Option<FxConversion>) -> String {
+        match fx_conversion {
+            None => String::from(""),
+            Some(fx_conversion) => format!(
+                ", compared in [{}] using rate [{}] for [{}] and rate [{}] for [{}] giving [{}] and [{}] with tolerance [{}]",
+                fx_conversion.base_currency,
+                fx_conversion.primary_file_rate,
+                fx_conversion.primary_file_currency,
+                fx_conversion.comparison_file_rate,
+                fx_conversion.comparison_file_currency,
+                fx_conversion.primary_file_base_currency_amount,
+                fx_conversion.comparison_file_base_currency_amount,
+                fx_conversion.tolerance
+            ),
+        }
+    }
+
+    //describes the tolerance 2 amounts were compared with, unless they were compared in a base currency
+    fn describe_amount_tolerance(
+        &self,
+        fx_conversion: &Option<FxConversion>,
+        amount_tolerance: Option<f64>,
+    ) -> String {
+        match (fx_conversion, amount_tolerance) {
+            (None, Some(amount_tolerance)) => {
+                format!(
+                    ", compared as amounts with tolerance [{}]",
+                    amount_tolerance
+                )
             }
+            _ => String::from(""),
+        }
+    }
+
+    //critical mismatches keep the reason text they always had,
+    //the others say which severity they were reported with
+    fn describe_severity(&self, severity: &MismatchSeverity) -> String {
+        match severity {
+            MismatchSeverity::Critical => String::from(""),
+            _ => format!(", Severity: [{:?}]", severity),
         }
-        return true;
+    }
+
+    //describes how column values are compared under the given recon configs
+    pub fn get_column_comparator(&self, recon_configs: &ReconciliationConfigs) -> ColumnComparator {
+        if recon_configs.should_reconciliation_be_case_sensitive {
+            return ColumnComparator::Equals;
+        }
+        return ColumnComparator::EqualsIgnoringCase;
+    }
+
+    //checks that 2 amounts are no further apart than the tolerance,
+    //a value that is not an amount does not match anything
+    pub fn are_amounts_the_same(
+        &self,
+        primary_file_amount: &str,
+        comparison_file_amount: &str,
+        amount_tolerance: f64,
+    ) -> bool {
+        return match (
+            parse_amount(primary_file_amount),
+            parse_amount(comparison_file_amount),
+        ) {
+            //the amounts are floating point so a tiny difference is allowed for rounding
+            (Ok(primary_file_amount), Ok(comparison_file_amount)) => {
+                (primary_file_amount - comparison_file_amount).abs() <= amount_tolerance + 1e-9
+            }
+            _ => false,
+        };
+    }
+
+    //gets how far apart 2 amounts are, values that are not amounts add nothing to the distance
+    fn get_amount_distance(&self, primary_file_amount: &str, comparison_file_amount: &str) -> f64 {
+        return match (
+            parse_amount(primary_file_amount),
+            parse_amount(comparison_file_amount),
+        ) {
+            (Ok(primary_file_amount), Ok(comparison_file_amount)) => {
+                (primary_file_amount - comparison_file_amount).abs()
+            }
+            _ => 0.0,
+        };
     }
 
     //checks to see if 2 string column values from a row in 2 different files are the same
     pub fn are_column_values_the_same(
         &self,
-        primary_file_row_column_value: &String,
-        comparison_file_row_column_value: &String,
+        primary_file_row_column_value: &str,
+        comparison_file_row_column_value: &str,
         recon_configs: &ReconciliationConfigs,
     ) -> bool {
-        let mut sanitized_primary_file_row_column_value = primary_file_row_column_value.clone();
-        let mut sanitized_comparison_file_row_column_value =
-            comparison_file_row_column_value.clone();
-
-        if recon_configs.should_ignore_white_space {
-            sanitized_comparison_file_row_column_value = sanitized_comparison_file_row_column_value
-                .trim()
-                .to_string();
-            sanitized_primary_file_row_column_value =
-                sanitized_primary_file_row_column_value.trim().to_string()
-        }
+        //the values are only trimmed as borrowed slices, since this runs for every candidate row
+        let (sanitized_primary_file_row_column_value, sanitized_comparison_file_row_column_value) =
+            match recon_configs.should_ignore_white_space {
+                true => (
+                    primary_file_row_column_value.trim(),
+                    comparison_file_row_column_value.trim(),
+                ),
+                false => (
+                    primary_file_row_column_value,
+                    comparison_file_row_column_value,
+                ),
+            };
 
         if recon_configs.should_reconciliation_be_case_sensitive {
             return sanitized_primary_file_row_column_value
-                .eq(&sanitized_comparison_file_row_column_value);
+                .eq(sanitized_comparison_file_row_column_value);
         }
 
         return sanitized_primary_file_row_column_value
-            .eq_ignore_ascii_case(&sanitized_comparison_file_row_column_value);
+            .eq_ignore_ascii_case(sanitized_comparison_file_row_column_value);
     }
 }
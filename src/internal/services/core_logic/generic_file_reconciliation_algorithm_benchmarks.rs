@@ -0,0 +1,140 @@
+//benchmarks for reconciling large chunks, ignored by default since they take a while.
+//run them with optimizations on using
+//cargo test --release generic_file_reconciliation_algorithm_benchmarks -- --ignored --nocapture
+use std::time::Instant;
+
+use crate::internal::{
+    models::entities::reconciliation_options::{CandidateAssignment, ReconciliationOptions},
+    shared_reconciler_rust_libraries::models::entities::{
+        file_chunk_queue::FileChunkQueue,
+        file_upload_chunk::{
+            FileUploadChunk, FileUploadChunkRow, FileUploadChunkSource, ReconStatus,
+        },
+        recon_tasks_models::{ComparisonPair, ReconciliationConfigs},
+    },
+};
+
+use super::generic_file_reconciliation_algorithm::GenericFileReconciliationAlgorithm;
+
+const BENCHMARK_ROW_COUNT: usize = 10_000;
+
+#[actix_web::test]
+#[ignore]
+async fn benchmark_reconcile_rows_10k_by_10k_comparing_every_candidate() {
+    run_benchmark(CandidateAssignment::CompareEveryCandidate);
+}
+
+#[actix_web::test]
+#[ignore]
+async fn benchmark_reconcile_rows_10k_by_10k_assigning_best_matches() {
+    run_benchmark(CandidateAssignment::BestMatch);
+}
+
+//reconciles the same chunks on a single thread and then on a thread per cpu
+fn run_benchmark(candidate_assignment: CandidateAssignment) {
+    let reconciliation_options = ReconciliationOptions {
+        candidate_assignment: candidate_assignment.clone(),
+        ..ReconciliationOptions::default()
+    };
+    let comparison_file_chunk = build_file_chunk(BENCHMARK_ROW_COUNT, true);
+
+    for thread_count in [1, 0] {
+        let thread_pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(thread_count)
+            .build()
+            .unwrap();
+
+        let mut primary_file_chunk = build_file_chunk(BENCHMARK_ROW_COUNT, false);
+        let mut rows_reconciliation_details = vec![];
+
+        let started_at = Instant::now();
+        thread_pool
+            .install(|| {
+                GenericFileReconciliationAlgorithm {}.reconcile_rows(
+                    &mut primary_file_chunk,
+                    &comparison_file_chunk,
+                    &reconciliation_options,
+                    &mut rows_reconciliation_details,
+                )
+            })
+            .unwrap();
+
+        println!(
+            "{:?} {}x{} rows on {} threads took {:?}",
+            candidate_assignment,
+            BENCHMARK_ROW_COUNT,
+            BENCHMARK_ROW_COUNT,
+            thread_pool.current_num_threads(),
+            started_at.elapsed()
+        );
+
+        assert_eq!(
+            primary_file_chunk
+                .chunk_rows
+                .iter()
+                .all(|row| row.recon_result == ReconStatus::Successful),
+            true
+        );
+    }
+}
+
+//a chunk with a unique reference and an amount on every row, with
+//the comparison file rows in the reverse order of the primary file rows
+fn build_file_chunk(row_count: usize, is_reversed: bool) -> FileUploadChunk {
+    let mut row_indexes: Vec<usize> = (0..row_count).collect();
+    if is_reversed {
+        row_indexes.reverse();
+    }
+
+    FileUploadChunk {
+        id: String::from("file-1234"),
+        upload_request_id: String::from("file-1234"),
+        chunk_sequence_number: 1,
+        chunk_source: FileUploadChunkSource::ComparisonFileChunk,
+        chunk_rows: row_indexes
+            .into_iter()
+            .map(|row_index| {
+                let raw_data = format!("REF-{},{}.00", row_index, row_index % 1000);
+                FileUploadChunkRow {
+                    row_number: row_index as u64,
+                    parsed_columns_from_row: raw_data.split(',').map(|s| s.to_string()).collect(),
+                    raw_data,
+                    recon_result: ReconStatus::Pending,
+                    recon_result_reasons: vec![],
+                }
+            })
+            .collect(),
+        date_created: chrono::Utc::now().timestamp(),
+        date_modified: chrono::Utc::now().timestamp(),
+        comparison_pairs: vec![
+            ComparisonPair {
+                primary_file_column_index: 0,
+                comparison_file_column_index: 0,
+                is_row_identifier: true,
+            },
+            ComparisonPair {
+                primary_file_column_index: 1,
+                comparison_file_column_index: 1,
+                is_row_identifier: false,
+            },
+        ],
+        column_headers: vec![String::from("Reference"), String::from("Amount")],
+        recon_config: ReconciliationConfigs {
+            should_check_for_duplicate_records_in_comparison_file: false,
+            should_reconciliation_be_case_sensitive: true,
+            should_ignore_white_space: true,
+            should_do_reverse_reconciliation: false,
+        },
+        primary_file_chunks_queue: build_file_chunks_queue("primary"),
+        comparison_file_chunks_queue: build_file_chunks_queue("comparison"),
+        result_chunks_queue: build_file_chunks_queue("result"),
+        is_last_chunk: false,
+    }
+}
+
+fn build_file_chunks_queue(prefix: &str) -> FileChunkQueue {
+    FileChunkQueue {
+        topic_id: format!("{}-file-chunks-queue-1", prefix),
+        last_acknowledged_id: None,
+    }
+}
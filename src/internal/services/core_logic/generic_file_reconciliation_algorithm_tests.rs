@@ -1,5 +1,20 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
 use crate::internal::{
     interfaces::file_reconciliation_algorithm::FileReconciliationAlgorithmInterface,
+    models::entities::{
+        comparison_pair_options::{
+            ColumnIndexComparisonPairOptions, ComparisonPairOptions, MismatchSeverity,
+        },
+        currency_conversion::{CurrencyConversion, FxRateTable},
+        derived_column::{DerivedColumn, SignedAmountColumn},
+        recon_result_reason::{ColumnComparator, ReconResultReason, ReconResultReasonCode},
+        reconciliation_options::{CandidateAssignment, ReconciliationOptions},
+        row_reconciliation_details::{ReconOutcome, RowReconciliationDetails},
+        value_mapping::ValueMappingTable,
+        value_normalization::NormalizationStep,
+    },
     shared_reconciler_rust_libraries::models::entities::{
         file_chunk_queue::FileChunkQueue,
         file_upload_chunk::{
@@ -9,7 +24,10 @@ use crate::internal::{
     },
 };
 
-use super::generic_file_reconciliation_algorithm::GenericFileReconciliationAlgorithm;
+use super::{
+    column_value_preparation::ColumnValuePreparer,
+    generic_file_reconciliation_algorithm::GenericFileReconciliationAlgorithm,
+};
 
 #[actix_web::test]
 async fn test_is_same_column_values_given_same_exact_column_values_returns_true() {
@@ -154,6 +172,7 @@ async fn test_are_same_row_identifiers_given_same_row_values_returns_true() {
         &comparison_file_row_values,
         &comparison_pairs,
         &recon_configs,
+        &ColumnValuePreparer::default(),
     );
 
     //assert
@@ -176,6 +195,7 @@ async fn test_are_same_row_identifiers_given_different_row_values_returns_false(
         &comparison_file_row_values,
         &comparison_pairs,
         &recon_configs,
+        &ColumnValuePreparer::default(),
     );
 
     //assert
@@ -194,20 +214,23 @@ async fn test_reconcile_primary_file_chunk_given_same_chunks_does_correct_reconc
 
     //act
     let result = sut
-        .reconcile_primary_file_chunk(&mut primary_file_chunk, &comparison_file_chunk)
+        .reconcile_primary_file_chunk(
+            &mut primary_file_chunk,
+            &Arc::new(comparison_file_chunk),
+            &ReconciliationOptions::default(),
+            &mut vec![],
+        )
         .await;
 
     //assert
     assert_eq!(result.is_ok(), true);
 
-    let reconciled_chunk = result.unwrap();
-
     assert_eq!(
-        reconciled_chunk.chunk_rows[0].recon_result,
+        primary_file_chunk.chunk_rows[0].recon_result,
         ReconStatus::Successful
     );
     assert_eq!(
-        reconciled_chunk.chunk_rows[1].recon_result,
+        primary_file_chunk.chunk_rows[1].recon_result,
         ReconStatus::Successful
     );
 }
@@ -225,20 +248,23 @@ async fn test_reconcile_primary_file_chunk_given_partially_simalar_chunks_does_c
 
     //act
     let result = sut
-        .reconcile_primary_file_chunk(&mut primary_file_chunk, &comparison_file_chunk)
+        .reconcile_primary_file_chunk(
+            &mut primary_file_chunk,
+            &Arc::new(comparison_file_chunk),
+            &ReconciliationOptions::default(),
+            &mut vec![],
+        )
         .await;
 
     //assert
     assert_eq!(result.is_ok(), true);
 
-    let reconciled_chunk = result.unwrap();
-
     assert_eq!(
-        reconciled_chunk.chunk_rows[0].recon_result,
+        primary_file_chunk.chunk_rows[0].recon_result,
         ReconStatus::Failed
     );
     assert_eq!(
-        reconciled_chunk.chunk_rows[1].recon_result,
+        primary_file_chunk.chunk_rows[1].recon_result,
         ReconStatus::Successful
     );
 }
@@ -256,22 +282,1114 @@ async fn test_reconcile_primary_file_chunk_given_different_chunks_returns_unreco
 
     //act
     let result = sut
-        .reconcile_primary_file_chunk(&mut primary_file_chunk, &comparison_file_chunk)
+        .reconcile_primary_file_chunk(
+            &mut primary_file_chunk,
+            &Arc::new(comparison_file_chunk),
+            &ReconciliationOptions::default(),
+            &mut vec![],
+        )
+        .await;
+
+    //assert
+    assert_eq!(result.is_ok(), true);
+
+    assert_eq!(
+        primary_file_chunk.chunk_rows[0].recon_result,
+        ReconStatus::Failed
+    );
+    assert_eq!(
+        primary_file_chunk.chunk_rows[1].recon_result,
+        ReconStatus::Failed
+    );
+}
+
+#[actix_web::test]
+async fn test_reconcile_primary_file_chunk_given_different_column_counts_but_paired_columns_present_does_correct_reconciliation(
+) {
+    //setup
+    let mut primary_file_chunk = build_valid_file_chunk("primary");
+
+    let mut comparison_file_chunk = build_valid_file_chunk("comparison");
+    comparison_file_chunk.chunk_rows = vec![
+        build_chunk_row(1, "142425, test, user, extra column"),
+        build_chunk_row(2, "142426, test2"),
+    ];
+
+    let sut = setup();
+
+    //act
+    let result = sut
+        .reconcile_primary_file_chunk(
+            &mut primary_file_chunk,
+            &Arc::new(comparison_file_chunk),
+            &ReconciliationOptions::default(),
+            &mut vec![],
+        )
+        .await;
+
+    //assert
+    assert_eq!(result.is_ok(), true);
+
+    assert_eq!(
+        primary_file_chunk.chunk_rows[0].recon_result,
+        ReconStatus::Successful
+    );
+    assert_eq!(
+        primary_file_chunk.chunk_rows[1].recon_result,
+        ReconStatus::Successful
+    );
+}
+
+#[actix_web::test]
+async fn test_reconcile_primary_file_chunk_given_pair_referencing_missing_column_reports_the_pair()
+{
+    //setup
+    let mut primary_file_chunk = build_valid_file_chunk("primary");
+    primary_file_chunk.comparison_pairs.push(ComparisonPair {
+        primary_file_column_index: 2,
+        comparison_file_column_index: 5,
+        is_row_identifier: false,
+    });
+
+    let comparison_file_chunk = build_valid_file_chunk("comparison");
+
+    let sut = setup();
+
+    //act
+    let result = sut
+        .reconcile_primary_file_chunk(
+            &mut primary_file_chunk,
+            &Arc::new(comparison_file_chunk),
+            &ReconciliationOptions::default(),
+            &mut vec![],
+        )
+        .await;
+
+    //assert
+    assert_eq!(result.is_ok(), true);
+
+    assert_eq!(
+        primary_file_chunk.chunk_rows[0].recon_result,
+        ReconStatus::Failed
+    );
+    assert_eq!(
+        primary_file_chunk.chunk_rows[0].recon_result_reasons[0]
+            .contains("ComparisonFile column index [5]"),
+        true
+    );
+}
+
+#[actix_web::test]
+async fn test_reconcile_primary_file_chunk_given_pair_referencing_missing_column_records_column_missing_reason(
+) {
+    //setup
+    let mut primary_file_chunk = build_valid_file_chunk("primary");
+    primary_file_chunk.comparison_pairs.push(ComparisonPair {
+        primary_file_column_index: 2,
+        comparison_file_column_index: 5,
+        is_row_identifier: false,
+    });
+
+    let comparison_file_chunk = build_valid_file_chunk("comparison");
+
+    let mut rows_reconciliation_details = vec![];
+
+    let sut = setup();
+
+    //act
+    let _ = sut
+        .reconcile_primary_file_chunk(
+            &mut primary_file_chunk,
+            &Arc::new(comparison_file_chunk),
+            &ReconciliationOptions::default(),
+            &mut rows_reconciliation_details,
+        )
+        .await;
+
+    //assert
+    let recon_result_reason = &rows_reconciliation_details[0].recon_result_reasons[0];
+
+    assert_eq!(
+        recon_result_reason.reason_code,
+        ReconResultReasonCode::ColumnMissing
+    );
+    assert_eq!(recon_result_reason.comparison_file_column_index, Some(5));
+}
+
+#[actix_web::test]
+async fn test_are_same_row_identifiers_given_missing_identifier_columns_returns_false() {
+    //setup
+    let primary_file_row_values = vec![String::from("column_1")];
+    let comparison_file_row_values = vec![String::from("column_1")];
+    let comparison_pairs = vec![default_comparison_pair(0), default_comparison_pair(1)];
+    let recon_configs = default_recon_configs();
+    let sut = setup();
+
+    //act
+    let actual = sut.are_same_row_identifiers(
+        &primary_file_row_values,
+        &comparison_file_row_values,
+        &comparison_pairs,
+        &recon_configs,
+        &ColumnValuePreparer::default(),
+    );
+
+    //assert
+    let expected = false;
+    assert_eq!(actual, expected);
+}
+
+#[actix_web::test]
+async fn test_reconcile_primary_file_chunk_given_several_mismatching_columns_records_all_of_them() {
+    //setup
+    let mut primary_file_chunk = build_valid_file_chunk("primary");
+    primary_file_chunk
+        .comparison_pairs
+        .push(build_comparison_pair(2, false));
+    primary_file_chunk.chunk_rows[0] = build_chunk_row(1, "142425, admin, root");
+
+    let comparison_file_chunk = build_valid_file_chunk("comparison");
+
+    let mut rows_reconciliation_details = vec![];
+
+    let sut = setup();
+
+    //act
+    let result = sut
+        .reconcile_primary_file_chunk(
+            &mut primary_file_chunk,
+            &Arc::new(comparison_file_chunk),
+            &ReconciliationOptions::default(),
+            &mut rows_reconciliation_details,
+        )
+        .await;
+
+    //assert
+    assert_eq!(result.is_ok(), true);
+
+    assert_eq!(
+        primary_file_chunk.chunk_rows[0].recon_result_reasons.len(),
+        2
+    );
+    assert_eq!(rows_reconciliation_details.len(), 2);
+    assert_eq!(rows_reconciliation_details[0].row_number, 1);
+    assert_eq!(
+        rows_reconciliation_details[0].recon_result_reasons,
+        vec![
+            ReconResultReason {
+                reason_code: ReconResultReasonCode::ColumnValueMismatch,
+                primary_file_column_header: String::from(" userRole"),
+                primary_file_column_index: Some(1),
+                primary_file_column_value: String::from(" admin"),
+                comparison_file_column_header: String::from(" userRole"),
+                comparison_file_column_index: Some(1),
+                comparison_file_column_value: String::from(" test"),
+                comparator: Some(ColumnComparator::Equals),
+                severity: Some(MismatchSeverity::Critical),
+                matched_comparison_row_number: Some(1),
+                comparison_file_chunk_id: String::from("comparison-file-1234"),
+                fx_conversion: None,
+                match_rule_trace: vec![],
+            },
+            ReconResultReason {
+                reason_code: ReconResultReasonCode::ColumnValueMismatch,
+                primary_file_column_header: String::from(" userName"),
+                primary_file_column_index: Some(2),
+                primary_file_column_value: String::from(" root"),
+                comparison_file_column_header: String::from(" userName"),
+                comparison_file_column_index: Some(2),
+                comparison_file_column_value: String::from(" user"),
+                comparator: Some(ColumnComparator::Equals),
+                severity: Some(MismatchSeverity::Critical),
+                matched_comparison_row_number: Some(1),
+                comparison_file_chunk_id: String::from("comparison-file-1234"),
+                fx_conversion: None,
+                match_rule_trace: vec![],
+            },
+        ]
+    );
+}
+
+#[actix_web::test]
+async fn test_reconcile_primary_file_chunk_given_stop_at_first_mismatch_option_records_only_first_mismatch(
+) {
+    //setup
+    let mut primary_file_chunk = build_valid_file_chunk("primary");
+    primary_file_chunk
+        .comparison_pairs
+        .push(build_comparison_pair(2, false));
+    primary_file_chunk.chunk_rows[0] = build_chunk_row(1, "142425, admin, root");
+
+    let comparison_file_chunk = build_valid_file_chunk("comparison");
+
+    let reconciliation_options = ReconciliationOptions {
+        should_stop_at_first_column_mismatch: true,
+        ..ReconciliationOptions::default()
+    };
+
+    let mut rows_reconciliation_details = vec![];
+
+    let sut = setup();
+
+    //act
+    let result = sut
+        .reconcile_primary_file_chunk(
+            &mut primary_file_chunk,
+            &Arc::new(comparison_file_chunk),
+            &reconciliation_options,
+            &mut rows_reconciliation_details,
+        )
         .await;
 
     //assert
     assert_eq!(result.is_ok(), true);
 
-    let reconciled_chunk = result.unwrap();
+    assert_eq!(
+        primary_file_chunk.chunk_rows[0].recon_result,
+        ReconStatus::Failed
+    );
+    assert_eq!(
+        primary_file_chunk.chunk_rows[0].recon_result_reasons.len(),
+        1
+    );
+    assert_eq!(rows_reconciliation_details[0].recon_result_reasons.len(), 1);
+}
+
+#[actix_web::test]
+async fn test_reconcile_primary_file_chunk_given_successful_rows_records_the_matched_comparison_rows(
+) {
+    //setup
+    let mut primary_file_chunk = build_valid_file_chunk("primary");
+
+    let mut comparison_file_chunk = build_valid_file_chunk("comparison");
+    comparison_file_chunk.id = String::from("1526919030474-55");
+    comparison_file_chunk.chunk_rows = vec![
+        build_chunk_row(7, "142426, test2, user2"),
+        build_chunk_row(8, "142425, test, user"),
+    ];
+
+    let mut rows_reconciliation_details = vec![];
+
+    let sut = setup();
+
+    //act
+    let _ = sut
+        .reconcile_primary_file_chunk(
+            &mut primary_file_chunk,
+            &Arc::new(comparison_file_chunk),
+            &ReconciliationOptions::default(),
+            &mut rows_reconciliation_details,
+        )
+        .await;
+
+    //assert
+    assert_eq!(rows_reconciliation_details.len(), 2);
+    assert_eq!(rows_reconciliation_details[0].row_number, 1);
+    assert_eq!(
+        rows_reconciliation_details[0].matched_comparison_row_number,
+        Some(8)
+    );
+    assert_eq!(
+        rows_reconciliation_details[0].matched_comparison_file_chunk_id,
+        Some(String::from("1526919030474-55"))
+    );
+    assert_eq!(
+        rows_reconciliation_details[0].matched_comparison_upload_request_id,
+        Some(String::from("comparison-file-1234"))
+    );
+    assert_eq!(rows_reconciliation_details[1].row_number, 2);
+    assert_eq!(
+        rows_reconciliation_details[1].matched_comparison_row_number,
+        Some(7)
+    );
+}
+
+#[actix_web::test]
+async fn test_reconcile_primary_file_chunk_given_warning_pair_mismatch_returns_matched_with_warnings(
+) {
+    //act
+    let (reconciled_chunk, rows_reconciliation_details) =
+        reconcile_with_mismatching_pair_of_severity(MismatchSeverity::Warning).await;
+
+    //assert
+    assert_eq!(
+        reconciled_chunk.chunk_rows[0].recon_result,
+        ReconStatus::Successful
+    );
+    assert_eq!(
+        rows_reconciliation_details[0].recon_outcome,
+        ReconOutcome::MatchedWithWarnings
+    );
+    assert_eq!(
+        rows_reconciliation_details[0].recon_result_reasons[0].severity,
+        Some(MismatchSeverity::Warning)
+    );
+    assert_eq!(
+        reconciled_chunk.chunk_rows[0].recon_result_reasons[0].ends_with("Severity: [Warning]"),
+        true
+    );
+}
+
+#[actix_web::test]
+async fn test_reconcile_primary_file_chunk_given_informational_pair_mismatch_returns_matched() {
+    //act
+    let (reconciled_chunk, rows_reconciliation_details) =
+        reconcile_with_mismatching_pair_of_severity(MismatchSeverity::Informational).await;
 
+    //assert
+    assert_eq!(
+        reconciled_chunk.chunk_rows[0].recon_result,
+        ReconStatus::Successful
+    );
+    assert_eq!(
+        rows_reconciliation_details[0].recon_outcome,
+        ReconOutcome::Matched
+    );
+    assert_eq!(rows_reconciliation_details[0].recon_result_reasons.len(), 1);
+}
+
+#[actix_web::test]
+async fn test_reconcile_primary_file_chunk_given_critical_pair_mismatch_returns_failed() {
+    //act
+    let (reconciled_chunk, rows_reconciliation_details) =
+        reconcile_with_mismatching_pair_of_severity(MismatchSeverity::Critical).await;
+
+    //assert
     assert_eq!(
         reconciled_chunk.chunk_rows[0].recon_result,
         ReconStatus::Failed
     );
+    assert_eq!(
+        rows_reconciliation_details[0].recon_outcome,
+        ReconOutcome::Failed
+    );
+}
+
+#[actix_web::test]
+async fn test_reconcile_primary_file_chunk_given_normalization_steps_normalizes_identifiers_and_values(
+) {
+    //setup
+    let mut primary_file_chunk = build_valid_file_chunk("primary");
+    primary_file_chunk.chunk_rows[0].parsed_columns_from_row =
+        build_chunk_row(1, "０00142425, Müller Straße, user").parsed_columns_from_row;
+
+    let mut comparison_file_chunk = build_valid_file_chunk("comparison");
+    comparison_file_chunk.chunk_rows[0].parsed_columns_from_row =
+        build_chunk_row(1, "142425, MÜLLER\u{00A0} STRASSE, user").parsed_columns_from_row;
+
+    let reconciliation_options = ReconciliationOptions {
+        normalization_steps: vec![
+            NormalizationStep::Nfkc,
+            NormalizationStep::UnicodeCaseFolding,
+            NormalizationStep::CollapseWhiteSpace,
+            NormalizationStep::RemoveLeadingZeros,
+        ],
+        ..ReconciliationOptions::default()
+    };
+
+    let sut = setup();
+
+    //act
+    sut.reconcile_primary_file_chunk(
+        &mut primary_file_chunk,
+        &Arc::new(comparison_file_chunk),
+        &reconciliation_options,
+        &mut vec![],
+    )
+    .await
+    .unwrap();
+
+    //assert
+    assert_eq!(
+        primary_file_chunk.chunk_rows[0].recon_result,
+        ReconStatus::Successful
+    );
+}
+
+#[actix_web::test]
+async fn test_reconcile_primary_file_chunk_given_pair_normalization_steps_uses_them_instead_of_the_chunk_ones(
+) {
+    //setup
+    let mut primary_file_chunk = build_valid_file_chunk("primary");
+    primary_file_chunk.chunk_rows[0].parsed_columns_from_row[1] = String::from("T.E.S.T");
+
+    let comparison_file_chunk = build_valid_file_chunk("comparison");
+
+    let reconciliation_options = ReconciliationOptions {
+        normalization_steps: vec![NormalizationStep::UnicodeCaseFolding],
+        comparison_pair_options: vec![ColumnIndexComparisonPairOptions {
+            primary_file_column_index: 1,
+            comparison_file_column_index: 1,
+            options: ComparisonPairOptions {
+                normalization_steps: Some(vec![
+                    NormalizationStep::StripPunctuation,
+                    NormalizationStep::UnicodeCaseFolding,
+                ]),
+                ..ComparisonPairOptions::default()
+            },
+        }],
+        ..ReconciliationOptions::default()
+    };
+
+    let sut = setup();
+
+    //act
+    sut.reconcile_primary_file_chunk(
+        &mut primary_file_chunk,
+        &Arc::new(comparison_file_chunk),
+        &reconciliation_options,
+        &mut vec![],
+    )
+    .await
+    .unwrap();
+
+    //assert
+    assert_eq!(
+        primary_file_chunk.chunk_rows[0].recon_result,
+        ReconStatus::Successful
+    );
+}
+
+#[actix_web::test]
+async fn test_reconcile_primary_file_chunk_given_value_mapping_table_maps_comparison_values() {
+    //act
+    let (reconciled_chunk, rows_reconciliation_details) =
+        reconcile_with_user_role_value_mapping(" usr").await;
+
+    //assert
+    assert_eq!(
+        reconciled_chunk.chunk_rows[0].recon_result,
+        ReconStatus::Successful
+    );
+    assert_eq!(
+        rows_reconciliation_details[0].recon_outcome,
+        ReconOutcome::Matched
+    );
+}
+
+#[actix_web::test]
+async fn test_reconcile_primary_file_chunk_given_unmapped_comparison_value_records_unmapped_value_reason(
+) {
+    //act
+    let (reconciled_chunk, rows_reconciliation_details) =
+        reconcile_with_user_role_value_mapping(" guest").await;
+
+    //assert
+    assert_eq!(
+        reconciled_chunk.chunk_rows[0].recon_result,
+        ReconStatus::Failed
+    );
+    assert_eq!(
+        rows_reconciliation_details[0].recon_result_reasons[0].reason_code,
+        ReconResultReasonCode::UnmappedValue
+    );
+    assert_eq!(
+        rows_reconciliation_details[0].recon_result_reasons[0].comparison_file_column_value,
+        String::from(" guest")
+    );
+}
+
+#[actix_web::test]
+async fn test_reconcile_primary_file_chunk_given_currency_conversion_matches_amounts_in_base_currency(
+) {
+    //act
+    let (reconciled_chunk, _rows_reconciliation_details) =
+        reconcile_amounts_in_different_currencies(" 370000").await;
+
+    //assert
+    assert_eq!(
+        reconciled_chunk.chunk_rows[0].recon_result,
+        ReconStatus::Successful
+    );
+}
+
+#[actix_web::test]
+async fn test_reconcile_primary_file_chunk_given_currency_conversion_mismatch_reports_the_rates_used(
+) {
+    //act
+    let (reconciled_chunk, rows_reconciliation_details) =
+        reconcile_amounts_in_different_currencies(" 400000").await;
+
+    //assert
+    assert_eq!(
+        reconciled_chunk.chunk_rows[0].recon_result,
+        ReconStatus::Failed
+    );
+    assert_eq!(
+        reconciled_chunk.chunk_rows[0].recon_result_reasons[0].contains("rate [0.00027] for [UGX]"),
+        true
+    );
+
+    let recon_result_reason = &rows_reconciliation_details[0].recon_result_reasons[0];
+    assert_eq!(
+        recon_result_reason.comparator,
+        Some(ColumnComparator::EqualsInBaseCurrency)
+    );
+    assert_eq!(
+        recon_result_reason
+            .fx_conversion
+            .as_ref()
+            .unwrap()
+            .comparison_file_rate,
+        0.00027
+    );
+}
+
+#[actix_web::test]
+async fn test_reconcile_primary_file_chunk_given_derived_amount_compares_it_as_an_amount() {
+    //setup
+    let mut primary_file_chunk = build_valid_file_chunk("primary");
+    primary_file_chunk.column_headers = build_column_headers("ID, Amount, DrCr, SignedAmount");
+    primary_file_chunk.chunk_rows = vec![build_chunk_row(1, "142425, 1000.50, DR,-1000.5")];
+    primary_file_chunk.comparison_pairs = vec![
+        build_comparison_pair(0, true),
+        ComparisonPair {
+            primary_file_column_index: 3,
+            comparison_file_column_index: 1,
+            is_row_identifier: false,
+        },
+    ];
+
+    let mut comparison_file_chunk = build_valid_file_chunk("comparison");
+    comparison_file_chunk.column_headers = build_column_headers("ID, Amount");
+    comparison_file_chunk.chunk_rows = vec![build_chunk_row(1, "142425, -1000.50")];
+
+    let reconciliation_options = ReconciliationOptions {
+        primary_file_derived_columns: vec![DerivedColumn::SignedAmount(SignedAmountColumn {
+            column_header: String::from("SignedAmount"),
+            amount_column_index: 1,
+            indicator_column_index: Some(2),
+            debit_indicators: vec![String::from("DR")],
+            credit_indicators: vec![String::from("CR")],
+            should_invert_sign: false,
+        })],
+        ..ReconciliationOptions::default()
+    };
+
+    let sut = setup();
+
+    //act
+    sut.reconcile_primary_file_chunk(
+        &mut primary_file_chunk,
+        &Arc::new(comparison_file_chunk),
+        &reconciliation_options,
+        &mut vec![],
+    )
+    .await
+    .unwrap();
+
+    //assert
+    assert_eq!(
+        primary_file_chunk.chunk_rows[0].recon_result,
+        ReconStatus::Successful
+    );
+}
+
+#[actix_web::test]
+async fn test_reconcile_primary_file_chunk_given_amount_tolerance_matches_amounts_within_it() {
+    //act
+    let (reconciled_chunk, _rows_reconciliation_details) =
+        reconcile_amounts_with_tolerance(" 100.40", 0.5).await;
+
+    //assert
+    assert_eq!(
+        reconciled_chunk.chunk_rows[0].recon_result,
+        ReconStatus::Successful
+    );
+}
+
+#[actix_web::test]
+async fn test_reconcile_primary_file_chunk_given_amount_tolerance_fails_amounts_further_apart() {
+    //act
+    let (reconciled_chunk, rows_reconciliation_details) =
+        reconcile_amounts_with_tolerance(" 101", 0.5).await;
+
+    //assert
+    assert_eq!(
+        reconciled_chunk.chunk_rows[0].recon_result,
+        ReconStatus::Failed
+    );
+    assert_eq!(
+        reconciled_chunk.chunk_rows[0].recon_result_reasons[0]
+            .contains("compared as amounts with tolerance [0.5]"),
+        true
+    );
+    assert_eq!(
+        rows_reconciliation_details[0].recon_result_reasons[0].comparator,
+        Some(ColumnComparator::EqualsWithinTolerance)
+    );
+}
+
+#[actix_web::test]
+async fn test_reconcile_primary_file_chunk_given_best_match_assigns_each_comparison_row_once() {
+    //setup
+    let (primary_file_chunk, mut comparison_file_chunk) = build_same_account_chunks();
+    comparison_file_chunk.chunk_rows = vec![
+        build_chunk_row(7, "ACC-1, 250.00"),
+        build_chunk_row(8, "ACC-1, 100.00"),
+    ];
+
+    //act
+    let (reconciled_chunk, rows_reconciliation_details) =
+        reconcile_using_best_match(primary_file_chunk, comparison_file_chunk, vec![]).await;
+
+    //assert
+    assert_eq!(
+        reconciled_chunk.chunk_rows[0].recon_result,
+        ReconStatus::Successful
+    );
     assert_eq!(
         reconciled_chunk.chunk_rows[1].recon_result,
+        ReconStatus::Successful
+    );
+
+    let matched_comparison_row_numbers: Vec<(u64, Option<u64>)> = rows_reconciliation_details
+        .iter()
+        .map(|row_details| {
+            (
+                row_details.row_number,
+                row_details.matched_comparison_row_number,
+            )
+        })
+        .collect();
+    assert_eq!(matched_comparison_row_numbers.contains(&(1, Some(8))), true);
+    assert_eq!(matched_comparison_row_numbers.contains(&(2, Some(7))), true);
+}
+
+#[actix_web::test]
+async fn test_reconcile_primary_file_chunk_given_best_match_prefers_the_closest_amount_within_tolerance(
+) {
+    //setup
+    let (mut primary_file_chunk, mut comparison_file_chunk) = build_same_account_chunks();
+    primary_file_chunk.chunk_rows = vec![build_chunk_row(1, "ACC-1, 100.00")];
+    comparison_file_chunk.chunk_rows = vec![
+        build_chunk_row(7, "ACC-1, 101.00"),
+        build_chunk_row(8, "ACC-1, 98.50"),
+        build_chunk_row(9, "ACC-1, 100.40"),
+    ];
+
+    //act
+    let (reconciled_chunk, rows_reconciliation_details) = reconcile_using_best_match(
+        primary_file_chunk,
+        comparison_file_chunk,
+        vec![amount_tolerance_pair_options(1, 2.0)],
+    )
+    .await;
+
+    //assert
+    assert_eq!(
+        reconciled_chunk.chunk_rows[0].recon_result,
+        ReconStatus::Successful
+    );
+    assert_eq!(
+        rows_reconciliation_details[0].matched_comparison_row_number,
+        Some(9)
+    );
+}
+
+#[actix_web::test]
+async fn test_reconcile_primary_file_chunk_given_best_match_does_not_score_pairs_not_compared_as_amounts(
+) {
+    //setup
+    //the amount matches both rows, the references are numbers but not amounts
+    //so a reference that is numerically closer is not a better match
+    let (mut primary_file_chunk, mut comparison_file_chunk) = build_same_account_chunks();
+    primary_file_chunk.chunk_rows = vec![build_chunk_row(1, "ACC-1, 100.00, 5001")];
+    primary_file_chunk.column_headers = build_column_headers("Account, Amount, Reference");
+    primary_file_chunk.comparison_pairs = vec![
+        build_comparison_pair(0, true),
+        build_comparison_pair(1, false),
+        build_comparison_pair(2, false),
+    ];
+    comparison_file_chunk.chunk_rows = vec![
+        build_chunk_row(7, "ACC-1, 100.00, 9000"),
+        build_chunk_row(8, "ACC-1, 100.00, 5002"),
+    ];
+    comparison_file_chunk.column_headers = build_column_headers("Account, Amount, Reference");
+
+    //act
+    let (reconciled_chunk, rows_reconciliation_details) = reconcile_using_best_match(
+        primary_file_chunk,
+        comparison_file_chunk,
+        vec![amount_tolerance_pair_options(1, 0.0)],
+    )
+    .await;
+
+    //assert
+    assert_eq!(
+        reconciled_chunk.chunk_rows[0].recon_result,
+        ReconStatus::Failed
+    );
+    assert_eq!(
+        rows_reconciliation_details[0]
+            .recon_result_reasons
+            .iter()
+            .any(|reason| reason.reason_code == ReconResultReasonCode::AmbiguousMatch),
+        true
+    );
+}
+
+#[actix_web::test]
+async fn test_reconcile_primary_file_chunk_given_best_match_only_assigns_a_comparison_row_once_per_primary_chunk(
+) {
+    //setup
+    //the primary file chunks are reconciled independently,
+    //so each of them can be assigned the same comparison row
+    let (mut first_primary_file_chunk, mut comparison_file_chunk) = build_same_account_chunks();
+    first_primary_file_chunk.chunk_rows = vec![build_chunk_row(1, "ACC-1, 100.00")];
+    let mut second_primary_file_chunk = first_primary_file_chunk.clone();
+    second_primary_file_chunk.chunk_rows = vec![build_chunk_row(2, "ACC-1, 100.00")];
+    comparison_file_chunk.chunk_rows = vec![build_chunk_row(7, "ACC-1, 100.00")];
+
+    //act
+    let (_, first_rows_reconciliation_details) = reconcile_using_best_match(
+        first_primary_file_chunk,
+        comparison_file_chunk.clone(),
+        vec![],
+    )
+    .await;
+    let (_, second_rows_reconciliation_details) =
+        reconcile_using_best_match(second_primary_file_chunk, comparison_file_chunk, vec![]).await;
+
+    //assert
+    assert_eq!(
+        first_rows_reconciliation_details[0].matched_comparison_row_number,
+        Some(7)
+    );
+    assert_eq!(
+        second_rows_reconciliation_details[0].matched_comparison_row_number,
+        Some(7)
+    );
+}
+
+#[actix_web::test]
+async fn test_reconcile_primary_file_chunk_given_best_match_tie_fails_the_row_as_ambiguous() {
+    //setup
+    let (mut primary_file_chunk, mut comparison_file_chunk) = build_same_account_chunks();
+    primary_file_chunk.chunk_rows = vec![build_chunk_row(1, "ACC-1, 100.00")];
+    comparison_file_chunk.chunk_rows = vec![
+        build_chunk_row(7, "ACC-1, 100.00"),
+        build_chunk_row(8, "ACC-1, 100.00"),
+    ];
+
+    //act
+    let (reconciled_chunk, rows_reconciliation_details) =
+        reconcile_using_best_match(primary_file_chunk, comparison_file_chunk, vec![]).await;
+
+    //assert
+    assert_eq!(
+        reconciled_chunk.chunk_rows[0].recon_result,
         ReconStatus::Failed
     );
+    assert_eq!(
+        reconciled_chunk.chunk_rows[0].recon_result_reasons,
+        vec![String::from(
+            "RowNumber: [1], ComparisonFile rows [7, 8] match the row equally well"
+        )]
+    );
+
+    let recon_result_reasons = &rows_reconciliation_details[0].recon_result_reasons;
+    assert_eq!(recon_result_reasons.len(), 2);
+    assert_eq!(
+        recon_result_reasons[1].reason_code,
+        ReconResultReasonCode::AmbiguousMatch
+    );
+    assert_eq!(
+        recon_result_reasons[1].matched_comparison_row_number,
+        Some(8)
+    );
+    assert_eq!(
+        rows_reconciliation_details[0].recon_outcome,
+        ReconOutcome::Failed
+    );
+}
+
+#[actix_web::test]
+async fn test_reconcile_primary_file_chunk_given_many_rows_keeps_row_order_and_details_order() {
+    //setup
+    let mut primary_file_chunk = build_valid_file_chunk("src");
+    primary_file_chunk.chunk_rows = (1..=500)
+        .map(|row_number| {
+            build_chunk_row(
+                row_number,
+                &format!("{}, role{}", row_number, row_number % 3),
+            )
+        })
+        .collect();
+
+    let mut comparison_file_chunk = build_valid_file_chunk("cmp");
+    comparison_file_chunk.chunk_rows = (1..=500)
+        .rev()
+        .map(|row_number| {
+            build_chunk_row(
+                row_number,
+                &format!("{}, role{}", row_number, row_number % 2),
+            )
+        })
+        .collect();
+
+    let mut rows_reconciliation_details = vec![];
+    let sut = setup();
+
+    //act
+    sut.reconcile_primary_file_chunk(
+        &mut primary_file_chunk,
+        &Arc::new(comparison_file_chunk),
+        &ReconciliationOptions::default(),
+        &mut rows_reconciliation_details,
+    )
+    .await
+    .unwrap();
+
+    //assert
+    let row_numbers: Vec<u64> = primary_file_chunk
+        .chunk_rows
+        .iter()
+        .map(|row| row.row_number)
+        .collect();
+    let details_row_numbers: Vec<u64> = rows_reconciliation_details
+        .iter()
+        .map(|row_details| row_details.row_number)
+        .collect();
+    assert_eq!(row_numbers, (1..=500).collect::<Vec<u64>>());
+    assert_eq!(details_row_numbers, row_numbers);
+
+    //the roles only agree when the row number is a multiple of 6 or one more than a multiple of 6
+    for chunk_row in &primary_file_chunk.chunk_rows {
+        let expected_recon_result = match chunk_row.row_number % 3 == chunk_row.row_number % 2 {
+            true => ReconStatus::Successful,
+            false => ReconStatus::Failed,
+        };
+        assert_eq!(chunk_row.recon_result, expected_recon_result);
+    }
+}
+
+//2 primary file rows for the same account, identified by the account
+//with the amount as the only compared column
+fn build_same_account_chunks() -> (FileUploadChunk, FileUploadChunk) {
+    let mut primary_file_chunk = build_valid_file_chunk("src");
+    primary_file_chunk.chunk_rows = vec![
+        build_chunk_row(1, "ACC-1, 100.00"),
+        build_chunk_row(2, "ACC-1, 250.00"),
+    ];
+    primary_file_chunk.column_headers = build_column_headers("Account, Amount");
+
+    let mut comparison_file_chunk = build_valid_file_chunk("cmp");
+    comparison_file_chunk.column_headers = build_column_headers("Account, Amount");
+
+    return (primary_file_chunk, comparison_file_chunk);
+}
+
+async fn reconcile_using_best_match(
+    mut primary_file_chunk: FileUploadChunk,
+    comparison_file_chunk: FileUploadChunk,
+    comparison_pair_options: Vec<ColumnIndexComparisonPairOptions>,
+) -> (FileUploadChunk, Vec<RowReconciliationDetails>) {
+    let reconciliation_options = ReconciliationOptions {
+        candidate_assignment: CandidateAssignment::BestMatch,
+        comparison_pair_options,
+        ..ReconciliationOptions::default()
+    };
+    let mut rows_reconciliation_details = vec![];
+    let sut = setup();
+
+    sut.reconcile_primary_file_chunk(
+        &mut primary_file_chunk,
+        &Arc::new(comparison_file_chunk),
+        &reconciliation_options,
+        &mut rows_reconciliation_details,
+    )
+    .await
+    .unwrap();
+
+    return (primary_file_chunk, rows_reconciliation_details);
+}
+
+//reconciles a primary file row of 100 USD against a comparison file row
+//with the given amount in UGX, converting both into USD
+async fn reconcile_amounts_in_different_currencies(
+    comparison_file_amount: &str,
+) -> (FileUploadChunk, Vec<RowReconciliationDetails>) {
+    let mut primary_file_chunk = build_valid_file_chunk("primary");
+    primary_file_chunk.chunk_rows = vec![build_chunk_row(1, "142425, 100, USD")];
+
+    let mut comparison_file_chunk = build_valid_file_chunk("comparison");
+    comparison_file_chunk.chunk_rows = vec![build_chunk_row(
+        1,
+        &format!("142425,{}, UGX", comparison_file_amount),
+    )];
+
+    let reconciliation_options = ReconciliationOptions {
+        comparison_pair_options: vec![ColumnIndexComparisonPairOptions {
+            primary_file_column_index: 1,
+            comparison_file_column_index: 1,
+            options: ComparisonPairOptions {
+                currency_conversion: Some(CurrencyConversion {
+                    primary_file_currency_column_index: 2,
+                    comparison_file_currency_column_index: 2,
+                    base_currency: String::from("USD"),
+                    fx_rate_table: FxRateTable::Inline {
+                        rates: HashMap::from([(String::from("UGX"), 0.00027)]),
+                    },
+                    tolerance: 0.1,
+                }),
+                ..ComparisonPairOptions::default()
+            },
+        }],
+        ..ReconciliationOptions::default()
+    };
+
+    let mut rows_reconciliation_details = vec![];
+
+    let sut = setup();
+
+    sut.reconcile_primary_file_chunk(
+        &mut primary_file_chunk,
+        &Arc::new(comparison_file_chunk),
+        &reconciliation_options,
+        &mut rows_reconciliation_details,
+    )
+    .await
+    .unwrap();
+
+    return (primary_file_chunk, rows_reconciliation_details);
+}
+
+//compares the amounts in the pair at the column index as amounts within the tolerance
+fn amount_tolerance_pair_options(
+    column_index: usize,
+    amount_tolerance: f64,
+) -> ColumnIndexComparisonPairOptions {
+    ColumnIndexComparisonPairOptions {
+        primary_file_column_index: column_index,
+        comparison_file_column_index: column_index,
+        options: ComparisonPairOptions {
+            amount_tolerance: Some(amount_tolerance),
+            ..ComparisonPairOptions::default()
+        },
+    }
+}
+
+//reconciles a primary file row with an amount of 100 against a comparison file row
+//with the given amount, comparing them as amounts within the tolerance
+async fn reconcile_amounts_with_tolerance(
+    comparison_file_amount: &str,
+    amount_tolerance: f64,
+) -> (FileUploadChunk, Vec<RowReconciliationDetails>) {
+    let mut primary_file_chunk = build_valid_file_chunk("primary");
+    primary_file_chunk.chunk_rows = vec![build_chunk_row(1, "142425, 100.00")];
+
+    let mut comparison_file_chunk = build_valid_file_chunk("comparison");
+    comparison_file_chunk.chunk_rows = vec![build_chunk_row(
+        1,
+        &format!("142425,{}", comparison_file_amount),
+    )];
+
+    let reconciliation_options = ReconciliationOptions {
+        comparison_pair_options: vec![ColumnIndexComparisonPairOptions {
+            primary_file_column_index: 1,
+            comparison_file_column_index: 1,
+            options: ComparisonPairOptions {
+                amount_tolerance: Some(amount_tolerance),
+                ..ComparisonPairOptions::default()
+            },
+        }],
+        ..ReconciliationOptions::default()
+    };
+
+    let mut rows_reconciliation_details = vec![];
+
+    let sut = setup();
+
+    sut.reconcile_primary_file_chunk(
+        &mut primary_file_chunk,
+        &Arc::new(comparison_file_chunk),
+        &reconciliation_options,
+        &mut rows_reconciliation_details,
+    )
+    .await
+    .unwrap();
+
+    return (primary_file_chunk, rows_reconciliation_details);
+}
+
+//reconciles 2 chunks whose first rows have the given userRole in the comparison
+//file, which is mapped using a table that only maps "usr" to "test"
+async fn reconcile_with_user_role_value_mapping(
+    comparison_file_user_role: &str,
+) -> (FileUploadChunk, Vec<RowReconciliationDetails>) {
+    let mut primary_file_chunk = build_valid_file_chunk("primary");
+
+    let mut comparison_file_chunk = build_valid_file_chunk("comparison");
+    comparison_file_chunk.chunk_rows[0].parsed_columns_from_row[1] =
+        comparison_file_user_role.to_string();
+
+    let reconciliation_options = ReconciliationOptions {
+        comparison_pair_options: vec![ColumnIndexComparisonPairOptions {
+            primary_file_column_index: 1,
+            comparison_file_column_index: 1,
+            options: ComparisonPairOptions {
+                comparison_file_value_mapping: Some(ValueMappingTable::Inline {
+                    entries: HashMap::from([(String::from("usr"), String::from("test"))]),
+                }),
+                ..ComparisonPairOptions::default()
+            },
+        }],
+        ..ReconciliationOptions::default()
+    };
+
+    let mut rows_reconciliation_details = vec![];
+
+    let sut = setup();
+
+    sut.reconcile_primary_file_chunk(
+        &mut primary_file_chunk,
+        &Arc::new(comparison_file_chunk),
+        &reconciliation_options,
+        &mut rows_reconciliation_details,
+    )
+    .await
+    .unwrap();
+
+    return (primary_file_chunk, rows_reconciliation_details);
+}
+
+//reconciles 2 chunks whose first rows only differ in the userRole column
+//which is paired using the given severity
+async fn reconcile_with_mismatching_pair_of_severity(
+    severity: MismatchSeverity,
+) -> (FileUploadChunk, Vec<RowReconciliationDetails>) {
+    let mut primary_file_chunk = build_valid_file_chunk("primary");
+    primary_file_chunk.chunk_rows[0].parsed_columns_from_row[1] = String::from("admin");
+
+    let comparison_file_chunk = build_valid_file_chunk("comparison");
+
+    let reconciliation_options = ReconciliationOptions {
+        comparison_pair_options: vec![ColumnIndexComparisonPairOptions {
+            primary_file_column_index: 1,
+            comparison_file_column_index: 1,
+            options: ComparisonPairOptions {
+                severity,
+                ..ComparisonPairOptions::default()
+            },
+        }],
+        ..ReconciliationOptions::default()
+    };
+
+    let mut rows_reconciliation_details = vec![];
+
+    let sut = setup();
+
+    sut.reconcile_primary_file_chunk(
+        &mut primary_file_chunk,
+        &Arc::new(comparison_file_chunk),
+        &reconciliation_options,
+        &mut rows_reconciliation_details,
+    )
+    .await
+    .unwrap();
+
+    return (primary_file_chunk, rows_reconciliation_details);
 }
 
 fn setup() -> GenericFileReconciliationAlgorithm {
@@ -0,0 +1,207 @@
+use caseless::default_case_fold_str;
+use chrono::{DateTime, NaiveDate};
+use std::collections::HashMap;
+
+use crate::internal::{
+    models::entities::match_rule::{
+        ColumnReference, MatchRule, MatchRuleFunction, MatchRulePredicate,
+    },
+    services::core_logic::{
+        comparison_pair_resolution::MatchRuleColumnIndexes, currency_conversion::parse_amount,
+    },
+};
+
+//the date formats a date column can be written in
+const DATE_FORMATS: [&str; 4] = ["%Y-%m-%d", "%d/%m/%Y", "%Y%m%d", "%d-%b-%Y"];
+
+//the rows a match rule is evaluated against, along with the columns
+//of the headers it references, which are resolved once per chunk
+pub struct MatchRuleEvaluationContext<'a> {
+    pub primary_file_row_parts: &'a Vec<String>,
+    pub comparison_file_row_parts: &'a Vec<String>,
+    pub column_indexes: &'a MatchRuleColumnIndexes,
+}
+
+//evaluates a match rule against a pair of rows, recording the outcome
+//of every predicate it evaluates in the trace. AND and OR stop evaluating
+//as soon as their outcome is known, so the trace only has the predicates that decided it
+pub fn evaluate_match_rule(
+    match_rule: &MatchRule,
+    context: &MatchRuleEvaluationContext,
+    trace: &mut Vec<String>,
+) -> bool {
+    match match_rule {
+        MatchRule::And(left, right) => {
+            return evaluate_match_rule(left, context, trace)
+                && evaluate_match_rule(right, context, trace)
+        }
+        MatchRule::Or(left, right) => {
+            return evaluate_match_rule(left, context, trace)
+                || evaluate_match_rule(right, context, trace)
+        }
+        MatchRule::Not(inner) => return !evaluate_match_rule(inner, context, trace),
+        MatchRule::Predicate(predicate) => {
+            let primary_file_value = read_column(
+                &predicate.primary_file_column,
+                context.primary_file_row_parts,
+                &context.column_indexes.primary_file_column_indexes,
+            );
+            let comparison_file_value = read_column(
+                &predicate.comparison_file_column,
+                context.comparison_file_row_parts,
+                &context.column_indexes.comparison_file_column_indexes,
+            );
+
+            let predicate_result = match (&primary_file_value, &comparison_file_value) {
+                (Some(primary_file_value), Some(comparison_file_value)) => {
+                    evaluate_predicate(predicate, primary_file_value, comparison_file_value)
+                }
+                _ => Err(String::from("column does not exist in the row")),
+            };
+
+            let description = format!(
+                "{}(primary{} = [{}], comparison{} = [{}]{})",
+                predicate.function.get_name(),
+                describe_column_reference(&predicate.primary_file_column),
+                primary_file_value.unwrap_or_default(),
+                describe_column_reference(&predicate.comparison_file_column),
+                comparison_file_value.unwrap_or_default(),
+                predicate
+                    .argument
+                    .map(|argument| format!(", {}", argument))
+                    .unwrap_or_default()
+            );
+
+            return match predicate_result {
+                Ok(is_satisfied) => {
+                    trace.push(format!("{} is {}", description, is_satisfied));
+                    is_satisfied
+                }
+                Err(error) => {
+                    trace.push(format!("{} is false: {}", description, error));
+                    false
+                }
+            };
+        }
+    }
+}
+
+fn evaluate_predicate(
+    predicate: &MatchRulePredicate,
+    primary_file_value: &String,
+    comparison_file_value: &String,
+) -> Result<bool, String> {
+    let argument = predicate.argument.unwrap_or_default();
+
+    match predicate.function {
+        MatchRuleFunction::Equals => {
+            return Ok(primary_file_value.trim() == comparison_file_value.trim())
+        }
+
+        MatchRuleFunction::EqualsIgnoringCase => {
+            return Ok(default_case_fold_str(primary_file_value.trim())
+                == default_case_fold_str(comparison_file_value.trim()))
+        }
+
+        MatchRuleFunction::AmountWithin => {
+            let difference =
+                (parse_amount(primary_file_value)? - parse_amount(comparison_file_value)?).abs();
+            return Ok(difference <= argument + 1e-9);
+        }
+
+        MatchRuleFunction::AmountWithinPercent => {
+            let primary_file_amount = parse_amount(primary_file_value)?;
+            let difference = (primary_file_amount - parse_amount(comparison_file_value)?).abs();
+            return Ok(difference <= primary_file_amount.abs() * argument / 100.0 + 1e-9);
+        }
+
+        MatchRuleFunction::DateWithinDays => {
+            let days_apart = (parse_date(primary_file_value)? - parse_date(comparison_file_value)?)
+                .num_days()
+                .abs();
+            return Ok(days_apart as f64 <= argument);
+        }
+
+        MatchRuleFunction::SimilarText => {
+            return Ok(
+                get_text_similarity(primary_file_value, comparison_file_value) >= argument - 1e-9,
+            )
+        }
+    }
+}
+
+//how alike 2 texts are, from 0 for nothing in common to 1 for the same text,
+//ignoring case and the white space between words
+pub fn get_text_similarity(text: &String, other_text: &String) -> f64 {
+    let characters: Vec<char> = default_case_fold_str(&collapse_white_space(text))
+        .chars()
+        .collect();
+    let other_characters: Vec<char> = default_case_fold_str(&collapse_white_space(other_text))
+        .chars()
+        .collect();
+
+    let longest_length = characters.len().max(other_characters.len());
+    if longest_length == 0 {
+        return 1.0;
+    }
+
+    //the levenshtein distance, keeping only the previous row of the edit matrix
+    let mut previous_distances: Vec<usize> = (0..=other_characters.len()).collect();
+    for (index, character) in characters.iter().enumerate() {
+        let mut distances = vec![index + 1];
+        for (other_index, other_character) in other_characters.iter().enumerate() {
+            let substitution_cost = match character == other_character {
+                true => 0,
+                false => 1,
+            };
+            distances.push(
+                (previous_distances[other_index] + substitution_cost)
+                    .min(previous_distances[other_index + 1] + 1)
+                    .min(distances[other_index] + 1),
+            );
+        }
+        previous_distances = distances;
+    }
+
+    let distance = previous_distances[other_characters.len()];
+    return 1.0 - distance as f64 / longest_length as f64;
+}
+
+fn collapse_white_space(text: &String) -> String {
+    return text.split_whitespace().collect::<Vec<&str>>().join(" ");
+}
+
+fn read_column(
+    column_reference: &ColumnReference,
+    row_parts: &Vec<String>,
+    column_indexes: &HashMap<String, usize>,
+) -> Option<String> {
+    let column_index = match column_reference {
+        ColumnReference::Index(column_index) => *column_index,
+        ColumnReference::Header(header) => *column_indexes.get(header)?,
+    };
+
+    return row_parts.get(column_index).map(|s| s.to_owned());
+}
+
+fn describe_column_reference(column_reference: &ColumnReference) -> String {
+    match column_reference {
+        ColumnReference::Index(column_index) => format!("[{}]", column_index),
+        ColumnReference::Header(header) => format!("[\"{}\"]", header),
+    }
+}
+
+//parses a date written in one of the supported date formats, or as an RFC 3339 date time
+pub fn parse_date(date: &String) -> Result<NaiveDate, String> {
+    let date = date.trim();
+
+    for date_format in DATE_FORMATS {
+        if let Ok(parsed_date) = NaiveDate::parse_from_str(date, date_format) {
+            return Ok(parsed_date);
+        }
+    }
+
+    return DateTime::parse_from_rfc3339(date)
+        .map(|parsed_date_time| parsed_date_time.naive_local().date())
+        .map_err(|_| format!("[{}] is not a date", date));
+}
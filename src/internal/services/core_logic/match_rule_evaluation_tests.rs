@@ -0,0 +1,291 @@
+use crate::internal::models::entities::match_rule::{
+    validate_match_rule, ColumnReference, MatchRule, MatchRuleFunction, MatchRulePredicate,
+    MAX_MATCH_RULE_NESTING_DEPTH, MAX_MATCH_RULE_PREDICATES,
+};
+
+use super::comparison_pair_resolution::resolve_match_rule_column_headers;
+use super::match_rule_evaluation::{
+    evaluate_match_rule, get_text_similarity, parse_date, MatchRuleEvaluationContext,
+};
+
+const AMOUNT_AND_DATE_OR_REFERENCE_RULE: &str = r#"amount_within_percent(primary[1], comparison[1], 0.5) AND (date_within_days(primary["Value Date"], comparison[2], 2) OR equals(primary[0], comparison[0]))"#;
+
+#[actix_web::test]
+async fn test_parse_match_rule_gives_and_precedence_over_or() {
+    //act
+    let actual = MatchRule::parse("equals(primary[0], comparison[0]) OR equals(primary[1], comparison[1]) and NOT equals(primary[2], comparison[2])").unwrap();
+
+    //assert
+    let expected = MatchRule::Or(
+        Box::new(equals_predicate(0)),
+        Box::new(MatchRule::And(
+            Box::new(equals_predicate(1)),
+            Box::new(MatchRule::Not(Box::new(equals_predicate(2)))),
+        )),
+    );
+    assert_eq!(actual, expected);
+}
+
+#[actix_web::test]
+async fn test_parse_match_rule_reads_headers_and_arguments() {
+    //act
+    let actual =
+        MatchRule::parse(r#"date_within_days(primary["Value Date"], comparison[2], 2)"#).unwrap();
+
+    //assert
+    let expected = MatchRule::Predicate(MatchRulePredicate {
+        function: MatchRuleFunction::DateWithinDays,
+        primary_file_column: ColumnReference::Header(String::from("Value Date")),
+        comparison_file_column: ColumnReference::Index(2),
+        argument: Some(2.0),
+    });
+    assert_eq!(actual, expected);
+}
+
+#[actix_web::test]
+async fn test_validate_match_rule_given_invalid_rules_returns_error() {
+    //setup
+    let invalid_match_rules = vec![
+        "equals(primary[0], comparison[0]) AND",
+        "unknown(primary[0], comparison[0])",
+        "amount_within(primary[0], comparison[0])",
+        "equals(comparison[0], primary[0])",
+        "(equals(primary[0], comparison[0])",
+        r#"equals(primary["ID, comparison[0])"#,
+    ];
+
+    for invalid_match_rule in invalid_match_rules {
+        //act
+        let actual = validate_match_rule(&invalid_match_rule.to_string());
+
+        //assert
+        assert_eq!(actual.is_err(), true, "{}", invalid_match_rule);
+    }
+}
+
+#[actix_web::test]
+async fn test_validate_match_rule_given_invalid_rule_returns_what_the_parser_stopped_at() {
+    //setup
+    let invalid_match_rules = vec![
+        (
+            String::from("equals(primary[0], comparison[0]))"),
+            String::from("unexpected [)] after the end of the rule"),
+        ),
+        (
+            format!(
+                "{}equals(primary[0], comparison[0]){}",
+                "(".repeat(MAX_MATCH_RULE_NESTING_DEPTH + 1),
+                ")".repeat(MAX_MATCH_RULE_NESTING_DEPTH + 1)
+            ),
+            format!("nested more than [{}] deep", MAX_MATCH_RULE_NESTING_DEPTH),
+        ),
+    ];
+
+    for (invalid_match_rule, expected) in invalid_match_rules {
+        //act
+        let actual = validate_match_rule(&invalid_match_rule);
+
+        //assert
+        let actual_message = actual.unwrap_err().message.unwrap();
+        assert!(actual_message.contains(&expected), "{}", actual_message);
+    }
+}
+
+#[actix_web::test]
+async fn test_validate_match_rule_given_deeply_nested_rules_returns_error() {
+    //setup
+    let predicate = "equals(primary[0], comparison[0])";
+    let deeply_nested_match_rules = vec![
+        format!("{}{}{}", "(".repeat(100_000), predicate, ")".repeat(100_000)),
+        format!("{}{}", "NOT ".repeat(100_000), predicate),
+        vec![predicate; MAX_MATCH_RULE_PREDICATES + 1].join(" AND "),
+    ];
+
+    for deeply_nested_match_rule in deeply_nested_match_rules {
+        //act
+        let actual = validate_match_rule(&deeply_nested_match_rule);
+
+        //assert
+        assert_eq!(actual.is_err(), true);
+    }
+}
+
+#[actix_web::test]
+async fn test_parse_match_rule_given_rule_nested_as_deep_as_allowed_parses_it() {
+    //setup
+    let match_rule = format!(
+        "{}equals(primary[0], comparison[0]){}",
+        "(".repeat(MAX_MATCH_RULE_NESTING_DEPTH),
+        ")".repeat(MAX_MATCH_RULE_NESTING_DEPTH)
+    );
+
+    //act
+    let actual = MatchRule::parse(&match_rule).unwrap();
+
+    //assert
+    assert_eq!(actual, equals_predicate(0));
+}
+
+#[actix_web::test]
+async fn test_evaluate_match_rule_given_amount_within_tolerance_and_close_dates_is_satisfied() {
+    //setup
+    let match_rule = MatchRule::parse(AMOUNT_AND_DATE_OR_REFERENCE_RULE).unwrap();
+    let mut trace = vec![];
+
+    //act
+    let actual = evaluate_with_rows(
+        &match_rule,
+        "REF-1, 1000, 2022-08-01",
+        "REF-2, 1004.50, 2022-08-03",
+        &mut trace,
+    );
+
+    //assert
+    assert_eq!(actual, true);
+    assert_eq!(trace.len(), 2);
+    assert_eq!(
+        trace[1],
+        r#"date_within_days(primary["Value Date"] = [ 2022-08-01], comparison[2] = [ 2022-08-03], 2) is true"#
+    );
+}
+
+#[actix_web::test]
+async fn test_evaluate_match_rule_given_far_dates_falls_back_to_the_reference() {
+    //setup
+    let match_rule = MatchRule::parse(AMOUNT_AND_DATE_OR_REFERENCE_RULE).unwrap();
+    let mut trace = vec![];
+
+    //act
+    let actual = evaluate_with_rows(
+        &match_rule,
+        "REF-1, 1000, 2022-08-01",
+        "REF-2, 1000, 01/09/2022",
+        &mut trace,
+    );
+
+    //assert
+    assert_eq!(actual, false);
+    assert_eq!(trace.len(), 3);
+    assert_eq!(trace[2].ends_with("is false"), true);
+}
+
+#[actix_web::test]
+async fn test_evaluate_match_rule_given_amount_outside_tolerance_stops_at_the_amount() {
+    //setup
+    let match_rule = MatchRule::parse(AMOUNT_AND_DATE_OR_REFERENCE_RULE).unwrap();
+    let mut trace = vec![];
+
+    //act
+    let actual = evaluate_with_rows(
+        &match_rule,
+        "REF-1, 1000, 2022-08-01",
+        "REF-1, 1010, 2022-08-01",
+        &mut trace,
+    );
+
+    //assert
+    assert_eq!(actual, false);
+    assert_eq!(trace.len(), 1);
+}
+
+#[actix_web::test]
+async fn test_evaluate_match_rule_given_unreadable_amount_records_why_it_failed() {
+    //setup
+    let match_rule = MatchRule::parse("amount_within(primary[1], comparison[1], 1)").unwrap();
+    let mut trace = vec![];
+
+    //act
+    let actual = evaluate_with_rows(
+        &match_rule,
+        "REF-1, 1000, 2022-08-01",
+        "REF-1, N/A, 2022-08-01",
+        &mut trace,
+    );
+
+    //assert
+    assert_eq!(actual, false);
+    assert_eq!(
+        trace[0].ends_with("is false: amount [N/A] is not a number"),
+        true
+    );
+}
+
+#[actix_web::test]
+async fn test_parse_date_given_supported_formats_returns_the_same_date() {
+    //setup
+    let dates = vec![
+        "2022-08-01",
+        "01/08/2022",
+        "20220801",
+        "2022-08-01T10:00:00+03:00",
+    ];
+
+    for date in dates {
+        //act
+        let actual = parse_date(&date.to_string());
+
+        //assert
+        assert_eq!(actual, parse_date(&String::from("2022-08-01")), "{}", date);
+    }
+}
+
+fn evaluate_with_rows(
+    match_rule: &MatchRule,
+    primary_file_row: &str,
+    comparison_file_row: &str,
+    trace: &mut Vec<String>,
+) -> bool {
+    let primary_file_row_parts = build_columns(primary_file_row);
+    let comparison_file_row_parts = build_columns(comparison_file_row);
+    let primary_file_column_headers = build_columns("Reference, Amount, Value Date");
+    let comparison_file_column_headers = build_columns("Reference, Amount, Date");
+    let column_indexes = resolve_match_rule_column_headers(
+        match_rule,
+        &primary_file_column_headers,
+        &comparison_file_column_headers,
+    )
+    .unwrap();
+
+    return evaluate_match_rule(
+        match_rule,
+        &MatchRuleEvaluationContext {
+            primary_file_row_parts: &primary_file_row_parts,
+            comparison_file_row_parts: &comparison_file_row_parts,
+            column_indexes: &column_indexes,
+        },
+        trace,
+    );
+}
+
+fn build_columns(row: &str) -> Vec<String> {
+    row.split(',').map(|s| s.to_string()).collect()
+}
+
+fn equals_predicate(column_index: usize) -> MatchRule {
+    MatchRule::Predicate(MatchRulePredicate {
+        function: MatchRuleFunction::Equals,
+        primary_file_column: ColumnReference::Index(column_index),
+        comparison_file_column: ColumnReference::Index(column_index),
+        argument: None,
+    })
+}
+
+#[actix_web::test]
+async fn test_get_text_similarity_ignores_case_and_extra_white_space() {
+    //act
+    let same_text = get_text_similarity(
+        &String::from("Acme  Trading Ltd"),
+        &String::from(" ACME Trading LTD"),
+    );
+    let similar_text = get_text_similarity(
+        &String::from("Acme Trading Ltd"),
+        &String::from("Acme Tradng Ltd."),
+    );
+    let different_text =
+        get_text_similarity(&String::from("Acme Trading Ltd"), &String::from("Globex"));
+
+    //assert
+    assert_eq!(same_text, 1.0);
+    assert_eq!(similar_text > 0.8 && similar_text < 1.0, true);
+    assert_eq!(different_text < 0.5, true);
+}
@@ -0,0 +1,299 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use crate::internal::{
+    interfaces::file_reconciliation_algorithm::FileReconciliationAlgorithmInterface,
+    models::entities::{
+        match_rule::MatchRule,
+        recon_result_reason::{ReconResultReason, ReconResultReasonCode},
+        reconciliation_options::{ReconciliationOptions, SuccessfulRowHandling},
+        row_reconciliation_details::RowReconciliationDetails,
+    },
+    services::core_logic::{
+        column_value_preparation::ColumnValuePreparer,
+        comparison_pair_resolution::{resolve_match_rule_column_headers, MatchRuleColumnIndexes},
+        generic_file_reconciliation_algorithm::GenericFileReconciliationAlgorithm,
+        match_rule_evaluation::{evaluate_match_rule, MatchRuleEvaluationContext},
+        parallel_row_reconciliation::{
+            reconcile_rows_in_parallel, reconcile_rows_on_blocking_pool,
+        },
+        row_identifier_index::RowIdentifierIndex,
+    },
+    shared_reconciler_rust_libraries::models::entities::{
+        app_errors::{AppError, AppErrorKind},
+        file_upload_chunk::{FileUploadChunk, FileUploadChunkRow, ReconStatus},
+    },
+};
+
+//reconciles rows using the match rule of the reconciliation options instead of the
+//non identifier comparison pairs. the row identifier comparison pairs, if there are any,
+//pick the comparison rows the rule is evaluated against, otherwise every comparison row is a candidate
+pub struct MatchRuleFileReconciliationAlgorithm {}
+
+#[async_trait]
+impl FileReconciliationAlgorithmInterface for MatchRuleFileReconciliationAlgorithm {
+    async fn reconcile_primary_file_chunk(
+        &self,
+        primary_file_chunk: &mut FileUploadChunk,
+        comparison_file_chunk: &Arc<FileUploadChunk>,
+        reconciliation_options: &ReconciliationOptions,
+        rows_reconciliation_details: &mut Vec<RowReconciliationDetails>,
+    ) -> Result<(), AppError> {
+        return reconcile_rows_on_blocking_pool(
+            primary_file_chunk,
+            comparison_file_chunk,
+            reconciliation_options,
+            rows_reconciliation_details,
+            |primary_file_chunk,
+             comparison_file_chunk,
+             reconciliation_options,
+             rows_reconciliation_details| {
+                MatchRuleFileReconciliationAlgorithm {}.reconcile_rows(
+                    primary_file_chunk,
+                    comparison_file_chunk,
+                    reconciliation_options,
+                    rows_reconciliation_details,
+                )
+            },
+        )
+        .await;
+    }
+}
+
+impl MatchRuleFileReconciliationAlgorithm {
+    //reconciles the rows of the primary file chunk in parallel on the rayon thread pool.
+    //every row only touches its own details, and new details are added in row order
+    pub fn reconcile_rows(
+        &self,
+        primary_file_chunk: &mut FileUploadChunk,
+        comparison_file_chunk: &FileUploadChunk,
+        reconciliation_options: &ReconciliationOptions,
+        rows_reconciliation_details: &mut Vec<RowReconciliationDetails>,
+    ) -> Result<(), AppError> {
+        //the rule is parsed once for the whole chunk
+        let match_rule = self.parse_match_rule(reconciliation_options)?;
+
+        //and the headers it references are looked up once for the whole chunk,
+        //failing the chunk if one of them is missing or ambiguous
+        let match_rule_column_indexes = resolve_match_rule_column_headers(
+            &match_rule,
+            &primary_file_chunk.column_headers,
+            &comparison_file_chunk.column_headers,
+        )?;
+
+        //identifiers are prepared once for the whole chunk,
+        //the same way the generic algorithm prepares them
+        let column_value_preparer =
+            ColumnValuePreparer::new(&primary_file_chunk.comparison_pairs, reconciliation_options)?;
+        let row_identifier_index = RowIdentifierIndex::new(
+            primary_file_chunk,
+            comparison_file_chunk,
+            &column_value_preparer,
+        );
+
+        let should_check_successful_rows = reconciliation_options.successful_row_handling
+            == SuccessfulRowHandling::CheckAgainstAllComparisonChunks;
+
+        let reconcile_row =
+            |primary_file_row_index: usize,
+             primary_chunk_row: &mut FileUploadChunkRow,
+             row_reconciliation_details: &mut RowReconciliationDetails| {
+                match primary_chunk_row.recon_result {
+                    ReconStatus::Successful if should_check_successful_rows => self
+                        .check_for_cross_chunk_duplicate(
+                            primary_file_row_index,
+                            primary_chunk_row,
+                            row_reconciliation_details,
+                            comparison_file_chunk,
+                            &match_rule,
+                            &match_rule_column_indexes,
+                            &row_identifier_index,
+                        ),
+                    //otherwise only rows that are still pending are matched
+                    ReconStatus::Pending => self.match_row(
+                        primary_file_row_index,
+                        primary_chunk_row,
+                        row_reconciliation_details,
+                        comparison_file_chunk,
+                        &match_rule,
+                        &match_rule_column_indexes,
+                        &row_identifier_index,
+                    ),
+                    _ => {}
+                }
+            };
+
+        reconcile_rows_in_parallel(
+            &mut primary_file_chunk.chunk_rows,
+            rows_reconciliation_details,
+            reconcile_row,
+        );
+
+        return Ok(());
+    }
+
+    //the row was already matched against an earlier comparison file chunk, so it is failed
+    //if the rule would also match it with a row of this chunk, the same way the generic
+    //algorithm fails a row with the same identifiers in a later chunk
+    fn check_for_cross_chunk_duplicate(
+        &self,
+        primary_file_row_index: usize,
+        primary_chunk_row: &mut FileUploadChunkRow,
+        row_reconciliation_details: &mut RowReconciliationDetails,
+        comparison_file_chunk: &FileUploadChunk,
+        match_rule: &MatchRule,
+        match_rule_column_indexes: &MatchRuleColumnIndexes,
+        row_identifier_index: &RowIdentifierIndex,
+    ) {
+        let generic_file_reconciliation_algorithm = GenericFileReconciliationAlgorithm {};
+
+        let duplicate_comparison_row = row_identifier_index
+            .get_comparison_file_row_indexes(primary_file_row_index)
+            .iter()
+            .map(|comparison_index| &comparison_file_chunk.chunk_rows[*comparison_index])
+            .find(|comparison_chunk_row| {
+                evaluate_match_rule(
+                    match_rule,
+                    &MatchRuleEvaluationContext {
+                        primary_file_row_parts: &primary_chunk_row.parsed_columns_from_row,
+                        comparison_file_row_parts: &comparison_chunk_row.parsed_columns_from_row,
+                        column_indexes: match_rule_column_indexes,
+                    },
+                    &mut vec![],
+                )
+            });
+
+        if let Some(duplicate_comparison_row) = duplicate_comparison_row {
+            generic_file_reconciliation_algorithm.fail_cross_chunk_duplicate(
+                primary_chunk_row,
+                row_reconciliation_details,
+                comparison_file_chunk,
+                duplicate_comparison_row,
+            );
+            row_reconciliation_details.recon_outcome = generic_file_reconciliation_algorithm
+                .get_recon_outcome(&ReconStatus::Failed, row_reconciliation_details);
+        }
+    }
+
+    //evaluates the rule against every comparison row with the same identifiers,
+    //matching the row with the first comparison row that satisfies it
+    fn match_row(
+        &self,
+        primary_file_row_index: usize,
+        primary_chunk_row: &mut FileUploadChunkRow,
+        row_reconciliation_details: &mut RowReconciliationDetails,
+        comparison_file_chunk: &FileUploadChunk,
+        match_rule: &MatchRule,
+        match_rule_column_indexes: &MatchRuleColumnIndexes,
+        row_identifier_index: &RowIdentifierIndex,
+    ) {
+        let generic_file_reconciliation_algorithm = GenericFileReconciliationAlgorithm {};
+
+        let mut recon_result_reasons = vec![];
+        let mut row_failures = vec![];
+        let mut matched_comparison_row_number = None;
+
+        for comparison_file_row_index in
+            row_identifier_index.get_comparison_file_row_indexes(primary_file_row_index)
+        {
+            let comparison_chunk_row =
+                &comparison_file_chunk.chunk_rows[*comparison_file_row_index];
+
+            let mut match_rule_trace = vec![];
+            let is_match = evaluate_match_rule(
+                match_rule,
+                &MatchRuleEvaluationContext {
+                    primary_file_row_parts: &primary_chunk_row.parsed_columns_from_row,
+                    comparison_file_row_parts: &comparison_chunk_row.parsed_columns_from_row,
+                    column_indexes: match_rule_column_indexes,
+                },
+                &mut match_rule_trace,
+            );
+
+            //without identifiers every comparison row is a candidate,
+            //so we only keep the trace of the row that matched
+            if !is_match && !row_identifier_index.has_row_identifiers() {
+                continue;
+            }
+
+            if !is_match {
+                row_failures.push(format!(
+                    "RowNumber: [{}], match rule is not satisfied by ComparisonFile row [{}]: {}",
+                    primary_chunk_row.row_number,
+                    comparison_chunk_row.row_number,
+                    match_rule_trace.join(", ")
+                ));
+            }
+
+            recon_result_reasons.push(ReconResultReason {
+                reason_code: match is_match {
+                    true => ReconResultReasonCode::MatchRuleSatisfied,
+                    false => ReconResultReasonCode::MatchRuleNotSatisfied,
+                },
+                matched_comparison_row_number: Some(comparison_chunk_row.row_number),
+                comparison_file_chunk_id: comparison_file_chunk.id.clone(),
+                match_rule_trace,
+                ..ReconResultReason::default()
+            });
+
+            if is_match {
+                matched_comparison_row_number = Some(comparison_chunk_row.row_number);
+                break;
+            }
+        }
+
+        if recon_result_reasons.is_empty() {
+            return;
+        }
+
+        row_reconciliation_details
+            .recon_result_reasons
+            .extend(recon_result_reasons);
+
+        match matched_comparison_row_number {
+            Some(matched_comparison_row_number) => {
+                //we keep track of the row it was matched with so the pairing can be audited
+                row_reconciliation_details.matched_comparison_row_number =
+                    Some(matched_comparison_row_number);
+                row_reconciliation_details.matched_comparison_file_chunk_id =
+                    Some(comparison_file_chunk.id.clone());
+                row_reconciliation_details.matched_comparison_upload_request_id =
+                    Some(comparison_file_chunk.upload_request_id.clone());
+
+                primary_chunk_row.recon_result = ReconStatus::Successful;
+            }
+            None => {
+                //the row has the same identifiers as rows in this chunk but the rule rejected all of them
+                primary_chunk_row.recon_result = ReconStatus::Failed;
+                primary_chunk_row.recon_result_reasons.extend(row_failures);
+            }
+        }
+
+        row_reconciliation_details.recon_outcome = generic_file_reconciliation_algorithm
+            .get_recon_outcome(&primary_chunk_row.recon_result, row_reconciliation_details);
+    }
+
+    //the rule was validated when the request arrived
+    //so this only fails for requests that skipped validation
+    pub fn parse_match_rule(
+        &self,
+        reconciliation_options: &ReconciliationOptions,
+    ) -> Result<MatchRule, AppError> {
+        let match_rule = match &reconciliation_options.match_rule {
+            Some(match_rule) => match_rule,
+            None => {
+                return Err(AppError::new(
+                    AppErrorKind::BadClientRequest,
+                    String::from("a match rule is required to reconcile using match rules"),
+                ))
+            }
+        };
+
+        return MatchRule::parse(match_rule).map_err(|error| {
+            AppError::new(
+                AppErrorKind::BadClientRequest,
+                format!("invalid match rule [{}]: {}", match_rule, error),
+            )
+        });
+    }
+}
@@ -0,0 +1,397 @@
+use crate::internal::{
+    interfaces::file_reconciliation_algorithm::FileReconciliationAlgorithmInterface,
+    models::entities::{
+        recon_result_reason::ReconResultReasonCode,
+        reconciliation_options::{ReconciliationOptions, SuccessfulRowHandling},
+        row_reconciliation_details::{ReconOutcome, RowReconciliationDetails},
+    },
+    shared_reconciler_rust_libraries::models::entities::{
+        app_errors::AppErrorKind,
+        file_chunk_queue::FileChunkQueue,
+        file_upload_chunk::{
+            FileUploadChunk, FileUploadChunkRow, FileUploadChunkSource, ReconStatus,
+        },
+        recon_tasks_models::{ComparisonPair, ReconciliationConfigs},
+    },
+};
+use std::sync::Arc;
+
+use super::match_rule_file_reconciliation_algorithm::MatchRuleFileReconciliationAlgorithm;
+
+const AMOUNT_WITHIN_ONE_RULE: &str = "amount_within(primary[1], comparison[1], 1)";
+
+#[actix_web::test]
+async fn test_reconcile_primary_file_chunk_given_rule_satisfied_matches_the_row_and_records_the_trace(
+) {
+    //setup
+    let mut primary_file_chunk = build_file_chunk(vec![build_chunk_row(1, "REF-1, 100.00")]);
+    let comparison_file_chunk = build_file_chunk(vec![
+        build_chunk_row(7, "REF-2, 100.50"),
+        build_chunk_row(8, "REF-1, 100.50"),
+    ]);
+    let mut rows_reconciliation_details = vec![];
+    let sut = setup();
+
+    //act
+    sut.reconcile_primary_file_chunk(
+        &mut primary_file_chunk,
+        &Arc::new(comparison_file_chunk),
+        &match_rule_options(AMOUNT_WITHIN_ONE_RULE),
+        &mut rows_reconciliation_details,
+    )
+    .await
+    .unwrap();
+
+    //assert
+    assert_eq!(
+        primary_file_chunk.chunk_rows[0].recon_result,
+        ReconStatus::Successful
+    );
+    assert_eq!(
+        rows_reconciliation_details[0].recon_outcome,
+        ReconOutcome::Matched
+    );
+    assert_eq!(
+        rows_reconciliation_details[0].matched_comparison_row_number,
+        Some(8)
+    );
+
+    let recon_result_reason = &rows_reconciliation_details[0].recon_result_reasons[0];
+    assert_eq!(
+        recon_result_reason.reason_code,
+        ReconResultReasonCode::MatchRuleSatisfied
+    );
+    assert_eq!(
+        recon_result_reason.match_rule_trace,
+        vec!["amount_within(primary[1] = [ 100.00], comparison[1] = [ 100.50], 1) is true"]
+    );
+}
+
+#[actix_web::test]
+async fn test_reconcile_primary_file_chunk_given_rule_not_satisfied_by_row_with_same_identifiers_fails_the_row(
+) {
+    //setup
+    let mut primary_file_chunk = build_file_chunk(vec![build_chunk_row(1, "REF-1, 100.00")]);
+    let comparison_file_chunk = build_file_chunk(vec![build_chunk_row(8, "REF-1, 150.00")]);
+    let mut rows_reconciliation_details = vec![];
+    let sut = setup();
+
+    //act
+    sut.reconcile_primary_file_chunk(
+        &mut primary_file_chunk,
+        &Arc::new(comparison_file_chunk),
+        &match_rule_options(AMOUNT_WITHIN_ONE_RULE),
+        &mut rows_reconciliation_details,
+    )
+    .await
+    .unwrap();
+
+    //assert
+    assert_eq!(
+        primary_file_chunk.chunk_rows[0].recon_result,
+        ReconStatus::Failed
+    );
+    assert_eq!(
+        primary_file_chunk.chunk_rows[0].recon_result_reasons[0].ends_with("1) is false"),
+        true
+    );
+    assert_eq!(
+        rows_reconciliation_details[0].recon_result_reasons[0].reason_code,
+        ReconResultReasonCode::MatchRuleNotSatisfied
+    );
+}
+
+#[actix_web::test]
+async fn test_reconcile_primary_file_chunk_given_no_identifiers_and_no_satisfying_row_leaves_the_row_pending(
+) {
+    //setup
+    let mut primary_file_chunk = build_file_chunk(vec![build_chunk_row(1, "REF-1, 100.00")]);
+    primary_file_chunk.comparison_pairs = vec![];
+    let comparison_file_chunk = build_file_chunk(vec![build_chunk_row(8, "REF-1, 150.00")]);
+    let mut rows_reconciliation_details = vec![];
+    let sut = setup();
+
+    //act
+    sut.reconcile_primary_file_chunk(
+        &mut primary_file_chunk,
+        &Arc::new(comparison_file_chunk),
+        &match_rule_options(AMOUNT_WITHIN_ONE_RULE),
+        &mut rows_reconciliation_details,
+    )
+    .await
+    .unwrap();
+
+    //assert
+    assert_eq!(
+        primary_file_chunk.chunk_rows[0].recon_result,
+        ReconStatus::Pending
+    );
+    assert_eq!(rows_reconciliation_details.len(), 0);
+}
+
+#[actix_web::test]
+async fn test_reconcile_primary_file_chunk_given_check_against_all_chunks_and_matched_row_satisfied_again_fails_the_row(
+) {
+    //setup
+    let mut primary_file_chunk = build_file_chunk(vec![build_chunk_row(1, "REF-1, 100.00")]);
+    primary_file_chunk.chunk_rows[0].recon_result = ReconStatus::Successful;
+    let mut comparison_file_chunk = build_file_chunk(vec![build_chunk_row(9, "REF-1, 100.50")]);
+    comparison_file_chunk.id = String::from("comparison-chunk-2");
+    let mut rows_reconciliation_details = vec![RowReconciliationDetails {
+        row_number: 1,
+        matched_comparison_row_number: Some(8),
+        matched_comparison_file_chunk_id: Some(String::from("comparison-chunk-1")),
+        ..RowReconciliationDetails::default()
+    }];
+    let sut = setup();
+
+    let mut reconciliation_options = match_rule_options(AMOUNT_WITHIN_ONE_RULE);
+    reconciliation_options.successful_row_handling =
+        SuccessfulRowHandling::CheckAgainstAllComparisonChunks;
+
+    //act
+    sut.reconcile_primary_file_chunk(
+        &mut primary_file_chunk,
+        &Arc::new(comparison_file_chunk),
+        &reconciliation_options,
+        &mut rows_reconciliation_details,
+    )
+    .await
+    .unwrap();
+
+    //assert
+    assert_eq!(
+        primary_file_chunk.chunk_rows[0].recon_result,
+        ReconStatus::Failed
+    );
+    assert_eq!(
+        rows_reconciliation_details[0].recon_outcome,
+        ReconOutcome::Failed
+    );
+
+    let recon_result_reason = &rows_reconciliation_details[0].recon_result_reasons[0];
+    assert_eq!(
+        recon_result_reason.reason_code,
+        ReconResultReasonCode::DuplicateComparisonRow
+    );
+    assert_eq!(recon_result_reason.matched_comparison_row_number, Some(9));
+    assert_eq!(
+        recon_result_reason.comparison_file_chunk_id,
+        "comparison-chunk-2"
+    );
+}
+
+#[actix_web::test]
+async fn test_reconcile_primary_file_chunk_given_check_against_all_chunks_and_rule_not_satisfied_again_keeps_the_match(
+) {
+    //setup
+    let mut primary_file_chunk = build_file_chunk(vec![build_chunk_row(1, "REF-1, 100.00")]);
+    primary_file_chunk.chunk_rows[0].recon_result = ReconStatus::Successful;
+    let comparison_file_chunk = build_file_chunk(vec![build_chunk_row(9, "REF-1, 150.00")]);
+    let mut rows_reconciliation_details = vec![];
+    let sut = setup();
+
+    let mut reconciliation_options = match_rule_options(AMOUNT_WITHIN_ONE_RULE);
+    reconciliation_options.successful_row_handling =
+        SuccessfulRowHandling::CheckAgainstAllComparisonChunks;
+
+    //act
+    sut.reconcile_primary_file_chunk(
+        &mut primary_file_chunk,
+        &Arc::new(comparison_file_chunk),
+        &reconciliation_options,
+        &mut rows_reconciliation_details,
+    )
+    .await
+    .unwrap();
+
+    //assert
+    assert_eq!(
+        primary_file_chunk.chunk_rows[0].recon_result,
+        ReconStatus::Successful
+    );
+    assert_eq!(rows_reconciliation_details.len(), 0);
+}
+
+#[actix_web::test]
+async fn test_reconcile_primary_file_chunk_given_first_match_wins_does_not_check_matched_rows_again(
+) {
+    //setup
+    let mut primary_file_chunk = build_file_chunk(vec![build_chunk_row(1, "REF-1, 100.00")]);
+    primary_file_chunk.chunk_rows[0].recon_result = ReconStatus::Successful;
+    let comparison_file_chunk = build_file_chunk(vec![build_chunk_row(9, "REF-1, 100.50")]);
+    let mut rows_reconciliation_details = vec![];
+    let sut = setup();
+
+    //act
+    sut.reconcile_primary_file_chunk(
+        &mut primary_file_chunk,
+        &Arc::new(comparison_file_chunk),
+        &match_rule_options(AMOUNT_WITHIN_ONE_RULE),
+        &mut rows_reconciliation_details,
+    )
+    .await
+    .unwrap();
+
+    //assert
+    assert_eq!(
+        primary_file_chunk.chunk_rows[0].recon_result,
+        ReconStatus::Successful
+    );
+    assert_eq!(rows_reconciliation_details.len(), 0);
+}
+
+#[actix_web::test]
+async fn test_reconcile_primary_file_chunk_given_no_match_rule_returns_bad_client_request() {
+    //setup
+    let mut primary_file_chunk = build_file_chunk(vec![build_chunk_row(1, "REF-1, 100.00")]);
+    let comparison_file_chunk = build_file_chunk(vec![]);
+    let sut = setup();
+
+    //act
+    let actual = sut
+        .reconcile_primary_file_chunk(
+            &mut primary_file_chunk,
+            &Arc::new(comparison_file_chunk),
+            &ReconciliationOptions::default(),
+            &mut vec![],
+        )
+        .await;
+
+    //assert
+    assert_eq!(actual.unwrap_err().kind, AppErrorKind::BadClientRequest);
+}
+
+#[actix_web::test]
+async fn test_reconcile_primary_file_chunk_given_rule_referencing_missing_header_returns_bad_client_request(
+) {
+    //setup
+    let mut primary_file_chunk = build_file_chunk(vec![build_chunk_row(1, "REF-1, 100.00")]);
+    let comparison_file_chunk = build_file_chunk(vec![build_chunk_row(8, "REF-1, 100.00")]);
+    let sut = setup();
+
+    //act
+    let actual = sut
+        .reconcile_primary_file_chunk(
+            &mut primary_file_chunk,
+            &Arc::new(comparison_file_chunk),
+            &match_rule_options(r#"equals(primary["Value Date"], comparison[1])"#),
+            &mut vec![],
+        )
+        .await;
+
+    //assert
+    assert_eq!(actual.unwrap_err().kind, AppErrorKind::BadClientRequest);
+}
+
+#[actix_web::test]
+async fn test_reconcile_primary_file_chunk_given_many_rows_keeps_row_order_and_details_order() {
+    //setup
+    let mut primary_file_chunk = build_file_chunk(
+        (1..=500)
+            .map(|row_number| build_chunk_row(row_number, &format!("REF-{}, 100.00", row_number)))
+            .collect(),
+    );
+
+    //only the comparison rows with an even row number are within the amount of the rule
+    let comparison_file_chunk = build_file_chunk(
+        (1..=500)
+            .rev()
+            .map(|row_number| {
+                build_chunk_row(
+                    row_number,
+                    &format!("REF-{}, {}.00", row_number, 100 + (row_number % 2) * 5),
+                )
+            })
+            .collect(),
+    );
+    let mut rows_reconciliation_details = vec![];
+    let sut = setup();
+
+    //act
+    sut.reconcile_primary_file_chunk(
+        &mut primary_file_chunk,
+        &Arc::new(comparison_file_chunk),
+        &match_rule_options(AMOUNT_WITHIN_ONE_RULE),
+        &mut rows_reconciliation_details,
+    )
+    .await
+    .unwrap();
+
+    //assert
+    let row_numbers: Vec<u64> = primary_file_chunk
+        .chunk_rows
+        .iter()
+        .map(|row| row.row_number)
+        .collect();
+    let details_row_numbers: Vec<u64> = rows_reconciliation_details
+        .iter()
+        .map(|row_details| row_details.row_number)
+        .collect();
+    assert_eq!(row_numbers, (1..=500).collect::<Vec<u64>>());
+    assert_eq!(details_row_numbers, row_numbers);
+
+    for chunk_row in &primary_file_chunk.chunk_rows {
+        let expected_recon_result = match chunk_row.row_number % 2 == 0 {
+            true => ReconStatus::Successful,
+            false => ReconStatus::Failed,
+        };
+        assert_eq!(chunk_row.recon_result, expected_recon_result);
+    }
+}
+
+fn setup() -> MatchRuleFileReconciliationAlgorithm {
+    MatchRuleFileReconciliationAlgorithm {}
+}
+
+fn match_rule_options(match_rule: &str) -> ReconciliationOptions {
+    ReconciliationOptions {
+        match_rule: Some(match_rule.to_string()),
+        ..ReconciliationOptions::default()
+    }
+}
+
+fn build_chunk_row(row_number: u64, raw_data: &str) -> FileUploadChunkRow {
+    FileUploadChunkRow {
+        row_number,
+        raw_data: raw_data.to_string(),
+        parsed_columns_from_row: raw_data.split(',').map(|s| s.to_string()).collect(),
+        recon_result: ReconStatus::Pending,
+        recon_result_reasons: vec![],
+    }
+}
+
+fn build_file_chunk(chunk_rows: Vec<FileUploadChunkRow>) -> FileUploadChunk {
+    FileUploadChunk {
+        id: String::from("file-1234"),
+        upload_request_id: String::from("file-1234"),
+        chunk_sequence_number: 1,
+        chunk_source: FileUploadChunkSource::ComparisonFileChunk,
+        chunk_rows,
+        date_created: chrono::Utc::now().timestamp(),
+        date_modified: chrono::Utc::now().timestamp(),
+        comparison_pairs: vec![ComparisonPair {
+            primary_file_column_index: 0,
+            comparison_file_column_index: 0,
+            is_row_identifier: true,
+        }],
+        column_headers: vec![String::from("Reference"), String::from("Amount")],
+        recon_config: ReconciliationConfigs {
+            should_check_for_duplicate_records_in_comparison_file: false,
+            should_reconciliation_be_case_sensitive: true,
+            should_ignore_white_space: true,
+            should_do_reverse_reconciliation: false,
+        },
+        primary_file_chunks_queue: build_file_chunks_queue("primary"),
+        comparison_file_chunks_queue: build_file_chunks_queue("comparison"),
+        result_chunks_queue: build_file_chunks_queue("result"),
+        is_last_chunk: false,
+    }
+}
+
+fn build_file_chunks_queue(prefix: &str) -> FileChunkQueue {
+    FileChunkQueue {
+        topic_id: format!("{}-file-chunks-queue-1", prefix),
+        last_acknowledged_id: None,
+    }
+}
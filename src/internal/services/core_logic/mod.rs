@@ -1,5 +1,74 @@
+pub mod best_match_assignment;
+pub mod chunk_serialization;
+pub mod column_derivation;
+pub mod column_value_preparation;
+pub mod comparison_pair_resolution;
+pub mod currency_conversion;
 pub mod generic_file_reconciliation_algorithm;
+pub mod match_rule_evaluation;
+pub mod match_rule_file_reconciliation_algorithm;
+pub mod multi_pass_file_reconciliation_algorithm;
+pub mod parallel_row_reconciliation;
+pub mod row_format_projection;
+pub mod row_identifier_index;
+pub mod value_normalization;
+
+#[cfg(test)]
+#[path = "./best_match_assignment_tests.rs"]
+mod best_match_assignment_tests;
+
+#[cfg(test)]
+#[path = "./chunk_serialization_tests.rs"]
+mod chunk_serialization_tests;
+
+#[cfg(test)]
+#[path = "./column_derivation_tests.rs"]
+mod column_derivation_tests;
+
+#[cfg(test)]
+#[path = "./column_value_preparation_tests.rs"]
+mod column_value_preparation_tests;
+
+#[cfg(test)]
+#[path = "./comparison_pair_resolution_tests.rs"]
+mod comparison_pair_resolution_tests;
+
+#[cfg(test)]
+#[path = "./currency_conversion_tests.rs"]
+mod currency_conversion_tests;
 
 #[cfg(test)]
 #[path = "./generic_file_reconciliation_algorithm_tests.rs"]
 mod generic_file_reconciliation_algorithm_tests;
+
+#[cfg(test)]
+#[path = "./generic_file_reconciliation_algorithm_benchmarks.rs"]
+mod generic_file_reconciliation_algorithm_benchmarks;
+
+#[cfg(test)]
+#[path = "./match_rule_evaluation_tests.rs"]
+mod match_rule_evaluation_tests;
+
+#[cfg(test)]
+#[path = "./match_rule_file_reconciliation_algorithm_tests.rs"]
+mod match_rule_file_reconciliation_algorithm_tests;
+
+#[cfg(test)]
+#[path = "./multi_pass_file_reconciliation_algorithm_tests.rs"]
+mod multi_pass_file_reconciliation_algorithm_tests;
+
+#[cfg(test)]
+#[path = "./parallel_row_reconciliation_tests.rs"]
+mod parallel_row_reconciliation_tests;
+
+#[cfg(test)]
+#[path = "./row_format_projection_tests.rs"]
+mod row_format_projection_tests;
+
+#[cfg(test)]
+#[path = "./row_identifier_index_tests.rs"]
+mod row_identifier_index_tests;
+
+#[cfg(test)]
+#[path = "./value_normalization_tests.rs"]
+mod value_normalization_tests;
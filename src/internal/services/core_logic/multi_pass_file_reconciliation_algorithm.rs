@@ -0,0 +1,225 @@
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use crate::internal::{
+    interfaces::file_reconciliation_algorithm::FileReconciliationAlgorithmInterface,
+    models::entities::{
+        match_pass::MatchPass,
+        reconciliation_options::ReconciliationOptions,
+        row_reconciliation_details::{RowReconciliationDetails, RowsReconciliationDetailsIndex},
+    },
+    shared_reconciler_rust_libraries::models::entities::{
+        app_errors::AppError,
+        file_upload_chunk::{FileUploadChunk, FileUploadChunkRow, ReconStatus},
+    },
+};
+
+//reconciles rows in the match passes of the reconciliation options, in order.
+//a row that an earlier pass could not pair with a comparison row, or that it failed,
+//falls through to the next pass. a failed row that no later pass matches
+//keeps the reasons it was failed with by the earlier pass.
+//the passes run in order against each comparison file chunk as it arrives, not against
+//the whole comparison file. so a row a later pass matches in an early comparison chunk
+//is not offered to an earlier pass for a comparison chunk that arrives after it
+pub struct MultiPassFileReconciliationAlgorithm {
+    pub file_reconciliation_algorithm: Box<dyn FileReconciliationAlgorithmInterface>,
+    pub match_rule_reconciliation_algorithm: Box<dyn FileReconciliationAlgorithmInterface>,
+}
+
+#[async_trait]
+impl FileReconciliationAlgorithmInterface for MultiPassFileReconciliationAlgorithm {
+    async fn reconcile_primary_file_chunk(
+        &self,
+        primary_file_chunk: &mut FileUploadChunk,
+        comparison_file_chunk: &Arc<FileUploadChunk>,
+        reconciliation_options: &ReconciliationOptions,
+        rows_reconciliation_details: &mut Vec<RowReconciliationDetails>,
+    ) -> Result<(), AppError> {
+        //the comparison pairs of the chunk are put back once every pass is done
+        let chunk_comparison_pairs = std::mem::take(&mut primary_file_chunk.comparison_pairs);
+
+        for (match_pass_index, match_pass) in reconciliation_options.match_passes.iter().enumerate()
+        {
+            let earlier_match_passes = &reconciliation_options.match_passes[..match_pass_index];
+            let mut rows_details_index =
+                RowsReconciliationDetailsIndex::new(rows_reconciliation_details);
+
+            //the rows of the pass are moved out of the chunk, the rest wait for a later pass
+            let (match_pass_rows, other_rows): (Vec<(usize, FileUploadChunkRow)>, Vec<_>) =
+                std::mem::take(&mut primary_file_chunk.chunk_rows)
+                    .into_iter()
+                    .enumerate()
+                    .partition(|(_, chunk_row)| {
+                        self.is_match_pass_row(
+                            chunk_row,
+                            match_pass,
+                            earlier_match_passes,
+                            &rows_details_index,
+                        )
+                    });
+
+            let (match_pass_row_indexes, mut match_pass_rows): (
+                Vec<usize>,
+                Vec<FileUploadChunkRow>,
+            ) = match_pass_rows.into_iter().unzip();
+
+            //rows failed by an earlier pass are tried again as pending rows,
+            //what the earlier pass found is kept in case this pass does not match them either
+            let earlier_failures: Vec<Option<EarlierFailure>> = match_pass_rows
+                .iter_mut()
+                .map(|chunk_row| Self::take_earlier_failure(chunk_row, &mut rows_details_index))
+                .collect();
+
+            drop(rows_details_index);
+
+            let were_pending: Vec<bool> = match_pass_rows
+                .iter()
+                .map(|chunk_row| chunk_row.recon_result == ReconStatus::Pending)
+                .collect();
+
+            //the pass works on the chunk holding only its rows
+            //and with its own comparison pairs in place of the ones of the chunk
+            primary_file_chunk.chunk_rows = match_pass_rows;
+            primary_file_chunk.comparison_pairs = match_pass.comparison_pairs.clone();
+
+            let file_reconciliation_algorithm = match match_pass.match_rule {
+                Some(_) => &self.match_rule_reconciliation_algorithm,
+                None => &self.file_reconciliation_algorithm,
+            };
+
+            if !primary_file_chunk.chunk_rows.is_empty() {
+                file_reconciliation_algorithm
+                    .reconcile_primary_file_chunk(
+                        primary_file_chunk,
+                        comparison_file_chunk,
+                        &match_pass.get_reconciliation_options(reconciliation_options),
+                        rows_reconciliation_details,
+                    )
+                    .await?;
+            }
+
+            //we note which pass matched or failed the rows that were pending before it
+            let mut rows_details_index =
+                RowsReconciliationDetailsIndex::new(rows_reconciliation_details);
+
+            for ((chunk_row, was_pending), earlier_failure) in primary_file_chunk
+                .chunk_rows
+                .iter_mut()
+                .zip(were_pending)
+                .zip(earlier_failures)
+            {
+                if !was_pending {
+                    continue;
+                }
+
+                match (&chunk_row.recon_result, earlier_failure) {
+                    (ReconStatus::Successful, _) | (ReconStatus::Failed, None) => {
+                        rows_details_index
+                            .find_or_insert(chunk_row.row_number)
+                            .match_pass_name = Some(match_pass.name.clone());
+                    }
+                    (_, Some(earlier_failure)) => Self::restore_earlier_failure(
+                        chunk_row,
+                        earlier_failure,
+                        &mut rows_details_index,
+                    ),
+                    (_, None) => {}
+                }
+            }
+
+            //the rows are put back where they came from
+            let mut chunk_rows: Vec<(usize, FileUploadChunkRow)> = match_pass_row_indexes
+                .into_iter()
+                .zip(std::mem::take(&mut primary_file_chunk.chunk_rows))
+                .chain(other_rows)
+                .collect();
+            chunk_rows.sort_by_key(|(index, _)| *index);
+
+            primary_file_chunk.chunk_rows = chunk_rows
+                .into_iter()
+                .map(|(_, chunk_row)| chunk_row)
+                .collect();
+        }
+
+        primary_file_chunk.comparison_pairs = chunk_comparison_pairs;
+
+        return Ok(());
+    }
+}
+
+//what an earlier pass found for a row it failed
+struct EarlierFailure {
+    recon_result_reasons: Vec<String>,
+    row_reconciliation_details: RowReconciliationDetails,
+}
+
+impl MultiPassFileReconciliationAlgorithm {
+    //a pass gets the rows that are still pending, the rows failed by an earlier pass
+    //and the rows it matched against an earlier comparison file chunk,
+    //so that those can be checked for duplicates using the identifiers they were matched with
+    fn is_match_pass_row(
+        &self,
+        chunk_row: &FileUploadChunkRow,
+        match_pass: &MatchPass,
+        earlier_match_passes: &[MatchPass],
+        rows_details_index: &RowsReconciliationDetailsIndex,
+    ) -> bool {
+        let row_match_pass_name = rows_details_index
+            .get(chunk_row.row_number)
+            .and_then(|row_details| row_details.match_pass_name.as_ref());
+
+        match chunk_row.recon_result {
+            ReconStatus::Pending => true,
+            ReconStatus::Successful => row_match_pass_name == Some(&match_pass.name),
+            //rows failed before matching in passes e.g for duplicates are not tried again
+            ReconStatus::Failed => earlier_match_passes
+                .iter()
+                .any(|earlier_match_pass| row_match_pass_name == Some(&earlier_match_pass.name)),
+        }
+    }
+
+    //sets a row failed by an earlier pass back to pending,
+    //returning what the earlier pass found for it
+    fn take_earlier_failure(
+        chunk_row: &mut FileUploadChunkRow,
+        rows_details_index: &mut RowsReconciliationDetailsIndex,
+    ) -> Option<EarlierFailure> {
+        if chunk_row.recon_result != ReconStatus::Failed {
+            return None;
+        }
+
+        let row_details = rows_details_index.find_or_insert(chunk_row.row_number);
+
+        //the row keeps the columns it was projected into, so it stays marked as projected
+        let is_projected = row_details.is_projected;
+
+        let earlier_failure = EarlierFailure {
+            recon_result_reasons: std::mem::take(&mut chunk_row.recon_result_reasons),
+            row_reconciliation_details: std::mem::replace(
+                row_details,
+                RowReconciliationDetails {
+                    row_number: chunk_row.row_number,
+                    is_projected,
+                    ..RowReconciliationDetails::default()
+                },
+            ),
+        };
+
+        chunk_row.recon_result = ReconStatus::Pending;
+
+        return Some(earlier_failure);
+    }
+
+    //puts back what an earlier pass found for a row this pass did not match
+    fn restore_earlier_failure(
+        chunk_row: &mut FileUploadChunkRow,
+        earlier_failure: EarlierFailure,
+        rows_details_index: &mut RowsReconciliationDetailsIndex,
+    ) {
+        chunk_row.recon_result = ReconStatus::Failed;
+        chunk_row.recon_result_reasons = earlier_failure.recon_result_reasons;
+
+        *rows_details_index.find_or_insert(chunk_row.row_number) =
+            earlier_failure.row_reconciliation_details;
+    }
+}
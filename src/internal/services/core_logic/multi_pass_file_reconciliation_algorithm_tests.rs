@@ -0,0 +1,462 @@
+use crate::internal::{
+    interfaces::file_reconciliation_algorithm::FileReconciliationAlgorithmInterface,
+    models::entities::{
+        match_pass::MatchPass, reconciliation_options::ReconciliationOptions,
+        row_reconciliation_details::RowReconciliationDetails,
+    },
+    shared_reconciler_rust_libraries::models::entities::{
+        file_chunk_queue::FileChunkQueue,
+        file_upload_chunk::{
+            FileUploadChunk, FileUploadChunkRow, FileUploadChunkSource, ReconStatus,
+        },
+        recon_tasks_models::{ComparisonPair, ReconciliationConfigs},
+    },
+};
+use std::sync::Arc;
+
+use super::{
+    generic_file_reconciliation_algorithm::GenericFileReconciliationAlgorithm,
+    match_rule_file_reconciliation_algorithm::MatchRuleFileReconciliationAlgorithm,
+    multi_pass_file_reconciliation_algorithm::MultiPassFileReconciliationAlgorithm,
+};
+
+#[actix_web::test]
+async fn test_reconcile_primary_file_chunk_given_rows_left_by_first_pass_matches_them_in_the_next_pass(
+) {
+    //setup
+    let mut primary_file_chunk = build_file_chunk(vec![
+        build_chunk_row(1, "REF-1,100.00,2024-03-01,Acme Ltd"),
+        build_chunk_row(2, "REF-2,250.00,2024-03-02,Globex"),
+        build_chunk_row(3, "REF-3,999.00,2024-03-03,Initech"),
+    ]);
+    let comparison_file_chunk = build_file_chunk(vec![
+        build_chunk_row(7, "REF-1,100.00,2024-03-01,Acme Ltd"),
+        build_chunk_row(8, "XYZ-9,250.00,2024-03-02,Globex"),
+    ]);
+    let mut rows_reconciliation_details = vec![];
+    let sut = setup();
+
+    //act
+    sut.reconcile_primary_file_chunk(
+        &mut primary_file_chunk,
+        &Arc::new(comparison_file_chunk),
+        &match_passes_options(vec![exact_reference_pass(), amount_and_date_pass()]),
+        &mut rows_reconciliation_details,
+    )
+    .await
+    .unwrap();
+
+    //assert
+    assert_eq!(
+        primary_file_chunk.chunk_rows[0].recon_result,
+        ReconStatus::Successful
+    );
+    assert_eq!(
+        primary_file_chunk.chunk_rows[1].recon_result,
+        ReconStatus::Successful
+    );
+    assert_eq!(
+        primary_file_chunk.chunk_rows[2].recon_result,
+        ReconStatus::Pending
+    );
+
+    assert_eq!(
+        find_row_details(&rows_reconciliation_details, 1).match_pass_name,
+        Some(String::from("ExactReference"))
+    );
+
+    let second_row_details = find_row_details(&rows_reconciliation_details, 2);
+    assert_eq!(
+        second_row_details.match_pass_name,
+        Some(String::from("AmountAndDate"))
+    );
+    assert_eq!(second_row_details.matched_comparison_row_number, Some(8));
+
+    assert_eq!(
+        rows_reconciliation_details
+            .iter()
+            .any(|row_details| row_details.row_number == 3
+                && row_details.match_pass_name.is_some()),
+        false
+    );
+}
+
+#[actix_web::test]
+async fn test_reconcile_primary_file_chunk_given_row_failed_by_first_pass_falls_through_to_the_next_pass(
+) {
+    //setup
+    let mut primary_file_chunk =
+        build_file_chunk(vec![build_chunk_row(1, "REF-1,100.00,2024-03-01,Acme Ltd")]);
+    let comparison_file_chunk = build_file_chunk(vec![
+        build_chunk_row(7, "REF-1,120.00,2024-03-01,Acme Ltd"),
+        build_chunk_row(8, "XYZ-9,100.00,2024-03-01,Acme Ltd"),
+    ]);
+    let mut rows_reconciliation_details = vec![];
+    let sut = setup();
+
+    //act
+    sut.reconcile_primary_file_chunk(
+        &mut primary_file_chunk,
+        &Arc::new(comparison_file_chunk),
+        &match_passes_options(vec![exact_reference_pass(), amount_and_date_pass()]),
+        &mut rows_reconciliation_details,
+    )
+    .await
+    .unwrap();
+
+    //assert
+    assert_eq!(
+        primary_file_chunk.chunk_rows[0].recon_result,
+        ReconStatus::Successful
+    );
+    assert_eq!(
+        primary_file_chunk.chunk_rows[0]
+            .recon_result_reasons
+            .is_empty(),
+        true
+    );
+
+    let row_details = find_row_details(&rows_reconciliation_details, 1);
+    assert_eq!(
+        row_details.match_pass_name,
+        Some(String::from("AmountAndDate"))
+    );
+    assert_eq!(row_details.matched_comparison_row_number, Some(8));
+    assert_eq!(row_details.recon_result_reasons.is_empty(), true);
+}
+
+#[actix_web::test]
+async fn test_reconcile_primary_file_chunk_given_row_failed_by_first_pass_and_not_matched_by_the_next_keeps_first_pass_reasons(
+) {
+    //setup
+    let mut primary_file_chunk =
+        build_file_chunk(vec![build_chunk_row(1, "REF-1,100.00,2024-03-01,Acme Ltd")]);
+    let comparison_file_chunk = build_file_chunk(vec![
+        build_chunk_row(7, "REF-1,120.00,2024-03-01,Acme Ltd"),
+        build_chunk_row(8, "XYZ-9,100.00,2024-03-09,Acme Ltd"),
+    ]);
+    let mut rows_reconciliation_details = vec![];
+    let sut = setup();
+
+    let mut first_pass_primary_file_chunk = primary_file_chunk.clone();
+    let mut first_pass_rows_reconciliation_details = vec![];
+    sut.reconcile_primary_file_chunk(
+        &mut first_pass_primary_file_chunk,
+        &Arc::new(comparison_file_chunk.clone()),
+        &match_passes_options(vec![exact_reference_pass()]),
+        &mut first_pass_rows_reconciliation_details,
+    )
+    .await
+    .unwrap();
+
+    //act
+    sut.reconcile_primary_file_chunk(
+        &mut primary_file_chunk,
+        &Arc::new(comparison_file_chunk),
+        &match_passes_options(vec![exact_reference_pass(), amount_and_date_pass()]),
+        &mut rows_reconciliation_details,
+    )
+    .await
+    .unwrap();
+
+    //assert
+    assert_eq!(
+        primary_file_chunk.chunk_rows[0].recon_result,
+        ReconStatus::Failed
+    );
+    assert_eq!(
+        primary_file_chunk.chunk_rows[0].recon_result_reasons,
+        first_pass_primary_file_chunk.chunk_rows[0].recon_result_reasons
+    );
+    assert_eq!(
+        primary_file_chunk.chunk_rows[0]
+            .recon_result_reasons
+            .is_empty(),
+        false
+    );
+    assert_eq!(
+        rows_reconciliation_details,
+        first_pass_rows_reconciliation_details
+    );
+    assert_eq!(
+        find_row_details(&rows_reconciliation_details, 1).match_pass_name,
+        Some(String::from("ExactReference"))
+    );
+}
+
+#[actix_web::test]
+async fn test_reconcile_primary_file_chunk_given_row_failed_by_a_pass_does_not_retry_it_on_the_next_comparison_chunk(
+) {
+    //setup
+    let mut primary_file_chunk =
+        build_file_chunk(vec![build_chunk_row(1, "REF-1,100.00,2024-03-01,Acme Ltd")]);
+    let first_comparison_file_chunk =
+        build_file_chunk(vec![build_chunk_row(7, "REF-1,120.00,2024-03-01,Acme Ltd")]);
+    let second_comparison_file_chunk =
+        build_file_chunk(vec![build_chunk_row(8, "REF-1,100.00,2024-03-05,Acme Ltd")]);
+    let mut rows_reconciliation_details = vec![];
+    let sut = setup();
+    let reconciliation_options =
+        match_passes_options(vec![exact_reference_pass(), amount_and_date_pass()]);
+
+    //act
+    for comparison_file_chunk in [first_comparison_file_chunk, second_comparison_file_chunk] {
+        sut.reconcile_primary_file_chunk(
+            &mut primary_file_chunk,
+            &Arc::new(comparison_file_chunk),
+            &reconciliation_options,
+            &mut rows_reconciliation_details,
+        )
+        .await
+        .unwrap();
+    }
+
+    //assert
+    //the first pass already failed the row so only the later passes try it again
+    assert_eq!(
+        primary_file_chunk.chunk_rows[0].recon_result,
+        ReconStatus::Failed
+    );
+    assert_eq!(
+        find_row_details(&rows_reconciliation_details, 1).match_pass_name,
+        Some(String::from("ExactReference"))
+    );
+}
+
+#[actix_web::test]
+async fn test_reconcile_primary_file_chunk_given_later_pass_matches_in_earlier_comparison_chunk_keeps_that_match(
+) {
+    //setup
+    let mut primary_file_chunk =
+        build_file_chunk(vec![build_chunk_row(1, "REF-1,100.00,2024-03-01,Acme Ltd")]);
+    let first_comparison_file_chunk =
+        build_file_chunk(vec![build_chunk_row(7, "XYZ-9,100.00,2024-03-01,Globex")]);
+    let second_comparison_file_chunk =
+        build_file_chunk(vec![build_chunk_row(8, "REF-1,100.00,2024-03-01,Acme Ltd")]);
+    let mut rows_reconciliation_details = vec![];
+    let sut = setup();
+    let reconciliation_options =
+        match_passes_options(vec![exact_reference_pass(), amount_and_date_pass()]);
+
+    //act
+    for comparison_file_chunk in [first_comparison_file_chunk, second_comparison_file_chunk] {
+        sut.reconcile_primary_file_chunk(
+            &mut primary_file_chunk,
+            &Arc::new(comparison_file_chunk),
+            &reconciliation_options,
+            &mut rows_reconciliation_details,
+        )
+        .await
+        .unwrap();
+    }
+
+    //assert
+    //the passes only ever see one comparison chunk, so the exact reference pass
+    //never gets to match the row with the comparison row that arrives later
+    let row_details = find_row_details(&rows_reconciliation_details, 1);
+    assert_eq!(
+        primary_file_chunk.chunk_rows[0].recon_result,
+        ReconStatus::Successful
+    );
+    assert_eq!(
+        row_details.match_pass_name,
+        Some(String::from("AmountAndDate"))
+    );
+    assert_eq!(row_details.matched_comparison_row_number, Some(7));
+}
+
+#[actix_web::test]
+async fn test_reconcile_primary_file_chunk_given_match_passes_keeps_row_order_and_chunk_comparison_pairs(
+) {
+    //setup
+    let mut primary_file_chunk = build_file_chunk(vec![
+        build_chunk_row(1, "XYZ-1,250.00,2024-03-02,Globex"),
+        build_chunk_row(2, "REF-2,100.00,2024-03-01,Acme Ltd"),
+        build_chunk_row(3, "XYZ-3,999.00,2024-03-03,Initech"),
+    ]);
+    primary_file_chunk.comparison_pairs = vec![build_comparison_pair(3, true)];
+    let comparison_file_chunk = build_file_chunk(vec![
+        build_chunk_row(7, "REF-2,100.00,2024-03-01,Acme Ltd"),
+        build_chunk_row(8, "ABC-8,250.00,2024-03-02,Globex"),
+    ]);
+    let sut = setup();
+
+    //act
+    sut.reconcile_primary_file_chunk(
+        &mut primary_file_chunk,
+        &Arc::new(comparison_file_chunk),
+        &match_passes_options(vec![exact_reference_pass(), amount_and_date_pass()]),
+        &mut vec![],
+    )
+    .await
+    .unwrap();
+
+    //assert
+    let row_numbers: Vec<u64> = primary_file_chunk
+        .chunk_rows
+        .iter()
+        .map(|chunk_row| chunk_row.row_number)
+        .collect();
+    assert_eq!(row_numbers, vec![1, 2, 3]);
+    assert_eq!(
+        primary_file_chunk.chunk_rows[0].recon_result,
+        ReconStatus::Successful
+    );
+    assert_eq!(
+        primary_file_chunk.comparison_pairs,
+        vec![build_comparison_pair(3, true)]
+    );
+}
+
+#[actix_web::test]
+async fn test_reconcile_primary_file_chunk_given_match_pass_with_match_rule_matches_similar_names()
+{
+    //setup
+    let mut primary_file_chunk = build_file_chunk(vec![build_chunk_row(
+        1,
+        "REF-1,100.00,2024-03-01,Acme Trading Ltd",
+    )]);
+    let comparison_file_chunk = build_file_chunk(vec![
+        build_chunk_row(7, "XYZ-8,100.00,2024-03-05,Globex"),
+        build_chunk_row(8, "XYZ-9,100.00,2024-03-09,ACME Trading Ltd."),
+    ]);
+    let mut rows_reconciliation_details = vec![];
+    let sut = setup();
+
+    let amount_and_name_pass = MatchPass {
+        name: String::from("AmountAndName"),
+        comparison_pairs: vec![build_comparison_pair(1, true)],
+        match_rule: Some(String::from(
+            "similar_text(primary[\"Name\"], comparison[\"Name\"], 0.8)",
+        )),
+        ..MatchPass::default()
+    };
+
+    //act
+    sut.reconcile_primary_file_chunk(
+        &mut primary_file_chunk,
+        &Arc::new(comparison_file_chunk),
+        &match_passes_options(vec![
+            exact_reference_pass(),
+            amount_and_date_pass(),
+            amount_and_name_pass,
+        ]),
+        &mut rows_reconciliation_details,
+    )
+    .await
+    .unwrap();
+
+    //assert
+    assert_eq!(
+        primary_file_chunk.chunk_rows[0].recon_result,
+        ReconStatus::Successful
+    );
+
+    let row_details = find_row_details(&rows_reconciliation_details, 1);
+    assert_eq!(
+        row_details.match_pass_name,
+        Some(String::from("AmountAndName"))
+    );
+    assert_eq!(row_details.matched_comparison_row_number, Some(8));
+}
+
+fn setup() -> MultiPassFileReconciliationAlgorithm {
+    MultiPassFileReconciliationAlgorithm {
+        file_reconciliation_algorithm: Box::new(GenericFileReconciliationAlgorithm {}),
+        match_rule_reconciliation_algorithm: Box::new(MatchRuleFileReconciliationAlgorithm {}),
+    }
+}
+
+fn exact_reference_pass() -> MatchPass {
+    MatchPass {
+        name: String::from("ExactReference"),
+        comparison_pairs: vec![
+            build_comparison_pair(0, true),
+            build_comparison_pair(1, false),
+        ],
+        ..MatchPass::default()
+    }
+}
+
+fn amount_and_date_pass() -> MatchPass {
+    MatchPass {
+        name: String::from("AmountAndDate"),
+        comparison_pairs: vec![
+            build_comparison_pair(1, true),
+            build_comparison_pair(2, true),
+        ],
+        ..MatchPass::default()
+    }
+}
+
+fn match_passes_options(match_passes: Vec<MatchPass>) -> ReconciliationOptions {
+    ReconciliationOptions {
+        match_passes,
+        ..ReconciliationOptions::default()
+    }
+}
+
+fn find_row_details(
+    rows_reconciliation_details: &Vec<RowReconciliationDetails>,
+    row_number: u64,
+) -> RowReconciliationDetails {
+    rows_reconciliation_details
+        .iter()
+        .find(|row_details| row_details.row_number == row_number)
+        .unwrap()
+        .clone()
+}
+
+fn build_comparison_pair(column_index: usize, is_row_identifier: bool) -> ComparisonPair {
+    ComparisonPair {
+        primary_file_column_index: column_index,
+        comparison_file_column_index: column_index,
+        is_row_identifier,
+    }
+}
+
+fn build_chunk_row(row_number: u64, raw_data: &str) -> FileUploadChunkRow {
+    FileUploadChunkRow {
+        row_number,
+        raw_data: raw_data.to_string(),
+        parsed_columns_from_row: raw_data.split(',').map(|s| s.to_string()).collect(),
+        recon_result: ReconStatus::Pending,
+        recon_result_reasons: vec![],
+    }
+}
+
+fn build_file_chunk(chunk_rows: Vec<FileUploadChunkRow>) -> FileUploadChunk {
+    FileUploadChunk {
+        id: String::from("file-1234"),
+        upload_request_id: String::from("file-1234"),
+        chunk_sequence_number: 1,
+        chunk_source: FileUploadChunkSource::ComparisonFileChunk,
+        chunk_rows,
+        date_created: chrono::Utc::now().timestamp(),
+        date_modified: chrono::Utc::now().timestamp(),
+        comparison_pairs: vec![],
+        column_headers: vec![
+            String::from("Reference"),
+            String::from("Amount"),
+            String::from("Date"),
+            String::from("Name"),
+        ],
+        recon_config: ReconciliationConfigs {
+            should_check_for_duplicate_records_in_comparison_file: false,
+            should_reconciliation_be_case_sensitive: true,
+            should_ignore_white_space: true,
+            should_do_reverse_reconciliation: false,
+        },
+        primary_file_chunks_queue: build_file_chunks_queue("primary"),
+        comparison_file_chunks_queue: build_file_chunks_queue("comparison"),
+        result_chunks_queue: build_file_chunks_queue("result"),
+        is_last_chunk: false,
+    }
+}
+
+fn build_file_chunks_queue(prefix: &str) -> FileChunkQueue {
+    FileChunkQueue {
+        topic_id: format!("{}-file-chunks-queue-1", prefix),
+        last_acknowledged_id: None,
+    }
+}
@@ -0,0 +1,121 @@
+use rayon::prelude::*;
+use std::{collections::HashMap, sync::Arc};
+
+use crate::internal::{
+    models::entities::{
+        reconciliation_options::ReconciliationOptions,
+        row_reconciliation_details::RowReconciliationDetails,
+    },
+    shared_reconciler_rust_libraries::models::entities::{
+        app_errors::{AppError, AppErrorKind},
+        file_upload_chunk::{FileUploadChunk, FileUploadChunkRow},
+    },
+};
+
+//reconciles the rows of the primary file chunk on the blocking thread pool, so that a large
+//chunk does not hold up the other requests on this actix worker. the rows and their details
+//are moved there and back, and only the rest of the chunk is copied
+pub async fn reconcile_rows_on_blocking_pool<F>(
+    primary_file_chunk: &mut FileUploadChunk,
+    comparison_file_chunk: &Arc<FileUploadChunk>,
+    reconciliation_options: &ReconciliationOptions,
+    rows_reconciliation_details: &mut Vec<RowReconciliationDetails>,
+    reconcile_rows: F,
+) -> Result<(), AppError>
+where
+    F: FnOnce(
+            &mut FileUploadChunk,
+            &FileUploadChunk,
+            &ReconciliationOptions,
+            &mut Vec<RowReconciliationDetails>,
+        ) -> Result<(), AppError>
+        + Send
+        + 'static,
+{
+    let primary_chunk_rows = std::mem::take(&mut primary_file_chunk.chunk_rows);
+    let mut owned_primary_file_chunk = primary_file_chunk.clone();
+    owned_primary_file_chunk.chunk_rows = primary_chunk_rows;
+
+    let shared_comparison_file_chunk = Arc::clone(comparison_file_chunk);
+    let owned_reconciliation_options = reconciliation_options.clone();
+    let mut owned_rows_reconciliation_details = std::mem::take(rows_reconciliation_details);
+
+    let (reconciled_primary_file_chunk, reconciled_rows_reconciliation_details) =
+        tokio::task::spawn_blocking(move || {
+            reconcile_rows(
+                &mut owned_primary_file_chunk,
+                &shared_comparison_file_chunk,
+                &owned_reconciliation_options,
+                &mut owned_rows_reconciliation_details,
+            )?;
+            return Ok((owned_primary_file_chunk, owned_rows_reconciliation_details));
+        })
+        .await
+        .map_err(|e| {
+            AppError::new(
+                AppErrorKind::InternalError,
+                format!("row reconciliation did not complete: {}", e),
+            )
+        })??;
+
+    *primary_file_chunk = reconciled_primary_file_chunk;
+    *rows_reconciliation_details = reconciled_rows_reconciliation_details;
+
+    return Ok(());
+}
+
+//reconciles every row in parallel on the rayon thread pool, along with its position in the chunk.
+//every row gets the details it already has so that it can update them in place. rows without
+//details yet get new ones if they have something to report, which are added in row order
+//so the outcome does not depend on how the rows were scheduled
+pub fn reconcile_rows_in_parallel<F>(
+    chunk_rows: &mut Vec<FileUploadChunkRow>,
+    rows_reconciliation_details: &mut Vec<RowReconciliationDetails>,
+    reconcile_row: F,
+) where
+    F: Fn(usize, &mut FileUploadChunkRow, &mut RowReconciliationDetails) + Send + Sync,
+{
+    let mut details_by_row_number: HashMap<u64, &mut RowReconciliationDetails> =
+        rows_reconciliation_details
+            .iter_mut()
+            .map(|row_details| (row_details.row_number, row_details))
+            .collect();
+
+    let mut existing_rows_details: Vec<Option<&mut RowReconciliationDetails>> = chunk_rows
+        .iter()
+        .map(|chunk_row| details_by_row_number.remove(&chunk_row.row_number))
+        .collect();
+
+    let new_rows_details: Vec<Option<RowReconciliationDetails>> = chunk_rows
+        .par_iter_mut()
+        .enumerate()
+        .zip(existing_rows_details.par_iter_mut())
+        .map(|((chunk_row_index, chunk_row), existing_row_details)| {
+            match existing_row_details {
+                Some(row_reconciliation_details) => {
+                    reconcile_row(chunk_row_index, chunk_row, row_reconciliation_details);
+                    return None;
+                }
+                None => {
+                    let empty_row_details = RowReconciliationDetails {
+                        row_number: chunk_row.row_number,
+                        ..RowReconciliationDetails::default()
+                    };
+                    let mut row_reconciliation_details = empty_row_details.clone();
+
+                    reconcile_row(chunk_row_index, chunk_row, &mut row_reconciliation_details);
+
+                    //rows that are still pending with nothing to report get no details yet
+                    if row_reconciliation_details == empty_row_details {
+                        return None;
+                    }
+                    return Some(row_reconciliation_details);
+                }
+            }
+        })
+        .collect();
+
+    drop(existing_rows_details);
+
+    rows_reconciliation_details.extend(new_rows_details.into_iter().flatten());
+}
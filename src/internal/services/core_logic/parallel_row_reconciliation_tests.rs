@@ -0,0 +1,81 @@
+use crate::internal::{
+    models::entities::row_reconciliation_details::{ReconOutcome, RowReconciliationDetails},
+    shared_reconciler_rust_libraries::models::entities::file_upload_chunk::{
+        FileUploadChunkRow, ReconStatus,
+    },
+};
+
+use super::parallel_row_reconciliation::reconcile_rows_in_parallel;
+
+#[actix_web::test]
+async fn test_reconcile_rows_in_parallel_given_row_with_details_updates_them_in_place() {
+    //setup
+    let mut chunk_rows = vec![build_chunk_row(1), build_chunk_row(2)];
+    let mut rows_reconciliation_details = vec![RowReconciliationDetails {
+        row_number: 2,
+        match_pass_name: Some(String::from("exact")),
+        ..RowReconciliationDetails::default()
+    }];
+
+    //act
+    reconcile_rows_in_parallel(
+        &mut chunk_rows,
+        &mut rows_reconciliation_details,
+        |chunk_row_index, chunk_row, row_reconciliation_details| {
+            if chunk_row_index == 1 {
+                chunk_row.recon_result = ReconStatus::Failed;
+                row_reconciliation_details.recon_outcome = ReconOutcome::Failed;
+            }
+        },
+    );
+
+    //assert
+    assert_eq!(chunk_rows[1].recon_result, ReconStatus::Failed);
+    assert_eq!(
+        rows_reconciliation_details,
+        vec![RowReconciliationDetails {
+            row_number: 2,
+            recon_outcome: ReconOutcome::Failed,
+            match_pass_name: Some(String::from("exact")),
+            ..RowReconciliationDetails::default()
+        }]
+    );
+}
+
+#[actix_web::test]
+async fn test_reconcile_rows_in_parallel_given_rows_without_details_adds_new_ones_in_row_order() {
+    //setup
+    let mut chunk_rows: Vec<FileUploadChunkRow> = (1..=100).map(build_chunk_row).collect();
+    let mut rows_reconciliation_details = vec![];
+
+    //act
+    reconcile_rows_in_parallel(
+        &mut chunk_rows,
+        &mut rows_reconciliation_details,
+        |_, chunk_row, row_reconciliation_details| {
+            //rows with an odd row number have nothing to report
+            if chunk_row.row_number % 2 == 0 {
+                row_reconciliation_details.recon_outcome = ReconOutcome::Matched;
+            }
+        },
+    );
+
+    //assert
+    let row_numbers: Vec<u64> = rows_reconciliation_details
+        .iter()
+        .map(|row_details| row_details.row_number)
+        .collect();
+    let expected: Vec<u64> = (1..=50).map(|row_number| row_number * 2).collect();
+
+    assert_eq!(row_numbers, expected);
+}
+
+fn build_chunk_row(row_number: u64) -> FileUploadChunkRow {
+    FileUploadChunkRow {
+        row_number,
+        raw_data: String::from(""),
+        parsed_columns_from_row: vec![],
+        recon_result: ReconStatus::Pending,
+        recon_result_reasons: vec![],
+    }
+}
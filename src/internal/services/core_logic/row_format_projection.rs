@@ -0,0 +1,129 @@
+use serde_json::Value;
+
+use crate::internal::{
+    models::entities::{
+        recon_result_reason::{ReconResultReason, ReconResultReasonCode},
+        row_format::{FixedWidthColumn, RowFormat},
+        row_reconciliation_details::{RowReconciliationDetails, RowsReconciliationDetailsIndex},
+    },
+    shared_reconciler_rust_libraries::models::entities::file_upload_chunk::{
+        FileUploadChunk, ReconStatus,
+    },
+};
+
+//projects the raw data of every row in a file chunk into parsed_columns_from_row
+//so that the reconciliation algorithm can compare the rows column by column.
+//a row is projected once, and is marked as projected in its details
+pub fn project_file_chunk_rows(
+    file_chunk: &mut FileUploadChunk,
+    row_format: &RowFormat,
+    rows_reconciliation_details: &mut Vec<RowReconciliationDetails>,
+) {
+    //delimited rows are already split into columns by the uploader
+    if *row_format == RowFormat::Delimited {
+        return;
+    }
+
+    let mut rows_details_index = RowsReconciliationDetailsIndex::new(rows_reconciliation_details);
+
+    for chunk_row in file_chunk.chunk_rows.iter_mut() {
+        //a primary file chunk carries the details of its rows with it when it is requeued,
+        //so a row that was projected, or that could not be read, is not read again.
+        //columns a row came with otherwise were split on the delimiter by the uploader
+        let is_projected = rows_details_index
+            .get(chunk_row.row_number)
+            .map(|row_details| row_details.is_projected)
+            .unwrap_or(false);
+
+        if is_projected {
+            continue;
+        }
+
+        let projection_result = match row_format {
+            RowFormat::Delimited => continue,
+            RowFormat::FixedWidth { columns } => {
+                project_fixed_width_row(&chunk_row.raw_data, columns)
+            }
+            RowFormat::JsonLines { column_paths } => {
+                project_json_lines_row(&chunk_row.raw_data, column_paths)
+            }
+        };
+
+        let row_details = rows_details_index.find_or_insert(chunk_row.row_number);
+        row_details.is_projected = true;
+
+        match projection_result {
+            Ok(columns) => chunk_row.parsed_columns_from_row = columns,
+
+            Err(error) => {
+                //a row we cannot read has nothing to compare
+                chunk_row.parsed_columns_from_row = vec![];
+
+                let reason = format!(
+                    "RowNumber: [{}], unable to read the columns from the row: {}",
+                    chunk_row.row_number, error
+                );
+                chunk_row.recon_result = ReconStatus::Failed;
+                chunk_row.recon_result_reasons.push(reason);
+
+                row_details.recon_result_reasons.push(ReconResultReason {
+                    reason_code: ReconResultReasonCode::UnreadableRow,
+                    ..ReconResultReason::default()
+                });
+            }
+        }
+    }
+}
+
+//splits the raw data of a fixed width row into columns
+pub fn project_fixed_width_row(
+    raw_data: &String,
+    columns: &Vec<FixedWidthColumn>,
+) -> Result<Vec<String>, String> {
+    //we slice by characters not bytes, so that multi-byte characters
+    //do not shift the columns that come after them
+    let characters: Vec<char> = raw_data.chars().collect();
+
+    columns
+        .iter()
+        .map(|column| {
+            //a row cut short would otherwise look like it has blank columns
+            let column_end = column.start + column.length;
+            if characters.len() < column_end {
+                return Err(format!(
+                    "the row is [{}] characters long but the column at [{}] ends at [{}]",
+                    characters.len(),
+                    column.start,
+                    column_end
+                ));
+            }
+
+            Ok(characters[column.start..column_end].iter().collect())
+        })
+        .collect()
+}
+
+//reads each column of a json lines row using its json pointer. an explicit null
+//is an empty column, but a value missing from the row is an error
+pub fn project_json_lines_row(
+    raw_data: &String,
+    column_paths: &Vec<String>,
+) -> Result<Vec<String>, String> {
+    let document: Value = serde_json::from_str(raw_data).map_err(|e| e.to_string())?;
+
+    column_paths
+        .iter()
+        .map(|path| match document.pointer(path) {
+            //strings are used as is, so they are not wrapped in quotes
+            Some(Value::String(value)) => Ok(value.clone()),
+
+            //an explicit null is an empty column
+            Some(Value::Null) => Ok(String::from("")),
+
+            Some(value) => Ok(value.to_string()),
+
+            //whereas a value that is not there at all means the row is not laid out as expected
+            None => Err(format!("the row has no value at [{}]", path)),
+        })
+        .collect()
+}
@@ -0,0 +1,359 @@
+use crate::internal::{
+    models::entities::{
+        recon_result_reason::ReconResultReasonCode,
+        row_format::{validate_row_format, FixedWidthColumn, RowFormat},
+        row_reconciliation_details::RowReconciliationDetails,
+    },
+    shared_reconciler_rust_libraries::models::entities::file_upload_chunk::{
+        FileUploadChunk, FileUploadChunkRow, ReconStatus,
+    },
+};
+
+use super::row_format_projection::{
+    project_file_chunk_rows, project_fixed_width_row, project_json_lines_row,
+};
+
+#[actix_web::test]
+async fn test_project_fixed_width_row_given_valid_columns_returns_column_values() {
+    //setup
+    let raw_data = String::from("0001Müller    100.50");
+    let columns = vec![
+        fixed_width_column(0, 4),
+        fixed_width_column(4, 10),
+        fixed_width_column(14, 6),
+    ];
+
+    //act
+    let actual = project_fixed_width_row(&raw_data, &columns);
+
+    //assert
+    let expected = vec!["0001", "Müller    ", "100.50"];
+    assert_eq!(actual.unwrap(), expected);
+}
+
+#[actix_web::test]
+async fn test_project_fixed_width_row_given_short_row_returns_error() {
+    //setup
+    let raw_data = String::from("0001Müller");
+    let columns = vec![fixed_width_column(0, 4), fixed_width_column(4, 10)];
+
+    //act
+    let actual = project_fixed_width_row(&raw_data, &columns);
+
+    //assert
+    assert_eq!(
+        actual.unwrap_err(),
+        "the row is [10] characters long but the column at [4] ends at [14]"
+    );
+}
+
+#[actix_web::test]
+async fn test_project_json_lines_row_given_valid_paths_returns_column_values() {
+    //setup
+    let raw_data = String::from(
+        r#"{"reference": "TXN-1", "payment": {"amount": 100.5, "settled": true}, "memo": null}"#,
+    );
+    let column_paths = vec![
+        String::from("/reference"),
+        String::from("/payment/amount"),
+        String::from("/payment/settled"),
+        String::from("/memo"),
+    ];
+
+    //act
+    let actual = project_json_lines_row(&raw_data, &column_paths);
+
+    //assert
+    let expected = vec!["TXN-1", "100.5", "true", ""];
+    assert_eq!(actual.unwrap(), expected);
+}
+
+#[actix_web::test]
+async fn test_project_json_lines_row_given_missing_path_returns_error() {
+    //setup
+    let raw_data = String::from(r#"{"reference": "TXN-1"}"#);
+    let column_paths = vec![String::from("/reference"), String::from("/payment/amount")];
+
+    //act
+    let actual = project_json_lines_row(&raw_data, &column_paths);
+
+    //assert
+    assert_eq!(
+        actual.unwrap_err(),
+        "the row has no value at [/payment/amount]"
+    );
+}
+
+#[actix_web::test]
+async fn test_project_json_lines_row_given_explicit_null_returns_empty_column() {
+    //setup
+    let raw_data = String::from(r#"{"reference": "TXN-1", "memo": null}"#);
+    let column_paths = vec![String::from("/reference"), String::from("/memo")];
+
+    //act
+    let actual = project_json_lines_row(&raw_data, &column_paths);
+
+    //assert
+    assert_eq!(actual.unwrap(), vec!["TXN-1", ""]);
+}
+
+#[actix_web::test]
+async fn test_project_json_lines_row_given_path_under_null_parent_returns_error() {
+    //setup
+    let raw_data = String::from(r#"{"reference": "TXN-1", "payment": null}"#);
+    let column_paths = vec![String::from("/reference"), String::from("/payment/amount")];
+
+    //act
+    let actual = project_json_lines_row(&raw_data, &column_paths);
+
+    //assert
+    assert_eq!(
+        actual.unwrap_err(),
+        "the row has no value at [/payment/amount]"
+    );
+}
+
+#[actix_web::test]
+async fn test_project_file_chunk_rows_given_short_fixed_width_row_marks_row_as_failed() {
+    //setup
+    let mut file_chunk = FileUploadChunk {
+        chunk_rows: vec![build_chunk_row(1, "0001")],
+        ..FileUploadChunk::default()
+    };
+    let row_format = RowFormat::FixedWidth {
+        columns: vec![fixed_width_column(0, 4), fixed_width_column(4, 10)],
+    };
+
+    let mut rows_reconciliation_details = vec![];
+
+    //act
+    project_file_chunk_rows(
+        &mut file_chunk,
+        &row_format,
+        &mut rows_reconciliation_details,
+    );
+
+    //assert
+    assert_eq!(file_chunk.chunk_rows[0].recon_result, ReconStatus::Failed);
+    assert_eq!(
+        file_chunk.chunk_rows[0].parsed_columns_from_row,
+        Vec::<String>::new()
+    );
+    assert_eq!(
+        rows_reconciliation_details[0].recon_result_reasons[0].reason_code,
+        ReconResultReasonCode::UnreadableRow
+    );
+}
+
+#[actix_web::test]
+async fn test_project_file_chunk_rows_given_already_projected_row_does_not_project_it_again() {
+    //setup
+    let mut file_chunk = FileUploadChunk::default();
+    let mut chunk_row = build_chunk_row(1, "0001Müller    ");
+    chunk_row.parsed_columns_from_row = vec![String::from("0001"), String::from("Müller")];
+    file_chunk.chunk_rows = vec![chunk_row];
+    let row_format = RowFormat::FixedWidth {
+        columns: vec![fixed_width_column(0, 4), fixed_width_column(4, 10)],
+    };
+
+    let mut rows_reconciliation_details = vec![RowReconciliationDetails {
+        row_number: 1,
+        is_projected: true,
+        ..RowReconciliationDetails::default()
+    }];
+
+    //act
+    project_file_chunk_rows(
+        &mut file_chunk,
+        &row_format,
+        &mut rows_reconciliation_details,
+    );
+
+    //assert
+    assert_eq!(
+        file_chunk.chunk_rows[0].parsed_columns_from_row,
+        vec!["0001", "Müller"]
+    );
+}
+
+#[actix_web::test]
+async fn test_project_file_chunk_rows_given_rows_split_on_the_delimiter_still_projects_them() {
+    //setup
+    let mut file_chunk = FileUploadChunk::default();
+    let mut fixed_width_row = build_chunk_row(1, "0001Müller,   100.50");
+    fixed_width_row.parsed_columns_from_row =
+        vec![String::from("0001Müller"), String::from("   100.50")];
+    file_chunk.chunk_rows = vec![fixed_width_row];
+    let row_format = RowFormat::FixedWidth {
+        columns: vec![fixed_width_column(0, 4), fixed_width_column(4, 6)],
+    };
+
+    let mut rows_reconciliation_details = vec![];
+
+    //act
+    project_file_chunk_rows(
+        &mut file_chunk,
+        &row_format,
+        &mut rows_reconciliation_details,
+    );
+
+    //assert
+    assert_eq!(
+        file_chunk.chunk_rows[0].parsed_columns_from_row,
+        vec!["0001", "Müller"]
+    );
+    assert_eq!(
+        rows_reconciliation_details,
+        vec![RowReconciliationDetails {
+            row_number: 1,
+            is_projected: true,
+            ..RowReconciliationDetails::default()
+        }]
+    );
+}
+
+#[actix_web::test]
+async fn test_project_file_chunk_rows_given_json_rows_split_on_the_delimiter_still_projects_them() {
+    //setup
+    let mut file_chunk = FileUploadChunk::default();
+    let mut json_lines_row = build_chunk_row(1, r#"{"reference": "TXN-1", "amount": 100.5}"#);
+    json_lines_row.parsed_columns_from_row = vec![
+        String::from(r#"{"reference": "TXN-1""#),
+        String::from(r#" "amount": 100.5}"#),
+    ];
+    file_chunk.chunk_rows = vec![json_lines_row];
+    let row_format = RowFormat::JsonLines {
+        column_paths: vec![String::from("/amount"), String::from("/reference")],
+    };
+
+    //act
+    project_file_chunk_rows(&mut file_chunk, &row_format, &mut vec![]);
+
+    //assert
+    assert_eq!(
+        file_chunk.chunk_rows[0].parsed_columns_from_row,
+        vec!["100.5", "TXN-1"]
+    );
+}
+
+#[actix_web::test]
+async fn test_project_file_chunk_rows_given_invalid_json_row_marks_row_as_failed_once() {
+    //setup
+    let mut file_chunk = FileUploadChunk {
+        chunk_rows: vec![build_chunk_row(1, "not json")],
+        ..FileUploadChunk::default()
+    };
+    let row_format = RowFormat::JsonLines {
+        column_paths: vec![String::from("/reference")],
+    };
+
+    let mut rows_reconciliation_details = vec![];
+
+    //act
+    project_file_chunk_rows(
+        &mut file_chunk,
+        &row_format,
+        &mut rows_reconciliation_details,
+    );
+    project_file_chunk_rows(
+        &mut file_chunk,
+        &row_format,
+        &mut rows_reconciliation_details,
+    );
+
+    //assert
+    assert_eq!(file_chunk.chunk_rows[0].recon_result, ReconStatus::Failed);
+    assert_eq!(file_chunk.chunk_rows[0].recon_result_reasons.len(), 1);
+    assert_eq!(
+        rows_reconciliation_details[0].recon_result_reasons[0].reason_code,
+        ReconResultReasonCode::UnreadableRow
+    );
+}
+
+#[actix_web::test]
+async fn test_project_file_chunk_rows_given_json_rows_fails_missing_values_but_not_nulls() {
+    //setup
+    let mut file_chunk = FileUploadChunk {
+        chunk_rows: vec![
+            build_chunk_row(1, r#"{"reference": "TXN-1", "memo": null}"#),
+            build_chunk_row(2, r#"{"reference": "TXN-2"}"#),
+        ],
+        ..FileUploadChunk::default()
+    };
+    let row_format = RowFormat::JsonLines {
+        column_paths: vec![String::from("/reference"), String::from("/memo")],
+    };
+
+    let mut rows_reconciliation_details = vec![];
+
+    //act
+    project_file_chunk_rows(
+        &mut file_chunk,
+        &row_format,
+        &mut rows_reconciliation_details,
+    );
+
+    //assert
+    assert_eq!(file_chunk.chunk_rows[0].recon_result, ReconStatus::Pending);
+    assert_eq!(
+        file_chunk.chunk_rows[0].parsed_columns_from_row,
+        vec!["TXN-1", ""]
+    );
+
+    assert_eq!(file_chunk.chunk_rows[1].recon_result, ReconStatus::Failed);
+    assert_eq!(rows_reconciliation_details.len(), 2);
+    assert!(rows_reconciliation_details[0]
+        .recon_result_reasons
+        .is_empty());
+    assert_eq!(rows_reconciliation_details[1].row_number, 2);
+    assert_eq!(
+        rows_reconciliation_details[1].recon_result_reasons[0].reason_code,
+        ReconResultReasonCode::UnreadableRow
+    );
+}
+
+#[actix_web::test]
+async fn test_project_file_chunk_rows_given_delimited_format_leaves_parsed_columns_untouched() {
+    //setup
+    let mut file_chunk = FileUploadChunk::default();
+    let mut chunk_row = build_chunk_row(1, "142425, test, user");
+    chunk_row.parsed_columns_from_row = vec![String::from("142425")];
+    file_chunk.chunk_rows = vec![chunk_row];
+
+    //act
+    project_file_chunk_rows(&mut file_chunk, &RowFormat::Delimited, &mut vec![]);
+
+    //assert
+    assert_eq!(
+        file_chunk.chunk_rows[0].parsed_columns_from_row,
+        vec!["142425"]
+    );
+}
+
+#[actix_web::test]
+async fn test_validate_row_format_given_invalid_json_pointer_returns_error() {
+    //setup
+    let row_format = RowFormat::JsonLines {
+        column_paths: vec![String::from("reference")],
+    };
+
+    //act
+    let actual = validate_row_format(&row_format);
+
+    //assert
+    assert_eq!(actual.is_err(), true);
+}
+
+fn build_chunk_row(row_number: u64, raw_line_data: &str) -> FileUploadChunkRow {
+    FileUploadChunkRow {
+        row_number,
+        raw_data: raw_line_data.to_string(),
+        parsed_columns_from_row: vec![],
+        recon_result: ReconStatus::Pending,
+        recon_result_reasons: vec![],
+    }
+}
+
+fn fixed_width_column(start: usize, length: usize) -> FixedWidthColumn {
+    FixedWidthColumn { start, length }
+}
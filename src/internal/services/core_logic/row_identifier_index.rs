@@ -0,0 +1,154 @@
+use rayon::prelude::*;
+use std::collections::HashMap;
+
+use crate::internal::{
+    services::core_logic::column_value_preparation::ColumnValuePreparer,
+    shared_reconciler_rust_libraries::models::entities::{
+        file_upload_chunk::FileUploadChunk,
+        recon_tasks_models::{ComparisonPair, ReconciliationConfigs},
+    },
+};
+
+//the identifiers of the rows of a primary file chunk and of a comparison file chunk,
+//transformed, normalized and sanitized once for the pair of chunks. the comparison rows
+//are grouped by their identifiers, so the candidates of a primary file row are looked up
+//instead of preparing and comparing its identifiers with those of every comparison row
+pub struct RowIdentifierIndex {
+    has_row_identifiers: bool,
+
+    //the identifiers of each primary file row, by its position in the chunk.
+    //a row that cannot be identified has none
+    primary_file_row_identifiers: Vec<Option<Vec<String>>>,
+
+    //the positions of the comparison file rows with each set of identifiers, in row order
+    comparison_file_row_indexes_by_identifiers: HashMap<Vec<String>, Vec<usize>>,
+}
+
+impl RowIdentifierIndex {
+    pub fn new(
+        primary_file_chunk: &FileUploadChunk,
+        comparison_file_chunk: &FileUploadChunk,
+        column_value_preparer: &ColumnValuePreparer,
+    ) -> RowIdentifierIndex {
+        let row_id_comparison_pairs = primary_file_chunk.get_row_identifier_comparison_pairs();
+        let recon_configs = &primary_file_chunk.recon_config;
+
+        let primary_file_row_identifiers: Vec<Option<Vec<String>>> = primary_file_chunk
+            .chunk_rows
+            .par_iter()
+            .map(|primary_chunk_row| {
+                get_row_identifiers(
+                    &primary_chunk_row.parsed_columns_from_row,
+                    &row_id_comparison_pairs,
+                    recon_configs,
+                    |pair| pair.primary_file_column_index,
+                    |pair, column_value| {
+                        Some(
+                            column_value_preparer
+                                .prepare_primary_file_column_value(pair, column_value),
+                        )
+                    },
+                )
+            })
+            .collect();
+
+        //identifiers without a mapping cannot identify the comparison row
+        let comparison_file_row_identifiers: Vec<Option<Vec<String>>> = comparison_file_chunk
+            .chunk_rows
+            .par_iter()
+            .map(|comparison_chunk_row| {
+                get_row_identifiers(
+                    &comparison_chunk_row.parsed_columns_from_row,
+                    &row_id_comparison_pairs,
+                    recon_configs,
+                    |pair| pair.comparison_file_column_index,
+                    |pair, column_value| {
+                        column_value_preparer
+                            .prepare_comparison_file_column_value(pair, column_value)
+                    },
+                )
+            })
+            .collect();
+
+        let mut comparison_file_row_indexes_by_identifiers: HashMap<Vec<String>, Vec<usize>> =
+            HashMap::new();
+
+        for (comparison_index, row_identifiers) in
+            comparison_file_row_identifiers.into_iter().enumerate()
+        {
+            if let Some(row_identifiers) = row_identifiers {
+                comparison_file_row_indexes_by_identifiers
+                    .entry(row_identifiers)
+                    .or_default()
+                    .push(comparison_index);
+            }
+        }
+
+        return RowIdentifierIndex {
+            has_row_identifiers: !row_id_comparison_pairs.is_empty(),
+            primary_file_row_identifiers,
+            comparison_file_row_indexes_by_identifiers,
+        };
+    }
+
+    //without row identifiers every comparison row is a candidate for every primary file row
+    pub fn has_row_identifiers(&self) -> bool {
+        return self.has_row_identifiers;
+    }
+
+    //the positions, in row order, of the comparison file rows
+    //with the same identifiers as the primary file row at this position
+    pub fn get_comparison_file_row_indexes(&self, primary_file_row_index: usize) -> &[usize] {
+        return self
+            .primary_file_row_identifiers
+            .get(primary_file_row_index)
+            .and_then(|row_identifiers| row_identifiers.as_ref())
+            .and_then(|row_identifiers| {
+                self.comparison_file_row_indexes_by_identifiers
+                    .get(row_identifiers)
+            })
+            .map(|comparison_indexes| comparison_indexes.as_slice())
+            .unwrap_or(&[]);
+    }
+}
+
+//prepares the identifier columns of a row, returning None if one of them is missing
+//or cannot be prepared, since the row cannot be identified then
+pub fn get_row_identifiers(
+    row_parts: &Vec<String>,
+    row_id_comparison_pairs: &Vec<ComparisonPair>,
+    recon_configs: &ReconciliationConfigs,
+    get_column_index: impl Fn(&ComparisonPair) -> usize,
+    prepare_column_value: impl Fn(&ComparisonPair, &String) -> Option<String>,
+) -> Option<Vec<String>> {
+    let mut row_identifiers = Vec::with_capacity(row_id_comparison_pairs.len());
+
+    for pair in row_id_comparison_pairs {
+        let column_value = row_parts.get(get_column_index(pair))?;
+        let prepared_column_value = prepare_column_value(pair, column_value)?;
+        row_identifiers.push(sanitize_row_identifier(
+            &prepared_column_value,
+            recon_configs,
+        ));
+    }
+
+    return Some(row_identifiers);
+}
+
+//trims and lower cases an identifier as the recon configs say, so that identifiers
+//are the same exactly when are_column_values_the_same would say they are
+fn sanitize_row_identifier(
+    prepared_column_value: &String,
+    recon_configs: &ReconciliationConfigs,
+) -> String {
+    let sanitized_column_value = match recon_configs.should_ignore_white_space {
+        true => prepared_column_value.trim(),
+        false => prepared_column_value.as_str(),
+    };
+
+    if recon_configs.should_reconciliation_be_case_sensitive {
+        return sanitized_column_value.to_string();
+    }
+
+    return sanitized_column_value.to_ascii_lowercase();
+}
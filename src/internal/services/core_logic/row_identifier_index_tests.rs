@@ -0,0 +1,161 @@
+use crate::internal::{
+    models::entities::reconciliation_options::ReconciliationOptions,
+    shared_reconciler_rust_libraries::models::entities::{
+        file_chunk_queue::FileChunkQueue,
+        file_upload_chunk::{
+            FileUploadChunk, FileUploadChunkRow, FileUploadChunkSource, ReconStatus,
+        },
+        recon_tasks_models::{ComparisonPair, ReconciliationConfigs},
+    },
+};
+
+use super::{
+    column_value_preparation::ColumnValuePreparer, row_identifier_index::RowIdentifierIndex,
+};
+
+#[actix_web::test]
+async fn test_get_comparison_file_row_indexes_given_same_row_identifiers_returns_rows_in_order() {
+    //setup
+    let primary_file_chunk = build_file_chunk(
+        vec![build_chunk_row(1, "column_1,column_2")],
+        vec![build_comparison_pair(0), build_comparison_pair(1)],
+    );
+    let comparison_file_chunk = build_file_chunk(
+        vec![
+            build_chunk_row(1, "column_1,column_2"),
+            build_chunk_row(2, "column 1,column_2"),
+            build_chunk_row(3, " column_1 ,column_2"),
+        ],
+        vec![],
+    );
+
+    //act
+    let sut = setup(&primary_file_chunk, &comparison_file_chunk);
+
+    //assert
+    assert_eq!(sut.has_row_identifiers(), true);
+    assert_eq!(sut.get_comparison_file_row_indexes(0), &[0, 2]);
+}
+
+#[actix_web::test]
+async fn test_get_comparison_file_row_indexes_given_different_row_identifiers_returns_no_rows() {
+    //setup
+    let primary_file_chunk = build_file_chunk(
+        vec![build_chunk_row(1, "column 1,column_2")],
+        vec![build_comparison_pair(0), build_comparison_pair(1)],
+    );
+    let comparison_file_chunk =
+        build_file_chunk(vec![build_chunk_row(1, "column_1,column_2")], vec![]);
+
+    //act
+    let sut = setup(&primary_file_chunk, &comparison_file_chunk);
+
+    //assert
+    assert!(sut.get_comparison_file_row_indexes(0).is_empty());
+}
+
+#[actix_web::test]
+async fn test_get_comparison_file_row_indexes_given_missing_identifier_columns_returns_no_rows() {
+    //setup
+    let primary_file_chunk = build_file_chunk(
+        vec![build_chunk_row(1, "column_1")],
+        vec![build_comparison_pair(0), build_comparison_pair(1)],
+    );
+    let comparison_file_chunk = build_file_chunk(vec![build_chunk_row(1, "column_1")], vec![]);
+
+    //act
+    let sut = setup(&primary_file_chunk, &comparison_file_chunk);
+
+    //assert
+    assert!(sut.get_comparison_file_row_indexes(0).is_empty());
+}
+
+#[actix_web::test]
+async fn test_get_comparison_file_row_indexes_given_no_row_identifiers_returns_every_row() {
+    //setup
+    let primary_file_chunk = build_file_chunk(vec![build_chunk_row(1, "column_1")], vec![]);
+    let comparison_file_chunk = build_file_chunk(
+        vec![
+            build_chunk_row(1, "column_1"),
+            build_chunk_row(2, "column_2"),
+        ],
+        vec![],
+    );
+
+    //act
+    let sut = setup(&primary_file_chunk, &comparison_file_chunk);
+
+    //assert
+    assert_eq!(sut.has_row_identifiers(), false);
+    assert_eq!(sut.get_comparison_file_row_indexes(0), &[0, 1]);
+}
+
+fn setup(
+    primary_file_chunk: &FileUploadChunk,
+    comparison_file_chunk: &FileUploadChunk,
+) -> RowIdentifierIndex {
+    let column_value_preparer = ColumnValuePreparer::new(
+        &primary_file_chunk.comparison_pairs,
+        &ReconciliationOptions::default(),
+    )
+    .unwrap();
+
+    return RowIdentifierIndex::new(
+        primary_file_chunk,
+        comparison_file_chunk,
+        &column_value_preparer,
+    );
+}
+
+fn build_file_chunk(
+    chunk_rows: Vec<FileUploadChunkRow>,
+    comparison_pairs: Vec<ComparisonPair>,
+) -> FileUploadChunk {
+    FileUploadChunk {
+        id: String::from("file-1234"),
+        upload_request_id: String::from("file-1234"),
+        chunk_sequence_number: 1,
+        chunk_source: FileUploadChunkSource::ComparisonFileChunk,
+        chunk_rows,
+        date_created: chrono::Utc::now().timestamp(),
+        date_modified: chrono::Utc::now().timestamp(),
+        comparison_pairs,
+        column_headers: vec![],
+        recon_config: ReconciliationConfigs {
+            should_check_for_duplicate_records_in_comparison_file: false,
+            should_reconciliation_be_case_sensitive: true,
+            should_ignore_white_space: true,
+            should_do_reverse_reconciliation: false,
+        },
+        primary_file_chunks_queue: build_file_chunks_queue("primary"),
+        comparison_file_chunks_queue: build_file_chunks_queue("comparison"),
+        result_chunks_queue: build_file_chunks_queue("result"),
+        is_last_chunk: false,
+    }
+}
+
+fn build_file_chunks_queue(prefix: &str) -> FileChunkQueue {
+    FileChunkQueue {
+        topic_id: format!("{}-{}", prefix, String::from("file-chunks-queue-1")),
+        last_acknowledged_id: Option::None,
+    }
+}
+
+fn build_chunk_row(row_number: u64, raw_line_data: &str) -> FileUploadChunkRow {
+    let parsed_columns: Vec<String> = raw_line_data.split(',').map(|s| s.to_string()).collect();
+    FileUploadChunkRow {
+        row_number,
+        raw_data: raw_line_data.to_string(),
+        parsed_columns_from_row: parsed_columns,
+        recon_result: ReconStatus::Pending,
+        recon_result_reasons: vec![],
+    }
+}
+
+fn build_comparison_pair(column_index: usize) -> ComparisonPair {
+    ComparisonPair {
+        primary_file_column_index: column_index,
+        comparison_file_column_index: column_index,
+        is_row_identifier: true,
+    }
+}
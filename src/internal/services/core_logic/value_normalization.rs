@@ -0,0 +1,43 @@
+use caseless::default_case_fold_str;
+use unicode_normalization::UnicodeNormalization;
+
+use crate::internal::models::entities::value_normalization::NormalizationStep;
+
+//applies the normalization steps to a column value in the order they are listed
+pub fn normalize_column_value(
+    column_value: &String,
+    normalization_steps: &Vec<NormalizationStep>,
+) -> String {
+    let mut normalized_column_value = column_value.clone();
+
+    for normalization_step in normalization_steps {
+        normalized_column_value = match normalization_step {
+            NormalizationStep::UnicodeCaseFolding => {
+                default_case_fold_str(&normalized_column_value)
+            }
+            NormalizationStep::Nfkc => normalized_column_value.nfkc().collect(),
+            NormalizationStep::CollapseWhiteSpace => normalized_column_value
+                .split_whitespace()
+                .collect::<Vec<&str>>()
+                .join(" "),
+            NormalizationStep::StripPunctuation => normalized_column_value
+                .chars()
+                .filter(|c| c.is_alphanumeric() || c.is_whitespace())
+                .collect(),
+            NormalizationStep::RemoveLeadingZeros => remove_leading_zeros(&normalized_column_value),
+        };
+    }
+
+    return normalized_column_value;
+}
+
+fn remove_leading_zeros(column_value: &String) -> String {
+    let without_leading_zeros = column_value.trim_start_matches('0');
+
+    //a value of only zeros is still a zero
+    if without_leading_zeros.is_empty() && !column_value.is_empty() {
+        return String::from("0");
+    }
+
+    return without_leading_zeros.to_string();
+}
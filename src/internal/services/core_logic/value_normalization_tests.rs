@@ -0,0 +1,89 @@
+use crate::internal::models::entities::value_normalization::NormalizationStep;
+
+use super::value_normalization::normalize_column_value;
+
+#[actix_web::test]
+async fn test_normalize_column_value_given_unicode_case_folding_folds_non_ascii_letters() {
+    //setup
+    let primary_file_column_value = String::from("Müller Straße");
+    let comparison_file_column_value = String::from("MÜLLER STRASSE");
+    let normalization_steps = vec![NormalizationStep::UnicodeCaseFolding];
+
+    //act
+    let actual_primary = normalize_column_value(&primary_file_column_value, &normalization_steps);
+    let actual_comparison =
+        normalize_column_value(&comparison_file_column_value, &normalization_steps);
+
+    //assert
+    assert_eq!(actual_primary, actual_comparison);
+}
+
+#[actix_web::test]
+async fn test_normalize_column_value_given_nfkc_converts_full_width_digits() {
+    //setup
+    let column_value = String::from("１２３");
+    let normalization_steps = vec![NormalizationStep::Nfkc];
+
+    //act
+    let actual = normalize_column_value(&column_value, &normalization_steps);
+
+    //assert
+    assert_eq!(actual, String::from("123"));
+}
+
+#[actix_web::test]
+async fn test_normalize_column_value_given_collapse_white_space_collapses_non_breaking_spaces() {
+    //setup
+    let column_value = String::from(" ACME\u{00A0}\u{00A0}Ltd   Kampala ");
+    let normalization_steps = vec![NormalizationStep::CollapseWhiteSpace];
+
+    //act
+    let actual = normalize_column_value(&column_value, &normalization_steps);
+
+    //assert
+    assert_eq!(actual, String::from("ACME Ltd Kampala"));
+}
+
+#[actix_web::test]
+async fn test_normalize_column_value_given_strip_punctuation_removes_punctuation() {
+    //setup
+    let column_value = String::from("INV-2022/001.");
+    let normalization_steps = vec![NormalizationStep::StripPunctuation];
+
+    //act
+    let actual = normalize_column_value(&column_value, &normalization_steps);
+
+    //assert
+    assert_eq!(actual, String::from("INV2022001"));
+}
+
+#[actix_web::test]
+async fn test_normalize_column_value_given_remove_leading_zeros_keeps_a_single_zero() {
+    //setup
+    let normalization_steps = vec![NormalizationStep::RemoveLeadingZeros];
+
+    //act
+    let actual_number = normalize_column_value(&String::from("000123"), &normalization_steps);
+    let actual_zero = normalize_column_value(&String::from("0000"), &normalization_steps);
+
+    //assert
+    assert_eq!(actual_number, String::from("123"));
+    assert_eq!(actual_zero, String::from("0"));
+}
+
+#[actix_web::test]
+async fn test_normalize_column_value_applies_steps_in_the_listed_order() {
+    //setup
+    let column_value = String::from(" ００42 ");
+    let normalization_steps = vec![
+        NormalizationStep::Nfkc,
+        NormalizationStep::CollapseWhiteSpace,
+        NormalizationStep::RemoveLeadingZeros,
+    ];
+
+    //act
+    let actual = normalize_column_value(&column_value, &normalization_steps);
+
+    //assert
+    assert_eq!(actual, String::from("42"));
+}
@@ -3,22 +3,58 @@ use crate::internal::{
         file_chunk_reconciliation_service::FileChunkReconciliationServiceInterface,
         file_reconciliation_algorithm::FileReconciliationAlgorithmInterface,
         pubsub_repository::PubSubRepositoryInterface,
+        upload_progress_repository::UploadProgressRepositoryInterface,
     },
-    models::view_models::{
-        requests::reconcile_file_chunk_request::ReconcileFileChunkRequest,
-        responses::reconcile_file_chunk_response::ReconcileFileChunkResponse,
+    metrics::reconciliation_metrics::{
+        observe_reconciliation_algorithm_duration, record_file_chunk, record_reconciled_rows,
+        FILE_CHUNK_COMPLETED, FILE_CHUNK_PROCESSED, FILE_CHUNK_REQUEUED, GENERIC_ALGORITHM,
+        MATCH_RULE_ALGORITHM, MULTI_PASS_ALGORITHM,
     },
-    shared_reconciler_rust_libraries::models::entities::{
-        app_errors::{AppError, AppErrorKind},
-        file_upload_chunk::FileUploadChunk,
+    models::{
+        entities::{
+            chunk_content_type::ChunkContentType,
+            chunk_payload_limits::ChunkPayloadLimits,
+            currency_conversion::FxRateTable,
+            file_chunk_claim_check::FileChunkClaimCheck,
+            reconciliation_options::ReconciliationOptions,
+            upload_progress::{
+                count_rows_by_recon_status, get_last_primary_chunk_sequence_number, UploadProgress,
+            },
+            value_mapping::ValueMappingTable,
+        },
+        view_models::{
+            requests::reconcile_file_chunk_request::ReconcileFileChunkRequest,
+            responses::{
+                reconcile_file_chunk_response::ReconcileFileChunkResponse,
+                reconciled_file_chunk::ReconciledFileChunk,
+                upload_reconciliation_completed_event::UploadReconciliationCompletedEvent,
+            },
+        },
     },
+    services::core_logic::{
+        chunk_serialization::deserialize_chunk_payload,
+        column_derivation::{derive_file_chunk_columns, remove_derived_columns},
+        comparison_pair_resolution::{
+            resolve_header_name_comparison_pair_options, resolve_header_name_comparison_pairs,
+        },
+        row_format_projection::project_file_chunk_rows,
+    },
+    shared_reconciler_rust_libraries::models::entities::app_errors::{AppError, AppErrorKind},
 };
 use async_trait::async_trait;
+use std::{collections::HashMap, sync::Arc, time::Instant};
+use tracing::{field, Instrument, Span};
 use validator::Validate;
 
 pub struct FileChunkReconciliationService {
     pub pubsub_repo: Box<dyn PubSubRepositoryInterface>,
     pub file_reconciliation_algorithm: Box<dyn FileReconciliationAlgorithmInterface>,
+    pub match_rule_reconciliation_algorithm: Box<dyn FileReconciliationAlgorithmInterface>,
+    pub multi_pass_reconciliation_algorithm: Box<dyn FileReconciliationAlgorithmInterface>,
+    pub upload_progress_repo: Box<dyn UploadProgressRepositoryInterface>,
+
+    //how large the body of a claim checked file chunk may get once decompressed
+    pub chunk_payload_limits: ChunkPayloadLimits,
 }
 
 #[async_trait]
@@ -32,7 +68,7 @@ impl FileChunkReconciliationServiceInterface for FileChunkReconciliationService
     */
     async fn reconcile_file_chunk(
         &self,
-        reconcile_primary_file_chunk_request: &ReconcileFileChunkRequest,
+        reconcile_primary_file_chunk_request: ReconcileFileChunkRequest,
     ) -> Result<ReconcileFileChunkResponse, AppError> {
         //validate request
         match reconcile_primary_file_chunk_request.validate() {
@@ -45,23 +81,174 @@ impl FileChunkReconciliationServiceInterface for FileChunkReconciliationService
             }
         }
 
-        //get a mutable handle to the primary file chunk
-        let mut primary_file_chunk = reconcile_primary_file_chunk_request
-            .primary_file_chunk
-            .clone();
+        //the request is taken apart so the primary file chunk can be reconciled in place
+        //and the row details gathered from the previous comparison file chunks carried on
+        let ReconcileFileChunkRequest {
+            mut primary_file_chunk,
+            reconciliation_options,
+            mut rows_reconciliation_details,
+        } = reconcile_primary_file_chunk_request;
+
+        //the resolved comparison pairs are not kept on the chunk
+        //since the next comparison chunk may have its columns in a different order
+        let unresolved_comparison_pairs = primary_file_chunk.comparison_pairs.clone();
+
+        //the chunk is only known once its request has been read,
+        //so it is recorded on the span the request is being handled in
+        let request_span = Span::current();
+        request_span.record(
+            "upload_request_id",
+            field::display(&primary_file_chunk.upload_request_id),
+        );
+        request_span.record(
+            "chunk_sequence_number",
+            primary_file_chunk.chunk_sequence_number,
+        );
 
         //go get the next chunk from the comparison file
-        let comparison_file_chunk = self
+        let mut comparison_file_chunk = self
             .pubsub_repo
             .get_next_comparison_file_upload_chunk(&primary_file_chunk.comparison_file_chunks_queue)
+            .instrument(tracing::info_span!(
+                "read_comparison_file_chunk",
+                comparison_file_chunks_queue =
+                    %primary_file_chunk.comparison_file_chunks_queue.topic_id,
+            ))
+            .await?;
+
+        request_span.record(
+            "comparison_chunk_sequence_number",
+            comparison_file_chunk.chunk_sequence_number,
+        );
+
+        //we read the columns out of the raw row data of both chunks
+        //so that the rows can be compared column by column
+        project_file_chunk_rows(
+            &mut primary_file_chunk,
+            &reconciliation_options.primary_file_row_format,
+            &mut rows_reconciliation_details,
+        );
+
+        //only the details of primary file rows are kept. comparison file chunks are
+        //read fresh every time, so their rows are always projected
+        project_file_chunk_rows(
+            &mut comparison_file_chunk,
+            &reconciliation_options.comparison_file_row_format,
+            &mut vec![],
+        );
+
+        //derived columns are worked out once the rows have been read into columns
+        derive_file_chunk_columns(
+            &mut primary_file_chunk,
+            &reconciliation_options.primary_file_derived_columns,
+            &mut rows_reconciliation_details,
+        );
+
+        derive_file_chunk_columns(
+            &mut comparison_file_chunk,
+            &reconciliation_options.comparison_file_derived_columns,
+            &mut vec![],
+        );
+
+        //comparison pairs that reference columns by header name are resolved
+        //against the headers of both chunks and used alongside the index based pairs
+        let resolved_comparison_pairs = resolve_header_name_comparison_pairs(
+            &reconciliation_options.header_name_comparison_pairs,
+            &primary_file_chunk.column_headers,
+            &comparison_file_chunk.column_headers,
+        )?;
+
+        //the options of the resolved pairs only apply to this comparison file chunk
+        let mut resolved_reconciliation_options = reconciliation_options.clone();
+        resolved_reconciliation_options
+            .comparison_pair_options
+            .extend(resolve_header_name_comparison_pair_options(
+                &reconciliation_options.header_name_comparison_pairs,
+                &resolved_comparison_pairs,
+            ));
+
+        //and so are the header name comparison pairs of every match pass
+        for match_pass in resolved_reconciliation_options.match_passes.iter_mut() {
+            let resolved_match_pass_comparison_pairs = resolve_header_name_comparison_pairs(
+                &match_pass.header_name_comparison_pairs,
+                &primary_file_chunk.column_headers,
+                &comparison_file_chunk.column_headers,
+            )?;
+
+            match_pass
+                .comparison_pair_options
+                .extend(resolve_header_name_comparison_pair_options(
+                    &match_pass.header_name_comparison_pairs,
+                    &resolved_match_pass_comparison_pairs,
+                ));
+
+            match_pass
+                .comparison_pairs
+                .extend(resolved_match_pass_comparison_pairs);
+        }
+
+        //value mapping tables kept in redis are loaded once for this comparison file chunk
+        self.load_value_mapping_tables(&mut resolved_reconciliation_options)
             .await?;
 
-        //we reconcile the primary file chunk
-        let mut reconciled_primary_file_chunk = self
-            .file_reconciliation_algorithm
-            .reconcile_primary_file_chunk(&mut primary_file_chunk, &comparison_file_chunk)
+        //and so are the fx rate tables kept in redis
+        self.load_fx_rate_tables(&mut resolved_reconciliation_options)
             .await?;
 
+        primary_file_chunk
+            .comparison_pairs
+            .extend(resolved_comparison_pairs);
+
+        //requests with match passes are reconciled pass by pass
+        //and requests with a match rule are reconciled using the rule
+        let (algorithm_name, file_reconciliation_algorithm) =
+            if !reconciliation_options.match_passes.is_empty() {
+                (
+                    MULTI_PASS_ALGORITHM,
+                    &self.multi_pass_reconciliation_algorithm,
+                )
+            } else if reconciliation_options.match_rule.is_some() {
+                (
+                    MATCH_RULE_ALGORITHM,
+                    &self.match_rule_reconciliation_algorithm,
+                )
+            } else {
+                (GENERIC_ALGORITHM, &self.file_reconciliation_algorithm)
+            };
+
+        //the comparison file chunk is only read from here on,
+        //so it is shared with the algorithm instead of being copied
+        let comparison_file_chunk = Arc::new(comparison_file_chunk);
+
+        //we reconcile the primary file chunk in place
+        let algorithm_started_at = Instant::now();
+        file_reconciliation_algorithm
+            .reconcile_primary_file_chunk(
+                &mut primary_file_chunk,
+                &comparison_file_chunk,
+                &resolved_reconciliation_options,
+                &mut rows_reconciliation_details,
+            )
+            .instrument(tracing::info_span!(
+                "reconcile_primary_file_chunk",
+                algorithm = algorithm_name,
+                comparison_file_chunk_id = %comparison_file_chunk.id,
+            ))
+            .await?;
+
+        tracing::info!(
+            algorithm = algorithm_name,
+            comparison_file_chunk_id = %comparison_file_chunk.id,
+            rows = primary_file_chunk.chunk_rows.len(),
+            "reconciled primary file chunk against comparison file chunk"
+        );
+
+        observe_reconciliation_algorithm_duration(
+            algorithm_name,
+            algorithm_started_at.elapsed().as_secs_f64(),
+        );
+        record_file_chunk(FILE_CHUNK_PROCESSED);
+
         //after reconciliation of the chunk,
         //we acknowledge reciept of the comparison file chunk by marking it as processed
         let is_processed = self
@@ -78,38 +265,187 @@ impl FileChunkReconciliationServiceInterface for FileChunkReconciliationService
             ));
         }
 
+        //progress is only reported on, so failing to record it does not fail the chunk
+        if let Err(e) = self
+            .upload_progress_repo
+            .record_primary_file_chunk_progress(
+                &primary_file_chunk.upload_request_id,
+                primary_file_chunk.chunk_sequence_number,
+                comparison_file_chunk.chunk_sequence_number,
+                get_last_primary_chunk_sequence_number(&primary_file_chunk),
+            )
+            .await
+        {
+            tracing::warn!(error = %e, "failed to record the progress of primary file chunk");
+        }
+
+        primary_file_chunk.comparison_pairs = unresolved_comparison_pairs;
+
+        //the derived columns are worked out again for every comparison file chunk
+        remove_derived_columns(
+            &mut primary_file_chunk,
+            reconciliation_options.primary_file_derived_columns.len(),
+        );
+
         //we update the primary file chunk to point to track this comparison file chunks ID
         //as the last_acknowledged_id
-        reconciled_primary_file_chunk
+        primary_file_chunk
             .comparison_file_chunks_queue
-            .last_acknowledged_id = Some(comparison_file_chunk.id);
+            .last_acknowledged_id = Some(comparison_file_chunk.id.clone());
 
         //if the comparison file chunk we got was actually
         //the last one in the comparison file, it means reconciliation is done
         if comparison_file_chunk.is_last_chunk {
+            let reconciled_file_chunk = ReconciledFileChunk {
+                file_chunk: primary_file_chunk,
+                rows_reconciliation_details,
+            };
+
             return self
-                .insert_into_recon_results_queue(&reconciled_primary_file_chunk)
+                .insert_into_recon_results_queue(&reconciled_file_chunk)
                 .await;
         }
 
         //we insert this primary file chunk back into the
-        //buttom of the primary file queue
+        //buttom of the primary file queue together with its reconciliation options
+        let requeued_request = ReconcileFileChunkRequest {
+            primary_file_chunk,
+            reconciliation_options,
+            rows_reconciliation_details,
+        };
+
         return self
-            .reinsert_into_primary_file_chunks_queue(&reconciled_primary_file_chunk)
+            .reinsert_into_primary_file_chunks_queue(&requeued_request)
             .await;
     }
+
+    /**
+    loads the request a claim check stands for and reconciles it
+
+    # Errors
+
+    This function will return an error if the body of the request cannot be loaded or decoded,
+    or if reconciling the request fails.
+    */
+    async fn reconcile_claim_checked_file_chunk(
+        &self,
+        claim_check: FileChunkClaimCheck,
+    ) -> Result<ReconcileFileChunkResponse, AppError> {
+        let claim_check_body = self
+            .pubsub_repo
+            .get_file_chunk_claim_check_body(&claim_check)
+            .await?;
+
+        let chunk_content_type = ChunkContentType::parse(&claim_check.content_type)?;
+
+        let reconcile_primary_file_chunk_request: ReconcileFileChunkRequest =
+            deserialize_chunk_payload(
+                &claim_check_body,
+                &chunk_content_type,
+                self.chunk_payload_limits.max_decompressed_bytes,
+            )?;
+
+        let response = self
+            .reconcile_file_chunk(reconcile_primary_file_chunk_request)
+            .await?;
+
+        //the chunk has been requeued or sent on by now, so failing here would only get
+        //it reconciled twice. a body that is left behind expires on its own
+        if let Err(e) = self
+            .pubsub_repo
+            .delete_file_chunk_claim_check_body(&claim_check)
+            .await
+        {
+            tracing::warn!(
+                claim_check_key = %claim_check.claim_check_key,
+                error = %e,
+                "failed to delete the body of a claim checked file chunk"
+            );
+        }
+
+        return Ok(response);
+    }
 }
 
 impl FileChunkReconciliationService {
+    //replaces every value mapping table that is kept in a redis hash with its entries
+    async fn load_value_mapping_tables(
+        &self,
+        reconciliation_options: &mut ReconciliationOptions,
+    ) -> Result<(), AppError> {
+        //pairs often share a table so each hash is only read once
+        let mut loaded_entries: HashMap<String, HashMap<String, String>> = HashMap::new();
+
+        for pair_options in reconciliation_options.get_all_comparison_pair_options_mut() {
+            let redis_hash_key = match &pair_options.comparison_file_value_mapping {
+                Some(ValueMappingTable::RedisHash { key }) => key.clone(),
+                _ => continue,
+            };
+
+            if !loaded_entries.contains_key(&redis_hash_key) {
+                let entries = self
+                    .pubsub_repo
+                    .get_value_mapping_table_entries(&redis_hash_key)
+                    .await?;
+                loaded_entries.insert(redis_hash_key.clone(), entries);
+            }
+
+            pair_options.comparison_file_value_mapping = Some(ValueMappingTable::Inline {
+                entries: loaded_entries[&redis_hash_key].clone(),
+            });
+        }
+
+        return Ok(());
+    }
+
+    //replaces every fx rate table that is kept in a redis hash with its rates
+    async fn load_fx_rate_tables(
+        &self,
+        reconciliation_options: &mut ReconciliationOptions,
+    ) -> Result<(), AppError> {
+        //pairs often share a table so each hash is only read once
+        let mut loaded_rates: HashMap<String, HashMap<String, f64>> = HashMap::new();
+
+        for pair_options in reconciliation_options.get_all_comparison_pair_options_mut() {
+            let currency_conversion = match &mut pair_options.currency_conversion {
+                Some(currency_conversion) => currency_conversion,
+                None => continue,
+            };
+
+            let redis_hash_key = match &currency_conversion.fx_rate_table {
+                FxRateTable::RedisHash { key } => key.clone(),
+                _ => continue,
+            };
+
+            if !loaded_rates.contains_key(&redis_hash_key) {
+                let rates = self
+                    .pubsub_repo
+                    .get_fx_rate_table_rates(&redis_hash_key)
+                    .await?;
+                loaded_rates.insert(redis_hash_key.clone(), rates);
+            }
+
+            currency_conversion.fx_rate_table = FxRateTable::Inline {
+                rates: loaded_rates[&redis_hash_key].clone(),
+            };
+        }
+
+        return Ok(());
+    }
+
     //handles insertion of a file chunk into the recon results queue
     //as well as any errors from that process
     async fn insert_into_recon_results_queue(
         &self,
-        reconciled_primary_file_chunk: &FileUploadChunk,
+        reconciled_file_chunk: &ReconciledFileChunk,
     ) -> Result<ReconcileFileChunkResponse, AppError> {
         let is_inserted = self
             .pubsub_repo
-            .insert_file_chunk_into_recon_results_queue(&reconciled_primary_file_chunk)
+            .insert_file_chunk_into_recon_results_queue(&reconciled_file_chunk)
+            .instrument(tracing::info_span!(
+                "publish_reconciled_file_chunk",
+                result_chunks_queue = %reconciled_file_chunk.file_chunk.result_chunks_queue.topic_id,
+            ))
             .await?;
 
         //failed to insert
@@ -120,22 +456,137 @@ impl FileChunkReconciliationService {
             ));
         }
 
+        //rows are only counted once their chunk is done
+        //since their recon status can change with every comparison file chunk
+        let rows_by_recon_status = count_rows_by_recon_status(&reconciled_file_chunk.file_chunk);
+
+        record_file_chunk(FILE_CHUNK_COMPLETED);
+        record_reconciled_rows(&rows_by_recon_status);
+
+        tracing::info!("primary file chunk is reconciled against the whole comparison file");
+
+        let upload_progress = self
+            .upload_progress_repo
+            .record_completed_primary_file_chunk(
+                &reconciled_file_chunk.file_chunk.upload_request_id,
+                reconciled_file_chunk.file_chunk.chunk_sequence_number,
+                get_last_primary_chunk_sequence_number(&reconciled_file_chunk.file_chunk),
+                &rows_by_recon_status,
+            )
+            .await;
+
+        //the chunk is already in the recon results queue, so failing to record
+        //its completion or to announce the upload is done does not fail it
+        match upload_progress {
+            Ok(upload_progress) if upload_progress.is_completed() => {
+                self.publish_upload_reconciliation_completed_event(
+                    &reconciled_file_chunk.file_chunk.upload_request_id,
+                    &upload_progress,
+                )
+                .await;
+            }
+            Ok(_) => (),
+            Err(e) => {
+                tracing::warn!(error = %e, "failed to record the completion of primary file chunk")
+            }
+        }
+
         //we then return success such that its removed
         //from the top of the primary file queue
         return Ok(ReconcileFileChunkResponse {
-            file_chunk_id: reconciled_primary_file_chunk.id.clone(),
+            file_chunk_id: reconciled_file_chunk.file_chunk.id.clone(),
         });
     }
 
+    //publishes the completion event of an upload request whose primary file chunks are all
+    //in the recon results queue. the last chunks of an upload can complete at the same time,
+    //so the event is claimed first to have it published only once. the claim expires unless
+    //the event is published, so if this process stops in between, a redelivered chunk publishes it
+    async fn publish_upload_reconciliation_completed_event(
+        &self,
+        upload_request_id: &String,
+        upload_progress: &UploadProgress,
+    ) {
+        match self
+            .upload_progress_repo
+            .claim_upload_completion(upload_request_id)
+            .await
+        {
+            Ok(true) => (),
+            Ok(false) => return,
+            Err(e) => {
+                tracing::error!(error = %e, "failed to claim the completion event of upload request");
+                return;
+            }
+        }
+
+        let rows_by_recon_status = upload_progress.count_completed_rows_by_recon_status();
+
+        let upload_reconciliation_completed_event = UploadReconciliationCompletedEvent {
+            upload_request_id: upload_request_id.clone(),
+            primary_file_chunks_total: upload_progress.completed_primary_file_chunks.len() as u64,
+            comparison_file_chunks_consumed: upload_progress
+                .count_comparison_file_chunks_consumed(),
+            rows_total: rows_by_recon_status.values().sum(),
+            rows_by_recon_status,
+            date_completed: chrono::Utc::now().timestamp(),
+        };
+
+        let publish_result = self
+            .pubsub_repo
+            .publish_upload_reconciliation_completed_event(&upload_reconciliation_completed_event)
+            .instrument(tracing::info_span!(
+                "publish_upload_reconciliation_completed_event"
+            ))
+            .await;
+
+        match publish_result {
+            Ok(true) => {
+                tracing::info!(
+                    rows_total = upload_reconciliation_completed_event.rows_total,
+                    "every primary file chunk of the upload request is reconciled"
+                );
+
+                if let Err(e) = self
+                    .upload_progress_repo
+                    .confirm_upload_completion(upload_request_id)
+                    .await
+                {
+                    tracing::error!(error = %e, "failed to confirm the completion event of upload request");
+                }
+            }
+            _ => {
+                tracing::error!(
+                    error = ?publish_result.err(),
+                    "failed to publish the completion event of upload request"
+                );
+
+                //the event is left for a redelivered chunk of the upload to publish
+                if let Err(e) = self
+                    .upload_progress_repo
+                    .release_upload_completion(upload_request_id)
+                    .await
+                {
+                    tracing::error!(error = %e, "failed to release the completion event of upload request");
+                }
+            }
+        }
+    }
+
     //handles insertion of a file chunk into the primary file queue
     //as well as any errors from that process
     async fn reinsert_into_primary_file_chunks_queue(
         &self,
-        reconciled_primary_file_chunk: &FileUploadChunk,
+        requeued_request: &ReconcileFileChunkRequest,
     ) -> Result<ReconcileFileChunkResponse, AppError> {
         let is_inserted = self
             .pubsub_repo
-            .insert_file_chunk_in_primary_file_queue(&reconciled_primary_file_chunk)
+            .insert_file_chunk_in_primary_file_queue(&requeued_request)
+            .instrument(tracing::info_span!(
+                "requeue_primary_file_chunk",
+                primary_file_chunks_queue =
+                    %requeued_request.primary_file_chunk.primary_file_chunks_queue.topic_id,
+            ))
             .await?;
 
         //failed to insert
@@ -146,10 +597,14 @@ impl FileChunkReconciliationService {
             ));
         }
 
+        record_file_chunk(FILE_CHUNK_REQUEUED);
+
+        tracing::info!("requeued primary file chunk for the next comparison file chunk");
+
         //we then return success such that its removed from
         //the top of the primary file queue
         return Ok(ReconcileFileChunkResponse {
-            file_chunk_id: reconciled_primary_file_chunk.id.clone(),
+            file_chunk_id: requeued_request.primary_file_chunk.id.clone(),
         });
     }
 }
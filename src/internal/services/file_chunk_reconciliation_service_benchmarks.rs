@@ -0,0 +1,187 @@
+//measures how much memory reconciling a multi megabyte chunk takes, and how many allocations
+//it makes. it counts allocations using its own global allocator, so it is only built with the
+//memory-benchmarks feature. the allocation count grows with the number of candidate rows
+//compared if anything is copied per candidate instead of once per chunk.
+//run it with optimizations on using
+//cargo test --release --features memory-benchmarks file_chunk_reconciliation_service_benchmarks -- --nocapture
+//
+//no figures have been recorded for it yet: it has not been run against the baseline commit
+//(e40a78a) or against the current tree, because neither builds without the
+//shared_reconciler_rust_libraries submodule. the baseline has no benchmark, so to measure it
+//copy this file, its mod.rs entry and the memory-benchmarks feature onto e40a78a, drop the
+//fields the service did not have yet, and run the command above on both. record the peak
+//bytes and allocation count printed by each run here.
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use crate::internal::{
+    interfaces::{
+        file_chunk_reconciliation_service::FileChunkReconciliationServiceInterface,
+        pubsub_repository::MockPubSubRepositoryInterface,
+        upload_progress_repository::MockUploadProgressRepositoryInterface,
+    },
+    models::{
+        entities::{
+            chunk_payload_limits::ChunkPayloadLimits, reconciliation_options::ReconciliationOptions,
+        },
+        view_models::requests::reconcile_file_chunk_request::ReconcileFileChunkRequest,
+    },
+    services::core_logic::{
+        generic_file_reconciliation_algorithm::GenericFileReconciliationAlgorithm,
+        match_rule_file_reconciliation_algorithm::MatchRuleFileReconciliationAlgorithm,
+        multi_pass_file_reconciliation_algorithm::MultiPassFileReconciliationAlgorithm,
+    },
+    shared_reconciler_rust_libraries::models::entities::{
+        file_chunk_queue::FileChunkQueue,
+        file_upload_chunk::{
+            FileUploadChunk, FileUploadChunkRow, FileUploadChunkSource, ReconStatus,
+        },
+        recon_tasks_models::{ComparisonPair, ReconciliationConfigs},
+    },
+};
+
+use super::file_chunk_reconciliation_service::FileChunkReconciliationService;
+
+const BENCHMARK_ROW_COUNT: usize = 5_000;
+const BENCHMARK_COLUMN_COUNT: usize = 10;
+
+//keeps track of the bytes allocated by the test binary, the most that were allocated at once
+//and how many allocations were made
+struct CountingAllocator;
+
+static ALLOCATED_BYTES: AtomicUsize = AtomicUsize::new(0);
+static PEAK_ALLOCATED_BYTES: AtomicUsize = AtomicUsize::new(0);
+static ALLOCATIONS_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let allocated_bytes = ALLOCATED_BYTES.fetch_add(layout.size(), Ordering::SeqCst);
+        PEAK_ALLOCATED_BYTES.fetch_max(allocated_bytes + layout.size(), Ordering::SeqCst);
+        ALLOCATIONS_COUNT.fetch_add(1, Ordering::SeqCst);
+        return System.alloc(layout);
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        ALLOCATED_BYTES.fetch_sub(layout.size(), Ordering::SeqCst);
+        System.dealloc(ptr, layout);
+    }
+}
+
+#[global_allocator]
+static COUNTING_ALLOCATOR: CountingAllocator = CountingAllocator;
+
+#[actix_web::test]
+async fn benchmark_reconcile_file_chunk_peak_memory() {
+    let comparison_file_chunk = build_file_chunk();
+
+    let mut mock_pubsub_repo = Box::new(MockPubSubRepositoryInterface::new());
+
+    mock_pubsub_repo
+        .expect_get_next_comparison_file_upload_chunk()
+        .returning(move |_y| Ok(comparison_file_chunk.clone()));
+
+    mock_pubsub_repo
+        .expect_mark_comparison_file_chunk_as_processed()
+        .returning(|_y| Ok(true));
+
+    mock_pubsub_repo
+        .expect_insert_file_chunk_in_primary_file_queue()
+        .returning(|_y| Ok(true));
+
+    let mut mock_upload_progress_repo = Box::new(MockUploadProgressRepositoryInterface::new());
+
+    mock_upload_progress_repo
+        .expect_record_primary_file_chunk_progress()
+        .returning(|_w, _x, _y, _z| Ok(()));
+
+    let sut = FileChunkReconciliationService {
+        pubsub_repo: mock_pubsub_repo,
+        file_reconciliation_algorithm: Box::new(GenericFileReconciliationAlgorithm {}),
+        match_rule_reconciliation_algorithm: Box::new(MatchRuleFileReconciliationAlgorithm {}),
+        multi_pass_reconciliation_algorithm: Box::new(MultiPassFileReconciliationAlgorithm {
+            file_reconciliation_algorithm: Box::new(GenericFileReconciliationAlgorithm {}),
+            match_rule_reconciliation_algorithm: Box::new(MatchRuleFileReconciliationAlgorithm {}),
+        }),
+        upload_progress_repo: mock_upload_progress_repo,
+        chunk_payload_limits: ChunkPayloadLimits {
+            max_decompressed_bytes: 67_108_864,
+        },
+    };
+
+    let request = ReconcileFileChunkRequest {
+        primary_file_chunk: build_file_chunk(),
+        reconciliation_options: ReconciliationOptions::default(),
+        rows_reconciliation_details: vec![],
+    };
+
+    let request_size = serde_json::to_vec(&request).unwrap().len();
+
+    //the request is already in memory, as it would be once the web handler has read it
+    let allocated_bytes_before = ALLOCATED_BYTES.load(Ordering::SeqCst);
+    PEAK_ALLOCATED_BYTES.store(allocated_bytes_before, Ordering::SeqCst);
+    let allocations_count_before = ALLOCATIONS_COUNT.load(Ordering::SeqCst);
+
+    sut.reconcile_file_chunk(request).await.unwrap();
+
+    println!(
+        "reconciling a {} byte primary file chunk of {} rows needed at most {} bytes on top of the request in {} allocations",
+        request_size,
+        BENCHMARK_ROW_COUNT,
+        PEAK_ALLOCATED_BYTES.load(Ordering::SeqCst) - allocated_bytes_before,
+        ALLOCATIONS_COUNT.load(Ordering::SeqCst) - allocations_count_before
+    );
+}
+
+fn build_file_chunk() -> FileUploadChunk {
+    FileUploadChunk {
+        id: String::from("1526919030474-55"),
+        upload_request_id: String::from("file-1234"),
+        chunk_sequence_number: 1,
+        chunk_source: FileUploadChunkSource::ComparisonFileChunk,
+        chunk_rows: (0..BENCHMARK_ROW_COUNT)
+            .map(|row_index| {
+                let parsed_columns_from_row: Vec<String> = (0..BENCHMARK_COLUMN_COUNT)
+                    .map(|column_index| format!("ROW-{:06}-COLUMN-{:02}", row_index, column_index))
+                    .collect();
+                FileUploadChunkRow {
+                    row_number: row_index as u64,
+                    raw_data: parsed_columns_from_row.join(","),
+                    parsed_columns_from_row,
+                    recon_result: ReconStatus::Pending,
+                    recon_result_reasons: vec![],
+                }
+            })
+            .collect(),
+        date_created: chrono::Utc::now().timestamp(),
+        date_modified: chrono::Utc::now().timestamp(),
+        comparison_pairs: (0..BENCHMARK_COLUMN_COUNT)
+            .map(|column_index| ComparisonPair {
+                primary_file_column_index: column_index,
+                comparison_file_column_index: column_index,
+                is_row_identifier: column_index == 0,
+            })
+            .collect(),
+        column_headers: (0..BENCHMARK_COLUMN_COUNT)
+            .map(|column_index| format!("Column{}", column_index))
+            .collect(),
+        recon_config: ReconciliationConfigs {
+            should_check_for_duplicate_records_in_comparison_file: false,
+            should_reconciliation_be_case_sensitive: true,
+            should_ignore_white_space: true,
+            should_do_reverse_reconciliation: false,
+        },
+        primary_file_chunks_queue: build_file_chunks_queue("primary"),
+        comparison_file_chunks_queue: build_file_chunks_queue("comparison"),
+        result_chunks_queue: build_file_chunks_queue("result"),
+        is_last_chunk: false,
+    }
+}
+
+fn build_file_chunks_queue(prefix: &str) -> FileChunkQueue {
+    FileChunkQueue {
+        topic_id: format!("{}-file-chunks-queue-1", prefix),
+        last_acknowledged_id: None,
+    }
+}
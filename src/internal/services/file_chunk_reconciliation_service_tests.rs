@@ -1,22 +1,998 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
 use crate::internal::{
     interfaces::{
         file_chunk_reconciliation_service::FileChunkReconciliationServiceInterface,
         file_reconciliation_algorithm::MockFileReconciliationAlgorithmInterface,
         pubsub_repository::MockPubSubRepositoryInterface,
+        upload_progress_repository::MockUploadProgressRepositoryInterface,
+    },
+    models::{
+        entities::{
+            chunk_content_type::{ChunkContentType, ChunkEncoding},
+            chunk_payload_limits::ChunkPayloadLimits,
+            comparison_pair_options::{ColumnIndexComparisonPairOptions, ComparisonPairOptions},
+            currency_conversion::{CurrencyConversion, FxRateTable},
+            derived_column::{DerivedColumn, SignedAmountColumn},
+            file_chunk_claim_check::FileChunkClaimCheck,
+            header_name_comparison_pair::HeaderNameComparisonPair,
+            match_pass::MatchPass,
+            recon_result_reason::ReconResultReasonCode,
+            reconciliation_options::{ReconciliationOptions, SuccessfulRowHandling},
+            row_format::{FixedWidthColumn, RowFormat},
+            row_reconciliation_details::{ReconOutcome, RowReconciliationDetails},
+            upload_progress::UploadProgress,
+            value_mapping::ValueMappingTable,
+            value_transform::ValueTransform,
+        },
+        view_models::{
+            requests::reconcile_file_chunk_request::ReconcileFileChunkRequest,
+            responses::{
+                reconciled_file_chunk::ReconciledFileChunk,
+                upload_reconciliation_completed_event::UploadReconciliationCompletedEvent,
+            },
+        },
     },
-    models::view_models::requests::reconcile_file_chunk_request::ReconcileFileChunkRequest,
     shared_reconciler_rust_libraries::models::entities::{
         app_errors::{AppError, AppErrorKind},
         file_chunk_queue::FileChunkQueue,
-        file_upload_chunk::{FileUploadChunk, FileUploadChunkSource},
+        file_upload_chunk::{
+            FileUploadChunk, FileUploadChunkRow, FileUploadChunkSource, ReconStatus,
+        },
         recon_tasks_models::{ComparisonPair, ReconciliationConfigs},
     },
 };
 
-use super::file_chunk_reconciliation_service::FileChunkReconciliationService;
+use super::{
+    core_logic::{
+        chunk_serialization::serialize_chunk_payload,
+        generic_file_reconciliation_algorithm::GenericFileReconciliationAlgorithm,
+        match_rule_file_reconciliation_algorithm::MatchRuleFileReconciliationAlgorithm,
+    },
+    file_chunk_reconciliation_service::FileChunkReconciliationService,
+};
+
+#[actix_web::test]
+async fn given_valid_request_calls_correct_dependencies() {
+    //setup
+    let (mut mock_pubsub_repo, mut mock_file_recon_algo) = setup_dependencies();
+
+    mock_pubsub_repo
+        .expect_get_next_comparison_file_upload_chunk()
+        .times(1)
+        .returning(|_y| Ok(dummy_comparison_file()));
+
+    mock_file_recon_algo
+        .expect_reconcile_primary_file_chunk()
+        .times(1)
+        .returning(|_y, _x, _w, _v| Ok(()));
+
+    mock_pubsub_repo
+        .expect_mark_comparison_file_chunk_as_processed()
+        .times(1)
+        .returning(|_y| Ok(true));
+
+    mock_pubsub_repo
+        .expect_insert_file_chunk_in_primary_file_queue()
+        .times(1)
+        .returning(|_y| Ok(true));
+
+    mock_pubsub_repo
+        .expect_insert_file_chunk_into_recon_results_queue()
+        .times(0)
+        .returning(|_y| Ok(true));
+
+    let sut = setup(mock_pubsub_repo, mock_file_recon_algo);
+
+    let request = get_dummy_valid_request();
+
+    //act
+    let actual = sut.reconcile_file_chunk(request).await;
+
+    //assert
+    assert_eq!(actual.is_ok(), true);
+}
+
+#[actix_web::test]
+async fn given_valid_request_and_last_comparison_file_chunk_calls_correct_dependencies() {
+    //setup
+    let (mut mock_pubsub_repo, mut mock_file_recon_algo) = setup_dependencies();
+
+    mock_pubsub_repo
+        .expect_get_next_comparison_file_upload_chunk()
+        .times(1)
+        .returning(|_y| {
+            let mut comparison_file_chunk = dummy_comparison_file();
+            comparison_file_chunk.is_last_chunk = true;
+            Ok(comparison_file_chunk)
+        });
+
+    mock_file_recon_algo
+        .expect_reconcile_primary_file_chunk()
+        .times(1)
+        .returning(|_y, _x, _w, _v| Ok(()));
+
+    mock_pubsub_repo
+        .expect_mark_comparison_file_chunk_as_processed()
+        .times(1)
+        .returning(|_y| Ok(true));
+
+    mock_pubsub_repo
+        .expect_insert_file_chunk_in_primary_file_queue()
+        .times(0)
+        .returning(|_y| Ok(true));
+
+    mock_pubsub_repo
+        .expect_insert_file_chunk_into_recon_results_queue()
+        .times(1)
+        .returning(|_y| Ok(true));
+
+    let sut = setup(mock_pubsub_repo, mock_file_recon_algo);
+
+    let request = get_dummy_valid_request();
+
+    //act
+    let actual = sut.reconcile_file_chunk(request).await;
+
+    //assert
+    assert_eq!(actual.is_ok(), true);
+}
+
+#[actix_web::test]
+async fn given_valid_request_records_the_comparison_file_chunk_the_primary_file_chunk_got_to() {
+    //setup
+    let (mut mock_pubsub_repo, mut mock_file_recon_algo) = setup_dependencies();
+    let mut mock_upload_progress_repo = Box::new(MockUploadProgressRepositoryInterface::new());
+
+    mock_pubsub_repo
+        .expect_get_next_comparison_file_upload_chunk()
+        .times(1)
+        .returning(|_y| {
+            let mut comparison_file_chunk = dummy_comparison_file();
+            comparison_file_chunk.chunk_sequence_number = 3;
+            Ok(comparison_file_chunk)
+        });
+
+    mock_file_recon_algo
+        .expect_reconcile_primary_file_chunk()
+        .times(1)
+        .returning(|_y, _x, _w, _v| Ok(()));
+
+    mock_pubsub_repo
+        .expect_mark_comparison_file_chunk_as_processed()
+        .times(1)
+        .returning(|_y| Ok(true));
+
+    mock_pubsub_repo
+        .expect_insert_file_chunk_in_primary_file_queue()
+        .times(1)
+        .returning(|_y| Ok(true));
+
+    mock_upload_progress_repo
+        .expect_record_primary_file_chunk_progress()
+        .withf(
+            |upload_request_id,
+             primary_chunk_sequence_number,
+             comparison_chunk_sequence_number,
+             last_primary_chunk_sequence_number| {
+                upload_request_id == "file-1234"
+                    && *primary_chunk_sequence_number == 1
+                    && *comparison_chunk_sequence_number == 3
+                    && last_primary_chunk_sequence_number.is_none()
+            },
+        )
+        .times(1)
+        .returning(|_w, _x, _y, _z| Ok(()));
+
+    mock_upload_progress_repo
+        .expect_record_completed_primary_file_chunk()
+        .times(0);
+
+    let sut = setup_with_upload_progress_repo(
+        mock_pubsub_repo,
+        mock_file_recon_algo,
+        mock_upload_progress_repo,
+    );
+
+    //act
+    let actual = sut.reconcile_file_chunk(get_dummy_valid_request()).await;
+
+    //assert
+    assert_eq!(actual.is_ok(), true);
+}
+
+#[actix_web::test]
+async fn given_last_comparison_file_chunk_records_the_rows_of_the_completed_primary_file_chunk() {
+    //setup
+    let (mut mock_pubsub_repo, mut mock_file_recon_algo) = setup_dependencies();
+    let mut mock_upload_progress_repo = Box::new(MockUploadProgressRepositoryInterface::new());
+
+    mock_pubsub_repo
+        .expect_get_next_comparison_file_upload_chunk()
+        .times(1)
+        .returning(|_y| {
+            let mut comparison_file_chunk = dummy_comparison_file();
+            comparison_file_chunk.is_last_chunk = true;
+            Ok(comparison_file_chunk)
+        });
+
+    mock_file_recon_algo
+        .expect_reconcile_primary_file_chunk()
+        .times(1)
+        .returning(|_y, _x, _w, _v| Ok(()));
+
+    mock_pubsub_repo
+        .expect_mark_comparison_file_chunk_as_processed()
+        .times(1)
+        .returning(|_y| Ok(true));
+
+    mock_pubsub_repo
+        .expect_insert_file_chunk_into_recon_results_queue()
+        .times(1)
+        .returning(|_y| Ok(true));
+
+    mock_upload_progress_repo
+        .expect_record_primary_file_chunk_progress()
+        .times(1)
+        .returning(|_w, _x, _y, _z| Ok(()));
+
+    mock_upload_progress_repo
+        .expect_record_completed_primary_file_chunk()
+        .withf(
+            |upload_request_id,
+             primary_chunk_sequence_number,
+             last_primary_chunk_sequence_number,
+             rows_by_recon_status| {
+                upload_request_id == "file-1234"
+                    && *primary_chunk_sequence_number == 1
+                    && last_primary_chunk_sequence_number.is_none()
+                    && *rows_by_recon_status
+                        == HashMap::from([
+                            (String::from("Successful"), 2),
+                            (String::from("Failed"), 1),
+                        ])
+            },
+        )
+        .times(1)
+        .returning(|_w, _x, _y, _z| Ok(UploadProgress::default()));
+
+    let sut = setup_with_upload_progress_repo(
+        mock_pubsub_repo,
+        mock_file_recon_algo,
+        mock_upload_progress_repo,
+    );
+
+    let mut request = get_dummy_valid_request();
+    request.primary_file_chunk.chunk_rows = vec![
+        parsed_chunk_row(1, "TXN-1,100"),
+        parsed_chunk_row(2, "TXN-2,100"),
+        parsed_chunk_row(3, "TXN-3,100"),
+    ];
+    request.primary_file_chunk.chunk_rows[0].recon_result = ReconStatus::Successful;
+    request.primary_file_chunk.chunk_rows[1].recon_result = ReconStatus::Successful;
+    request.primary_file_chunk.chunk_rows[2].recon_result = ReconStatus::Failed;
+
+    //act
+    let actual = sut.reconcile_file_chunk(request).await;
+
+    //assert
+    assert_eq!(actual.is_ok(), true);
+}
+
+#[actix_web::test]
+async fn given_progress_fails_to_be_recorded_still_requeues_the_primary_file_chunk() {
+    //setup
+    let (mut mock_pubsub_repo, mut mock_file_recon_algo) = setup_dependencies();
+    let mut mock_upload_progress_repo = Box::new(MockUploadProgressRepositoryInterface::new());
+
+    mock_pubsub_repo
+        .expect_get_next_comparison_file_upload_chunk()
+        .times(1)
+        .returning(|_y| Ok(dummy_comparison_file()));
+
+    mock_file_recon_algo
+        .expect_reconcile_primary_file_chunk()
+        .times(1)
+        .returning(|_y, _x, _w, _v| Ok(()));
+
+    mock_pubsub_repo
+        .expect_mark_comparison_file_chunk_as_processed()
+        .times(1)
+        .returning(|_y| Ok(true));
+
+    mock_pubsub_repo
+        .expect_insert_file_chunk_in_primary_file_queue()
+        .times(1)
+        .returning(|_y| Ok(true));
+
+    mock_upload_progress_repo
+        .expect_record_primary_file_chunk_progress()
+        .times(1)
+        .returning(|_w, _x, _y, _z| {
+            Err(AppError::new(
+                AppErrorKind::ConnectionError,
+                String::from("Connection refused"),
+            ))
+        });
+
+    let sut = setup_with_upload_progress_repo(
+        mock_pubsub_repo,
+        mock_file_recon_algo,
+        mock_upload_progress_repo,
+    );
+
+    //act
+    let actual = sut.reconcile_file_chunk(get_dummy_valid_request()).await;
+
+    //assert
+    assert_eq!(actual.is_ok(), true);
+}
+
+#[actix_web::test]
+async fn given_the_last_primary_file_chunk_to_complete_publishes_the_upload_completion_event() {
+    //setup
+    let (mock_pubsub_repo, mock_file_recon_algo) =
+        setup_dependencies_for_the_last_comparison_file_chunk(1);
+    let mut mock_upload_progress_repo = setup_completed_upload_progress_repo();
+
+    mock_upload_progress_repo
+        .expect_claim_upload_completion()
+        .withf(|upload_request_id| upload_request_id == "file-1234")
+        .times(1)
+        .returning(|_x| Ok(true));
+
+    //the claim is only kept once the event is published
+    mock_upload_progress_repo
+        .expect_confirm_upload_completion()
+        .withf(|upload_request_id| upload_request_id == "file-1234")
+        .times(1)
+        .returning(|_x| Ok(()));
+
+    let published_events = Arc::new(Mutex::new(vec![]));
+    let captured_published_events = published_events.clone();
+    let mut mock_pubsub_repo = mock_pubsub_repo;
+    mock_pubsub_repo
+        .expect_publish_upload_reconciliation_completed_event()
+        .times(1)
+        .returning(move |upload_reconciliation_completed_event| {
+            captured_published_events
+                .lock()
+                .unwrap()
+                .push(upload_reconciliation_completed_event.clone());
+            Ok(true)
+        });
+
+    let sut = setup_with_upload_progress_repo(
+        mock_pubsub_repo,
+        mock_file_recon_algo,
+        mock_upload_progress_repo,
+    );
+
+    let mut request = get_dummy_valid_request();
+    request.primary_file_chunk.chunk_sequence_number = 2;
+    request.primary_file_chunk.is_last_chunk = true;
+
+    //act
+    let actual = sut.reconcile_file_chunk(request).await;
+
+    //assert
+    assert_eq!(actual.is_ok(), true);
+
+    let published_event: UploadReconciliationCompletedEvent =
+        published_events.lock().unwrap().remove(0);
+    assert_eq!(published_event.upload_request_id, "file-1234");
+    assert_eq!(published_event.primary_file_chunks_total, 2);
+    assert_eq!(published_event.comparison_file_chunks_consumed, 4);
+    assert_eq!(published_event.rows_total, 7);
+    assert_eq!(
+        published_event.rows_by_recon_status,
+        HashMap::from([(String::from("Successful"), 5), (String::from("Failed"), 2),])
+    );
+}
+
+#[actix_web::test]
+async fn given_upload_completion_already_claimed_does_not_publish_it_again() {
+    //setup
+    let (mut mock_pubsub_repo, mock_file_recon_algo) =
+        setup_dependencies_for_the_last_comparison_file_chunk(1);
+    let mut mock_upload_progress_repo = setup_completed_upload_progress_repo();
+
+    mock_upload_progress_repo
+        .expect_claim_upload_completion()
+        .times(1)
+        .returning(|_x| Ok(false));
+
+    mock_pubsub_repo
+        .expect_publish_upload_reconciliation_completed_event()
+        .times(0);
+
+    let sut = setup_with_upload_progress_repo(
+        mock_pubsub_repo,
+        mock_file_recon_algo,
+        mock_upload_progress_repo,
+    );
+
+    //act
+    let actual = sut.reconcile_file_chunk(get_dummy_valid_request()).await;
+
+    //assert
+    assert_eq!(actual.is_ok(), true);
+}
+
+#[actix_web::test]
+async fn given_upload_completion_event_fails_to_publish_releases_its_claim() {
+    //setup
+    let (mut mock_pubsub_repo, mock_file_recon_algo) =
+        setup_dependencies_for_the_last_comparison_file_chunk(1);
+    let mut mock_upload_progress_repo = setup_completed_upload_progress_repo();
+
+    mock_upload_progress_repo
+        .expect_claim_upload_completion()
+        .times(1)
+        .returning(|_x| Ok(true));
+
+    mock_pubsub_repo
+        .expect_publish_upload_reconciliation_completed_event()
+        .times(1)
+        .returning(|_x| {
+            Err(AppError::new(
+                AppErrorKind::ConnectionError,
+                String::from("Connection refused"),
+            ))
+        });
+
+    mock_upload_progress_repo
+        .expect_release_upload_completion()
+        .withf(|upload_request_id| upload_request_id == "file-1234")
+        .times(1)
+        .returning(|_x| Ok(()));
+
+    mock_upload_progress_repo
+        .expect_confirm_upload_completion()
+        .times(0);
+
+    let sut = setup_with_upload_progress_repo(
+        mock_pubsub_repo,
+        mock_file_recon_algo,
+        mock_upload_progress_repo,
+    );
+
+    //act
+    let actual = sut.reconcile_file_chunk(get_dummy_valid_request()).await;
+
+    //assert
+    assert_eq!(actual.is_ok(), true);
+}
+
+#[actix_web::test]
+async fn given_invalid_request_returns_error() {}
+
+#[actix_web::test]
+async fn given_request_with_invalid_row_format_returns_bad_client_request_error() {
+    //setup
+    let (mock_pubsub_repo, mock_file_recon_algo) = setup_dependencies();
+
+    let sut = setup(mock_pubsub_repo, mock_file_recon_algo);
+
+    let mut request = get_dummy_valid_request();
+    request.reconciliation_options.primary_file_row_format =
+        RowFormat::FixedWidth { columns: vec![] };
+
+    //act
+    let actual = sut.reconcile_file_chunk(request).await;
+
+    //assert
+    assert_eq!(actual.unwrap_err().kind, AppErrorKind::BadClientRequest);
+}
+
+#[actix_web::test]
+async fn given_request_with_invalid_value_transform_pattern_returns_bad_client_request_error() {
+    //setup
+    let (mock_pubsub_repo, mock_file_recon_algo) = setup_dependencies();
+
+    let sut = setup(mock_pubsub_repo, mock_file_recon_algo);
+
+    let mut request = get_dummy_valid_request();
+    let mut pair = header_name_comparison_pair("ID", "ID");
+    pair.options.comparison_file_value_transform = Some(ValueTransform::RegexCapture {
+        pattern: String::from("TXN/(\\d+"),
+        capture_group: 1,
+    });
+    request.reconciliation_options.header_name_comparison_pairs = vec![pair];
+
+    //act
+    let actual = sut.reconcile_file_chunk(request).await;
+
+    //assert
+    assert_eq!(actual.unwrap_err().kind, AppErrorKind::BadClientRequest);
+}
+
+#[actix_web::test]
+async fn given_fixed_width_row_formats_projects_rows_before_reconciliation() {
+    //setup
+    let (mut mock_pubsub_repo, mut mock_file_recon_algo) = setup_dependencies();
+
+    mock_pubsub_repo
+        .expect_get_next_comparison_file_upload_chunk()
+        .times(1)
+        .returning(|_y| {
+            let mut comparison_file_chunk = dummy_comparison_file();
+            comparison_file_chunk.chunk_rows = vec![unparsed_chunk_row(1, "0001DR   100")];
+            Ok(comparison_file_chunk)
+        });
+
+    mock_file_recon_algo
+        .expect_reconcile_primary_file_chunk()
+        .withf(
+            |primary_file_chunk, comparison_file_chunk, _options, _rows_details| {
+                primary_file_chunk.chunk_rows[0].parsed_columns_from_row == vec!["0001", "100"]
+                    && comparison_file_chunk.chunk_rows[0].parsed_columns_from_row
+                        == vec!["0001", "100"]
+            },
+        )
+        .times(1)
+        .returning(|_y, _x, _w, _v| Ok(()));
+
+    mock_pubsub_repo
+        .expect_mark_comparison_file_chunk_as_processed()
+        .times(1)
+        .returning(|_y| Ok(true));
+
+    mock_pubsub_repo
+        .expect_insert_file_chunk_in_primary_file_queue()
+        .withf(|requeued_request| {
+            requeued_request
+                .reconciliation_options
+                .comparison_file_row_format
+                != RowFormat::Delimited
+        })
+        .times(1)
+        .returning(|_y| Ok(true));
+
+    let sut = setup(mock_pubsub_repo, mock_file_recon_algo);
+
+    let mut request = get_dummy_valid_request();
+    request.primary_file_chunk.chunk_rows = vec![unparsed_chunk_row(1, "0001100")];
+    request.reconciliation_options = ReconciliationOptions {
+        primary_file_row_format: RowFormat::FixedWidth {
+            columns: vec![fixed_width_column(0, 4), fixed_width_column(4, 3)],
+        },
+        comparison_file_row_format: RowFormat::FixedWidth {
+            columns: vec![fixed_width_column(0, 4), fixed_width_column(9, 3)],
+        },
+        ..ReconciliationOptions::default()
+    };
+
+    //act
+    let actual = sut.reconcile_file_chunk(request).await;
+
+    //assert
+    assert_eq!(actual.is_ok(), true);
+}
+
+#[actix_web::test]
+async fn given_fixed_width_rows_split_by_the_uploader_still_projects_them() {
+    //setup
+    let (mut mock_pubsub_repo, mut mock_file_recon_algo) = setup_dependencies();
+
+    mock_pubsub_repo
+        .expect_get_next_comparison_file_upload_chunk()
+        .times(1)
+        .returning(|_y| {
+            let mut comparison_file_chunk = dummy_comparison_file();
+            comparison_file_chunk.chunk_rows = vec![parsed_chunk_row(1, "0001DR   100")];
+            Ok(comparison_file_chunk)
+        });
+
+    mock_file_recon_algo
+        .expect_reconcile_primary_file_chunk()
+        .withf(
+            |primary_file_chunk, comparison_file_chunk, _options, _rows_details| {
+                primary_file_chunk.chunk_rows[0].parsed_columns_from_row == vec!["0001", "100"]
+                    && comparison_file_chunk.chunk_rows[0].parsed_columns_from_row
+                        == vec!["0001", "100"]
+            },
+        )
+        .times(1)
+        .returning(|_y, _x, _w, _v| Ok(()));
+
+    mock_pubsub_repo
+        .expect_mark_comparison_file_chunk_as_processed()
+        .times(1)
+        .returning(|_y| Ok(true));
+
+    //the requeued rows are marked as projected so they are not read again
+    mock_pubsub_repo
+        .expect_insert_file_chunk_in_primary_file_queue()
+        .withf(|requeued_request| {
+            requeued_request.rows_reconciliation_details
+                == vec![RowReconciliationDetails {
+                    row_number: 1,
+                    is_projected: true,
+                    ..RowReconciliationDetails::default()
+                }]
+        })
+        .times(1)
+        .returning(|_y| Ok(true));
+
+    let sut = setup(mock_pubsub_repo, mock_file_recon_algo);
+
+    let mut request = get_dummy_valid_request();
+    request.primary_file_chunk.chunk_rows = vec![parsed_chunk_row(1, "0001100")];
+    request.reconciliation_options = ReconciliationOptions {
+        primary_file_row_format: RowFormat::FixedWidth {
+            columns: vec![fixed_width_column(0, 4), fixed_width_column(4, 3)],
+        },
+        comparison_file_row_format: RowFormat::FixedWidth {
+            columns: vec![fixed_width_column(0, 4), fixed_width_column(9, 3)],
+        },
+        ..ReconciliationOptions::default()
+    };
+
+    //act
+    let actual = sut.reconcile_file_chunk(request).await;
+
+    //assert
+    assert_eq!(actual.is_ok(), true);
+}
+
+#[actix_web::test]
+async fn given_header_name_comparison_pair_resolves_it_against_both_chunks_column_headers() {
+    //setup
+    let (mut mock_pubsub_repo, mut mock_file_recon_algo) = setup_dependencies();
+
+    mock_pubsub_repo
+        .expect_get_next_comparison_file_upload_chunk()
+        .times(1)
+        .returning(|_y| {
+            let mut comparison_file_chunk = dummy_comparison_file();
+            comparison_file_chunk.column_headers = build_column_headers("Amount, Reference");
+            Ok(comparison_file_chunk)
+        });
+
+    mock_file_recon_algo
+        .expect_reconcile_primary_file_chunk()
+        .withf(
+            |primary_file_chunk, _comparison_file_chunk, _options, _rows_details| {
+                primary_file_chunk.comparison_pairs
+                    == vec![
+                        new_same_column_index_comparison_pair(0),
+                        ComparisonPair {
+                            primary_file_column_index: 1,
+                            comparison_file_column_index: 0,
+                            is_row_identifier: false,
+                        },
+                    ]
+            },
+        )
+        .times(1)
+        .returning(|_y, _x, _w, _v| Ok(()));
+
+    mock_pubsub_repo
+        .expect_mark_comparison_file_chunk_as_processed()
+        .times(1)
+        .returning(|_y| Ok(true));
+
+    mock_pubsub_repo
+        .expect_insert_file_chunk_in_primary_file_queue()
+        .withf(|requeued_request| {
+            requeued_request.primary_file_chunk.comparison_pairs
+                == vec![new_same_column_index_comparison_pair(0)]
+        })
+        .times(1)
+        .returning(|_y| Ok(true));
+
+    let sut = setup(mock_pubsub_repo, mock_file_recon_algo);
+
+    let mut request = get_dummy_valid_request();
+    request.primary_file_chunk.column_headers = build_column_headers("Reference, Amount");
+    request.reconciliation_options.header_name_comparison_pairs =
+        vec![header_name_comparison_pair("Amount", "Amount")];
+
+    //act
+    let actual = sut.reconcile_file_chunk(request).await;
+
+    //assert
+    assert_eq!(actual.is_ok(), true);
+}
+
+#[actix_web::test]
+async fn given_header_name_comparison_pair_with_missing_header_returns_bad_client_request_error() {
+    //setup
+    let (mut mock_pubsub_repo, mut mock_file_recon_algo) = setup_dependencies();
+
+    mock_pubsub_repo
+        .expect_get_next_comparison_file_upload_chunk()
+        .times(1)
+        .returning(|_y| {
+            let mut comparison_file_chunk = dummy_comparison_file();
+            comparison_file_chunk.column_headers = build_column_headers("Reference, Amount");
+            Ok(comparison_file_chunk)
+        });
+
+    mock_file_recon_algo
+        .expect_reconcile_primary_file_chunk()
+        .times(0);
+
+    let sut = setup(mock_pubsub_repo, mock_file_recon_algo);
+
+    let mut request = get_dummy_valid_request();
+    request.primary_file_chunk.column_headers = build_column_headers("Reference, Amount");
+    request.reconciliation_options.header_name_comparison_pairs =
+        vec![header_name_comparison_pair("Amount", "Value")];
+
+    //act
+    let actual = sut.reconcile_file_chunk(request).await;
+
+    //assert
+    assert_eq!(actual.unwrap_err().kind, AppErrorKind::BadClientRequest);
+}
+
+#[actix_web::test]
+async fn given_redis_hash_value_mapping_table_loads_its_entries_once_before_reconciliation() {
+    //setup
+    let (mut mock_pubsub_repo, mut mock_file_recon_algo) = setup_dependencies();
+
+    mock_pubsub_repo
+        .expect_get_next_comparison_file_upload_chunk()
+        .times(1)
+        .returning(|_y| {
+            let mut comparison_file_chunk = dummy_comparison_file();
+            comparison_file_chunk.column_headers = build_column_headers("Indicator, Currency");
+            Ok(comparison_file_chunk)
+        });
+
+    mock_pubsub_repo
+        .expect_get_value_mapping_table_entries()
+        .withf(|redis_hash_key| redis_hash_key == "debit-credit-codes")
+        .times(1)
+        .returning(|_y| {
+            Ok(HashMap::from([
+                (String::from("D"), String::from("DR")),
+                (String::from("C"), String::from("CR")),
+            ]))
+        });
+
+    mock_file_recon_algo
+        .expect_reconcile_primary_file_chunk()
+        .withf(
+            |_primary_file_chunk, _comparison_file_chunk, options, _rows_details| {
+                options.comparison_pair_options.len() == 2
+                    && options.comparison_pair_options.iter().all(|pair_options| {
+                        pair_options.options.comparison_file_value_mapping
+                            == Some(ValueMappingTable::Inline {
+                                entries: HashMap::from([
+                                    (String::from("D"), String::from("DR")),
+                                    (String::from("C"), String::from("CR")),
+                                ]),
+                            })
+                    })
+            },
+        )
+        .times(1)
+        .returning(|_y, _x, _w, _v| Ok(()));
+
+    mock_pubsub_repo
+        .expect_mark_comparison_file_chunk_as_processed()
+        .times(1)
+        .returning(|_y| Ok(true));
+
+    mock_pubsub_repo
+        .expect_insert_file_chunk_in_primary_file_queue()
+        .withf(|requeued_request| {
+            requeued_request
+                .reconciliation_options
+                .header_name_comparison_pairs[0]
+                .options
+                .comparison_file_value_mapping
+                == Some(ValueMappingTable::RedisHash {
+                    key: String::from("debit-credit-codes"),
+                })
+        })
+        .times(1)
+        .returning(|_y| Ok(true));
+
+    let sut = setup(mock_pubsub_repo, mock_file_recon_algo);
+
+    let mut request = get_dummy_valid_request();
+    request.primary_file_chunk.column_headers = build_column_headers("Indicator, Currency");
+
+    let mut indicator_pair = header_name_comparison_pair("Indicator", "Indicator");
+    indicator_pair.options.comparison_file_value_mapping = Some(ValueMappingTable::RedisHash {
+        key: String::from("debit-credit-codes"),
+    });
+    let mut currency_pair = header_name_comparison_pair("Currency", "Currency");
+    currency_pair.options.comparison_file_value_mapping =
+        indicator_pair.options.comparison_file_value_mapping.clone();
+    request.reconciliation_options.header_name_comparison_pairs =
+        vec![indicator_pair, currency_pair];
+
+    //act
+    let actual = sut.reconcile_file_chunk(request).await;
+
+    //assert
+    assert_eq!(actual.is_ok(), true);
+}
+
+#[actix_web::test]
+async fn given_match_rule_reconciles_using_the_match_rule_algorithm() {
+    //setup
+    let (mut mock_pubsub_repo, mut mock_file_recon_algo) = setup_dependencies();
+
+    mock_pubsub_repo
+        .expect_get_next_comparison_file_upload_chunk()
+        .times(1)
+        .returning(|_y| Ok(dummy_comparison_file()));
+
+    mock_file_recon_algo
+        .expect_reconcile_primary_file_chunk()
+        .times(0);
+
+    let mut mock_match_rule_recon_algo = Box::new(MockFileReconciliationAlgorithmInterface::new());
+
+    mock_match_rule_recon_algo
+        .expect_reconcile_primary_file_chunk()
+        .times(1)
+        .returning(|_y, _x, _w, _v| Ok(()));
+
+    mock_pubsub_repo
+        .expect_mark_comparison_file_chunk_as_processed()
+        .times(1)
+        .returning(|_y| Ok(true));
+
+    mock_pubsub_repo
+        .expect_insert_file_chunk_in_primary_file_queue()
+        .times(1)
+        .returning(|_y| Ok(true));
+
+    let mut sut = setup(mock_pubsub_repo, mock_file_recon_algo);
+    sut.match_rule_reconciliation_algorithm = mock_match_rule_recon_algo;
+
+    let mut request = get_dummy_valid_request();
+    request.reconciliation_options.match_rule =
+        Some(String::from("equals(primary[0], comparison[0])"));
+
+    //act
+    let actual = sut.reconcile_file_chunk(request).await;
+
+    //assert
+    assert_eq!(actual.is_ok(), true);
+}
+
+#[actix_web::test]
+async fn given_request_with_invalid_match_rule_returns_bad_client_request_error() {
+    //setup
+    let (mock_pubsub_repo, mock_file_recon_algo) = setup_dependencies();
+
+    let sut = setup(mock_pubsub_repo, mock_file_recon_algo);
+
+    let mut request = get_dummy_valid_request();
+    request.reconciliation_options.match_rule =
+        Some(String::from("equals(primary[0], comparison[0]) AND"));
+
+    //act
+    let actual = sut.reconcile_file_chunk(request).await;
+
+    //assert
+    assert_eq!(actual.unwrap_err().kind, AppErrorKind::BadClientRequest);
+}
+
+#[actix_web::test]
+async fn given_match_passes_reconciles_using_the_multi_pass_algorithm_with_resolved_passes() {
+    //setup
+    let (mut mock_pubsub_repo, mut mock_file_recon_algo) = setup_dependencies();
+
+    mock_pubsub_repo
+        .expect_get_next_comparison_file_upload_chunk()
+        .times(1)
+        .returning(|_y| {
+            let mut comparison_file_chunk = dummy_comparison_file();
+            comparison_file_chunk.column_headers = build_column_headers("Name, Amount");
+            Ok(comparison_file_chunk)
+        });
+
+    mock_pubsub_repo
+        .expect_get_value_mapping_table_entries()
+        .withf(|redis_hash_key| redis_hash_key == "counterparty-names")
+        .times(1)
+        .returning(|_y| {
+            Ok(HashMap::from([(
+                String::from("ACME"),
+                String::from("Acme"),
+            )]))
+        });
+
+    mock_file_recon_algo
+        .expect_reconcile_primary_file_chunk()
+        .times(0);
+
+    let mut mock_multi_pass_recon_algo = Box::new(MockFileReconciliationAlgorithmInterface::new());
+
+    //the header name pairs of the pass are resolved and its redis tables loaded
+    mock_multi_pass_recon_algo
+        .expect_reconcile_primary_file_chunk()
+        .withf(
+            |_primary_file_chunk, _comparison_file_chunk, reconciliation_options, _details| {
+                let match_pass = &reconciliation_options.match_passes[0];
+                match_pass.comparison_pairs
+                    == vec![ComparisonPair {
+                        primary_file_column_index: 0,
+                        comparison_file_column_index: 0,
+                        is_row_identifier: true,
+                    }]
+                    && match_pass.comparison_pair_options[0]
+                        .options
+                        .comparison_file_value_mapping
+                        == Some(ValueMappingTable::Inline {
+                            entries: HashMap::from([(String::from("ACME"), String::from("Acme"))]),
+                        })
+            },
+        )
+        .times(1)
+        .returning(|_y, _x, _w, _v| Ok(()));
+
+    mock_pubsub_repo
+        .expect_mark_comparison_file_chunk_as_processed()
+        .times(1)
+        .returning(|_y| Ok(true));
+
+    mock_pubsub_repo
+        .expect_insert_file_chunk_in_primary_file_queue()
+        .times(1)
+        .returning(|_y| Ok(true));
+
+    let mut sut = setup(mock_pubsub_repo, mock_file_recon_algo);
+    sut.multi_pass_reconciliation_algorithm = mock_multi_pass_recon_algo;
+
+    let mut reference_pair = header_name_comparison_pair("Name", "Name");
+    reference_pair.is_row_identifier = true;
+    reference_pair.options.comparison_file_value_mapping = Some(ValueMappingTable::RedisHash {
+        key: String::from("counterparty-names"),
+    });
+
+    let mut request = get_dummy_valid_request();
+    request.primary_file_chunk.column_headers = build_column_headers("Name, Amount");
+    request.reconciliation_options.match_passes = vec![MatchPass {
+        name: String::from("ExactName"),
+        header_name_comparison_pairs: vec![reference_pair],
+        ..MatchPass::default()
+    }];
+
+    //act
+    let actual = sut.reconcile_file_chunk(request).await;
+
+    //assert
+    assert_eq!(actual.is_ok(), true);
+}
+
+#[actix_web::test]
+async fn given_match_passes_with_the_same_name_returns_bad_client_request_error() {
+    //setup
+    let (mock_pubsub_repo, mock_file_recon_algo) = setup_dependencies();
+
+    let sut = setup(mock_pubsub_repo, mock_file_recon_algo);
+
+    let match_pass = MatchPass {
+        name: String::from("ExactReference"),
+        comparison_pairs: vec![new_same_column_index_comparison_pair(0)],
+        ..MatchPass::default()
+    };
+
+    let mut request = get_dummy_valid_request();
+    request.reconciliation_options.match_passes = vec![match_pass.clone(), match_pass];
+
+    //act
+    let actual = sut.reconcile_file_chunk(request).await;
+
+    //assert
+    assert_eq!(actual.unwrap_err().kind, AppErrorKind::BadClientRequest);
+}
 
 #[actix_web::test]
-async fn given_valid_request_calls_correct_dependencies() {
+async fn given_redis_hash_fx_rate_table_loads_its_rates_before_reconciliation() {
     //setup
     let (mut mock_pubsub_repo, mut mock_file_recon_algo) = setup_dependencies();
 
@@ -25,10 +1001,29 @@ async fn given_valid_request_calls_correct_dependencies() {
         .times(1)
         .returning(|_y| Ok(dummy_comparison_file()));
 
+    mock_pubsub_repo
+        .expect_get_fx_rate_table_rates()
+        .withf(|redis_hash_key| redis_hash_key == "usd-rates")
+        .times(1)
+        .returning(|_y| Ok(HashMap::from([(String::from("UGX"), 0.00027)])));
+
     mock_file_recon_algo
         .expect_reconcile_primary_file_chunk()
+        .withf(
+            |_primary_file_chunk, _comparison_file_chunk, options, _rows_details| {
+                options.comparison_pair_options[0]
+                    .options
+                    .currency_conversion
+                    .as_ref()
+                    .unwrap()
+                    .fx_rate_table
+                    == FxRateTable::Inline {
+                        rates: HashMap::from([(String::from("UGX"), 0.00027)]),
+                    }
+            },
+        )
         .times(1)
-        .returning(|_y, _x| Ok(dummy_reconciled_file_upload_chunk()));
+        .returning(|_y, _x, _w, _v| Ok(()));
 
     mock_pubsub_repo
         .expect_mark_comparison_file_chunk_as_processed()
@@ -40,24 +1035,36 @@ async fn given_valid_request_calls_correct_dependencies() {
         .times(1)
         .returning(|_y| Ok(true));
 
-    mock_pubsub_repo
-        .expect_insert_file_chunk_into_recon_results_queue()
-        .times(0)
-        .returning(|_y| Ok(true));
-
     let sut = setup(mock_pubsub_repo, mock_file_recon_algo);
 
-    let request = get_dummy_valid_request();
+    let mut request = get_dummy_valid_request();
+    request.reconciliation_options.comparison_pair_options =
+        vec![ColumnIndexComparisonPairOptions {
+            primary_file_column_index: 0,
+            comparison_file_column_index: 0,
+            options: ComparisonPairOptions {
+                currency_conversion: Some(CurrencyConversion {
+                    primary_file_currency_column_index: 1,
+                    comparison_file_currency_column_index: 1,
+                    base_currency: String::from("USD"),
+                    fx_rate_table: FxRateTable::RedisHash {
+                        key: String::from("usd-rates"),
+                    },
+                    tolerance: 0.0,
+                }),
+                ..ComparisonPairOptions::default()
+            },
+        }];
 
     //act
-    let actual = sut.reconcile_file_chunk(&request).await;
+    let actual = sut.reconcile_file_chunk(request).await;
 
     //assert
     assert_eq!(actual.is_ok(), true);
 }
 
 #[actix_web::test]
-async fn given_valid_request_and_last_comparison_file_chunk_calls_correct_dependencies() {
+async fn given_derived_columns_derives_them_for_reconciliation_but_not_for_requeueing() {
     //setup
     let (mut mock_pubsub_repo, mut mock_file_recon_algo) = setup_dependencies();
 
@@ -66,14 +1073,30 @@ async fn given_valid_request_and_last_comparison_file_chunk_calls_correct_depend
         .times(1)
         .returning(|_y| {
             let mut comparison_file_chunk = dummy_comparison_file();
-            comparison_file_chunk.is_last_chunk = true;
+            comparison_file_chunk.column_headers = build_column_headers("Reference, Amount");
+            comparison_file_chunk.chunk_rows = vec![parsed_chunk_row(1, "1001, -100")];
             Ok(comparison_file_chunk)
         });
 
     mock_file_recon_algo
         .expect_reconcile_primary_file_chunk()
+        .withf(
+            |primary_file_chunk, comparison_file_chunk, _options, _rows_details| {
+                primary_file_chunk.chunk_rows[0].parsed_columns_from_row[3] == "-100"
+                    && comparison_file_chunk.chunk_rows[0]
+                        .parsed_columns_from_row
+                        .len()
+                        == 2
+                    && primary_file_chunk.comparison_pairs.last()
+                        == Some(&ComparisonPair {
+                            primary_file_column_index: 3,
+                            comparison_file_column_index: 1,
+                            is_row_identifier: false,
+                        })
+            },
+        )
         .times(1)
-        .returning(|_y, _x| Ok(dummy_reconciled_file_upload_chunk()));
+        .returning(|_y, _x, _w, _v| Ok(()));
 
     mock_pubsub_repo
         .expect_mark_comparison_file_chunk_as_processed()
@@ -82,27 +1105,244 @@ async fn given_valid_request_and_last_comparison_file_chunk_calls_correct_depend
 
     mock_pubsub_repo
         .expect_insert_file_chunk_in_primary_file_queue()
-        .times(0)
+        .withf(|requeued_request| {
+            requeued_request.primary_file_chunk.column_headers.len() == 3
+                && requeued_request.primary_file_chunk.chunk_rows[0]
+                    .parsed_columns_from_row
+                    .len()
+                    == 3
+        })
+        .times(1)
+        .returning(|_y| Ok(true));
+
+    let sut = setup(mock_pubsub_repo, mock_file_recon_algo);
+
+    let mut request = get_dummy_valid_request();
+    request.primary_file_chunk.column_headers = build_column_headers("Reference, Amount, DrCr");
+    request.primary_file_chunk.chunk_rows = vec![parsed_chunk_row(1, "1001, 100, DR")];
+    request.reconciliation_options.primary_file_derived_columns =
+        vec![DerivedColumn::SignedAmount(SignedAmountColumn {
+            column_header: String::from("SignedAmount"),
+            amount_column_index: 1,
+            indicator_column_index: Some(2),
+            debit_indicators: vec![String::from("DR")],
+            credit_indicators: vec![String::from("CR")],
+            should_invert_sign: false,
+        })];
+    request.reconciliation_options.header_name_comparison_pairs =
+        vec![header_name_comparison_pair("SignedAmount", "Amount")];
+
+    //act
+    let actual = sut.reconcile_file_chunk(request).await;
+
+    //assert
+    assert_eq!(actual.is_ok(), true);
+}
+
+#[actix_web::test]
+async fn given_request_with_derived_column_without_indicators_returns_bad_client_request_error() {
+    //setup
+    let (mock_pubsub_repo, mock_file_recon_algo) = setup_dependencies();
+
+    let sut = setup(mock_pubsub_repo, mock_file_recon_algo);
+
+    let mut request = get_dummy_valid_request();
+    request
+        .reconciliation_options
+        .comparison_file_derived_columns = vec![DerivedColumn::SignedAmount(SignedAmountColumn {
+        column_header: String::from("SignedAmount"),
+        amount_column_index: 1,
+        indicator_column_index: Some(2),
+        debit_indicators: vec![],
+        credit_indicators: vec![],
+        should_invert_sign: false,
+    })];
+
+    //act
+    let actual = sut.reconcile_file_chunk(request).await;
+
+    //assert
+    assert_eq!(actual.unwrap_err().kind, AppErrorKind::BadClientRequest);
+}
+
+#[actix_web::test]
+async fn given_request_with_rows_reconciliation_details_keeps_them_when_requeueing() {
+    //setup
+    let (mut mock_pubsub_repo, mut mock_file_recon_algo) = setup_dependencies();
+
+    mock_pubsub_repo
+        .expect_get_next_comparison_file_upload_chunk()
+        .times(1)
+        .returning(|_y| Ok(dummy_comparison_file()));
+
+    mock_file_recon_algo
+        .expect_reconcile_primary_file_chunk()
+        .withf(
+            |_primary_file_chunk, _comparison_file_chunk, _options, rows_details| {
+                rows_details.len() == 1
+            },
+        )
+        .times(1)
+        .returning(|_y, _x, _w, _v| Ok(()));
+
+    mock_pubsub_repo
+        .expect_mark_comparison_file_chunk_as_processed()
+        .times(1)
         .returning(|_y| Ok(true));
 
     mock_pubsub_repo
-        .expect_insert_file_chunk_into_recon_results_queue()
+        .expect_insert_file_chunk_in_primary_file_queue()
+        .withf(|requeued_request| {
+            requeued_request.rows_reconciliation_details == vec![matched_row_details(1)]
+        })
         .times(1)
         .returning(|_y| Ok(true));
 
     let sut = setup(mock_pubsub_repo, mock_file_recon_algo);
 
-    let request = get_dummy_valid_request();
+    let mut request = get_dummy_valid_request();
+    request.rows_reconciliation_details = vec![matched_row_details(1)];
 
     //act
-    let actual = sut.reconcile_file_chunk(&request).await;
+    let actual = sut.reconcile_file_chunk(request).await;
 
     //assert
     assert_eq!(actual.is_ok(), true);
 }
 
 #[actix_web::test]
-async fn given_invalid_request_returns_error() {}
+async fn given_first_match_wins_a_successful_row_is_not_compared_with_later_comparison_chunks() {
+    //act
+    let reconciled_file_chunk =
+        reconcile_against_two_comparison_chunks(SuccessfulRowHandling::FirstMatchWins).await;
+
+    //assert
+    let reconciled_row = &reconciled_file_chunk.file_chunk.chunk_rows[0];
+    assert_eq!(reconciled_row.recon_result, ReconStatus::Successful);
+    assert_eq!(reconciled_row.recon_result_reasons.len(), 0);
+    assert_eq!(
+        reconciled_file_chunk.rows_reconciliation_details[0].matched_comparison_file_chunk_id,
+        Some(String::from("1-0"))
+    );
+}
+
+#[actix_web::test]
+async fn given_check_against_all_comparison_chunks_a_cross_chunk_duplicate_fails_the_row() {
+    //act
+    let reconciled_file_chunk = reconcile_against_two_comparison_chunks(
+        SuccessfulRowHandling::CheckAgainstAllComparisonChunks,
+    )
+    .await;
+
+    //assert
+    let reconciled_row = &reconciled_file_chunk.file_chunk.chunk_rows[0];
+    assert_eq!(reconciled_row.recon_result, ReconStatus::Failed);
+    assert_eq!(
+        reconciled_file_chunk.rows_reconciliation_details[0].recon_result_reasons[0].reason_code,
+        ReconResultReasonCode::DuplicateComparisonRow
+    );
+    assert_eq!(
+        reconciled_file_chunk.rows_reconciliation_details[0].recon_result_reasons[0]
+            .comparison_file_chunk_id,
+        String::from("2-0")
+    );
+}
+
+#[actix_web::test]
+async fn given_claim_check_reconciles_the_stored_request_and_deletes_its_body() {
+    //setup
+    let (mut mock_pubsub_repo, mut mock_file_recon_algo) = setup_dependencies();
+
+    let chunk_content_type = ChunkContentType {
+        encoding: ChunkEncoding::MessagePack,
+        is_zstd_compressed: true,
+    };
+    let claim_check_body =
+        serialize_chunk_payload(&get_dummy_valid_request(), &chunk_content_type).unwrap();
+    let claim_check = FileChunkClaimCheck {
+        claim_check_key: String::from("file-chunk-claim-checks:file-1234:1"),
+        content_type: chunk_content_type.to_content_type_header(),
+        primary_file_chunk_id: String::from("src-file-1234"),
+    };
+
+    mock_pubsub_repo
+        .expect_get_file_chunk_claim_check_body()
+        .withf(|claim_check| claim_check.claim_check_key == "file-chunk-claim-checks:file-1234:1")
+        .times(1)
+        .returning(move |_y| Ok(claim_check_body.clone()));
+
+    mock_pubsub_repo
+        .expect_get_next_comparison_file_upload_chunk()
+        .times(1)
+        .returning(|_y| Ok(dummy_comparison_file()));
+
+    mock_file_recon_algo
+        .expect_reconcile_primary_file_chunk()
+        .times(1)
+        .returning(|_y, _x, _w, _v| Ok(()));
+
+    mock_pubsub_repo
+        .expect_mark_comparison_file_chunk_as_processed()
+        .times(1)
+        .returning(|_y| Ok(true));
+
+    mock_pubsub_repo
+        .expect_insert_file_chunk_in_primary_file_queue()
+        .withf(|requeued_request| requeued_request.primary_file_chunk.id == "src-file-1234")
+        .times(1)
+        .returning(|_y| Ok(true));
+
+    mock_pubsub_repo
+        .expect_delete_file_chunk_claim_check_body()
+        .times(1)
+        .returning(|_y| Ok(true));
+
+    let sut = setup(mock_pubsub_repo, mock_file_recon_algo);
+
+    //act
+    let actual = sut.reconcile_claim_checked_file_chunk(claim_check).await;
+
+    //assert
+    assert_eq!(actual.is_ok(), true);
+}
+
+#[actix_web::test]
+async fn given_claim_check_whose_body_has_expired_returns_error_without_reconciling() {
+    //setup
+    let (mut mock_pubsub_repo, mut mock_file_recon_algo) = setup_dependencies();
+
+    mock_pubsub_repo
+        .expect_get_file_chunk_claim_check_body()
+        .times(1)
+        .returning(|_y| {
+            Err(AppError::new(
+                AppErrorKind::NotFound,
+                String::from("claim check has expired"),
+            ))
+        });
+
+    mock_file_recon_algo
+        .expect_reconcile_primary_file_chunk()
+        .times(0);
+
+    mock_pubsub_repo
+        .expect_delete_file_chunk_claim_check_body()
+        .times(0);
+
+    let sut = setup(mock_pubsub_repo, mock_file_recon_algo);
+
+    let claim_check = FileChunkClaimCheck {
+        claim_check_key: String::from("file-chunk-claim-checks:file-1234:1"),
+        content_type: String::from("application/json"),
+        primary_file_chunk_id: String::from("src-file-1234"),
+    };
+
+    //act
+    let actual = sut.reconcile_claim_checked_file_chunk(claim_check).await;
+
+    //assert
+    assert_eq!(actual.unwrap_err().kind, AppErrorKind::NotFound);
+}
 
 #[actix_web::test]
 async fn given_valid_request_but_call_to_dependency_fails_returns_error() {
@@ -124,12 +1364,106 @@ async fn given_valid_request_but_call_to_dependency_fails_returns_error() {
     let request = get_dummy_valid_request();
 
     //act
-    let actual = sut.reconcile_file_chunk(&request).await;
+    let actual = sut.reconcile_file_chunk(request).await;
 
     //assert
     assert_eq!(actual.is_err(), true);
 }
 
+//drives a primary file chunk through 2 comparison file chunks using the real algorithm.
+//both comparison chunks have a row with the same identifier as the primary row
+//but only the first one has the same amount
+async fn reconcile_against_two_comparison_chunks(
+    successful_row_handling: SuccessfulRowHandling,
+) -> ReconciledFileChunk {
+    let (mut mock_pubsub_repo, _) = setup_dependencies();
+
+    mock_pubsub_repo
+        .expect_get_next_comparison_file_upload_chunk()
+        .times(2)
+        .returning(|queue| {
+            let mut comparison_file_chunk = dummy_comparison_file();
+            match queue.last_acknowledged_id {
+                None => {
+                    comparison_file_chunk.id = String::from("1-0");
+                    comparison_file_chunk.chunk_rows = vec![parsed_chunk_row(1, "TXN-1,100")];
+                }
+                Some(_) => {
+                    comparison_file_chunk.id = String::from("2-0");
+                    comparison_file_chunk.chunk_rows = vec![parsed_chunk_row(1, "TXN-1,250")];
+                    comparison_file_chunk.is_last_chunk = true;
+                }
+            }
+            Ok(comparison_file_chunk)
+        });
+
+    mock_pubsub_repo
+        .expect_mark_comparison_file_chunk_as_processed()
+        .times(2)
+        .returning(|_y| Ok(true));
+
+    let requeued_requests = Arc::new(Mutex::new(vec![]));
+    let captured_requeued_requests = requeued_requests.clone();
+    mock_pubsub_repo
+        .expect_insert_file_chunk_in_primary_file_queue()
+        .times(1)
+        .returning(move |requeued_request| {
+            captured_requeued_requests
+                .lock()
+                .unwrap()
+                .push(requeued_request.clone());
+            Ok(true)
+        });
+
+    let reconciled_file_chunks = Arc::new(Mutex::new(vec![]));
+    let captured_reconciled_file_chunks = reconciled_file_chunks.clone();
+    mock_pubsub_repo
+        .expect_insert_file_chunk_into_recon_results_queue()
+        .times(1)
+        .returning(move |reconciled_file_chunk| {
+            captured_reconciled_file_chunks
+                .lock()
+                .unwrap()
+                .push(reconciled_file_chunk.clone());
+            Ok(true)
+        });
+
+    let sut = FileChunkReconciliationService {
+        pubsub_repo: mock_pubsub_repo,
+        file_reconciliation_algorithm: Box::new(GenericFileReconciliationAlgorithm {}),
+        match_rule_reconciliation_algorithm: Box::new(MatchRuleFileReconciliationAlgorithm {}),
+        multi_pass_reconciliation_algorithm: Box::new(
+            MockFileReconciliationAlgorithmInterface::new(),
+        ),
+        upload_progress_repo: setup_upload_progress_repo(),
+        chunk_payload_limits: ChunkPayloadLimits {
+            max_decompressed_bytes: 67_108_864,
+        },
+    };
+
+    let mut request = get_dummy_valid_request();
+    request.primary_file_chunk.chunk_rows = vec![parsed_chunk_row(1, "TXN-1,100")];
+    request.primary_file_chunk.comparison_pairs = vec![
+        new_same_column_index_comparison_pair(0),
+        ComparisonPair {
+            primary_file_column_index: 1,
+            comparison_file_column_index: 1,
+            is_row_identifier: false,
+        },
+    ];
+    request.reconciliation_options.successful_row_handling = successful_row_handling;
+
+    //first comparison file chunk
+    sut.reconcile_file_chunk(request).await.unwrap();
+
+    //the requeued primary file chunk meets the second and last comparison file chunk
+    let requeued_request = requeued_requests.lock().unwrap().remove(0);
+    sut.reconcile_file_chunk(requeued_request).await.unwrap();
+
+    let reconciled_file_chunk = reconciled_file_chunks.lock().unwrap().remove(0);
+    return reconciled_file_chunk;
+}
+
 fn setup_dependencies() -> (
     Box<MockPubSubRepositoryInterface>,
     Box<MockFileReconciliationAlgorithmInterface>,
@@ -143,10 +1477,116 @@ fn setup_dependencies() -> (
 fn setup(
     mock_pubsub_repo: Box<MockPubSubRepositoryInterface>,
     mock_file_recon_algo: Box<MockFileReconciliationAlgorithmInterface>,
+) -> FileChunkReconciliationService {
+    return setup_with_upload_progress_repo(
+        mock_pubsub_repo,
+        mock_file_recon_algo,
+        setup_upload_progress_repo(),
+    );
+}
+
+//progress is recorded for every chunk, so tests that are not about it accept any progress
+fn setup_upload_progress_repo() -> Box<MockUploadProgressRepositoryInterface> {
+    let mut mock_upload_progress_repo = Box::new(MockUploadProgressRepositoryInterface::new());
+
+    mock_upload_progress_repo
+        .expect_record_primary_file_chunk_progress()
+        .returning(|_w, _x, _y, _z| Ok(()));
+
+    mock_upload_progress_repo
+        .expect_record_completed_primary_file_chunk()
+        .returning(|_w, _x, _y, _z| Ok(UploadProgress::default()));
+
+    return mock_upload_progress_repo;
+}
+
+//a primary file chunk that meets the last comparison file chunk and goes to the recon results queue
+fn setup_dependencies_for_the_last_comparison_file_chunk(
+    times: usize,
+) -> (
+    Box<MockPubSubRepositoryInterface>,
+    Box<MockFileReconciliationAlgorithmInterface>,
+) {
+    let (mut mock_pubsub_repo, mut mock_file_recon_algo) = setup_dependencies();
+
+    mock_pubsub_repo
+        .expect_get_next_comparison_file_upload_chunk()
+        .times(times)
+        .returning(|_y| {
+            let mut comparison_file_chunk = dummy_comparison_file();
+            comparison_file_chunk.chunk_sequence_number = 2;
+            comparison_file_chunk.is_last_chunk = true;
+            Ok(comparison_file_chunk)
+        });
+
+    mock_file_recon_algo
+        .expect_reconcile_primary_file_chunk()
+        .times(times)
+        .returning(|_y, _x, _w, _v| Ok(()));
+
+    mock_pubsub_repo
+        .expect_mark_comparison_file_chunk_as_processed()
+        .times(times)
+        .returning(|_y| Ok(true));
+
+    mock_pubsub_repo
+        .expect_insert_file_chunk_into_recon_results_queue()
+        .times(times)
+        .returning(|_y| Ok(true));
+
+    return (mock_pubsub_repo, mock_file_recon_algo);
+}
+
+//an upload request of 2 primary file chunks that have both been through 2 comparison file chunks
+fn setup_completed_upload_progress_repo() -> Box<MockUploadProgressRepositoryInterface> {
+    let mut mock_upload_progress_repo = Box::new(MockUploadProgressRepositoryInterface::new());
+
+    mock_upload_progress_repo
+        .expect_record_primary_file_chunk_progress()
+        .returning(|_w, _x, _y, _z| Ok(()));
+
+    mock_upload_progress_repo
+        .expect_record_completed_primary_file_chunk()
+        .times(1)
+        .returning(|_w, _x, _y, _z| {
+            Ok(UploadProgress {
+                primary_file_chunks: HashMap::from([(1, 2), (2, 2)]),
+                completed_primary_file_chunks: HashMap::from([
+                    (
+                        1,
+                        HashMap::from([
+                            (String::from("Successful"), 3),
+                            (String::from("Failed"), 2),
+                        ]),
+                    ),
+                    (2, HashMap::from([(String::from("Successful"), 2)])),
+                ]),
+                last_primary_chunk_sequence_number: Some(2),
+                first_chunk_sequence_number: 1,
+            })
+        });
+
+    return mock_upload_progress_repo;
+}
+
+fn setup_with_upload_progress_repo(
+    mock_pubsub_repo: Box<MockPubSubRepositoryInterface>,
+    mock_file_recon_algo: Box<MockFileReconciliationAlgorithmInterface>,
+    mock_upload_progress_repo: Box<MockUploadProgressRepositoryInterface>,
 ) -> FileChunkReconciliationService {
     let sut = FileChunkReconciliationService {
         pubsub_repo: mock_pubsub_repo,
         file_reconciliation_algorithm: mock_file_recon_algo,
+        match_rule_reconciliation_algorithm: Box::new(
+            MockFileReconciliationAlgorithmInterface::new(),
+        ),
+        multi_pass_reconciliation_algorithm: Box::new(
+            MockFileReconciliationAlgorithmInterface::new(),
+        ),
+        upload_progress_repo: mock_upload_progress_repo,
+        chunk_payload_limits: ChunkPayloadLimits {
+            max_decompressed_bytes: 67_108_864,
+        },
     };
     return sut;
 }
@@ -178,6 +1618,8 @@ fn get_dummy_valid_request() -> ReconcileFileChunkRequest {
             },
             is_last_chunk: false,
         },
+        reconciliation_options: ReconciliationOptions::default(),
+        rows_reconciliation_details: vec![],
     }
 }
 
@@ -198,9 +1640,9 @@ fn new_same_column_index_comparison_pair(column_index: usize) -> ComparisonPair
     }
 }
 
-fn dummy_reconciled_file_upload_chunk() -> FileUploadChunk {
+fn dummy_comparison_file() -> FileUploadChunk {
     FileUploadChunk {
-        id: String::from("src-file-1234"),
+        id: String::from("cmp-file-1234"),
         upload_request_id: String::from("file-1234"),
         chunk_sequence_number: 1,
         chunk_source: FileUploadChunkSource::ComparisonFileChunk,
@@ -226,30 +1668,54 @@ fn dummy_reconciled_file_upload_chunk() -> FileUploadChunk {
     }
 }
 
-fn dummy_comparison_file() -> FileUploadChunk {
-    FileUploadChunk {
-        id: String::from("cmp-file-1234"),
-        upload_request_id: String::from("file-1234"),
-        chunk_sequence_number: 1,
-        chunk_source: FileUploadChunkSource::ComparisonFileChunk,
-        chunk_rows: vec![],
-        date_created: chrono::Utc::now().timestamp(),
-        date_modified: chrono::Utc::now().timestamp(),
-        comparison_pairs: vec![new_same_column_index_comparison_pair(0)],
-        column_headers: vec![],
-        recon_config: default_recon_configs(),
-        primary_file_chunks_queue: FileChunkQueue {
-            topic_id: String::from("src-file-chunks-queue-1"),
-            last_acknowledged_id: Option::None,
-        },
-        comparison_file_chunks_queue: FileChunkQueue {
-            topic_id: String::from("cmp-file-chunks-queue-1"),
-            last_acknowledged_id: Option::None,
-        },
-        result_chunks_queue: FileChunkQueue {
-            topic_id: String::from("results-file-chunks-queue-1"),
-            last_acknowledged_id: Option::None,
-        },
-        is_last_chunk: false,
+fn unparsed_chunk_row(row_number: u64, raw_data: &str) -> FileUploadChunkRow {
+    FileUploadChunkRow {
+        row_number,
+        raw_data: raw_data.to_string(),
+        parsed_columns_from_row: vec![],
+        recon_result: ReconStatus::Pending,
+        recon_result_reasons: vec![],
+    }
+}
+
+fn parsed_chunk_row(row_number: u64, raw_data: &str) -> FileUploadChunkRow {
+    FileUploadChunkRow {
+        row_number,
+        raw_data: raw_data.to_string(),
+        parsed_columns_from_row: raw_data.split(',').map(|s| s.to_string()).collect(),
+        recon_result: ReconStatus::Pending,
+        recon_result_reasons: vec![],
+    }
+}
+
+fn fixed_width_column(start: usize, length: usize) -> FixedWidthColumn {
+    FixedWidthColumn { start, length }
+}
+
+fn build_column_headers(header: &str) -> Vec<String> {
+    header.split(',').map(|s| s.to_string()).collect()
+}
+
+fn header_name_comparison_pair(
+    primary_file_column_header: &str,
+    comparison_file_column_header: &str,
+) -> HeaderNameComparisonPair {
+    HeaderNameComparisonPair {
+        primary_file_column_header: primary_file_column_header.to_string(),
+        comparison_file_column_header: comparison_file_column_header.to_string(),
+        is_row_identifier: false,
+        options: ComparisonPairOptions::default(),
+    }
+}
+
+fn matched_row_details(row_number: u64) -> RowReconciliationDetails {
+    RowReconciliationDetails {
+        row_number,
+        recon_outcome: ReconOutcome::Matched,
+        matched_comparison_row_number: Some(row_number),
+        matched_comparison_file_chunk_id: Some(String::from("1526919030474-55")),
+        matched_comparison_upload_request_id: Some(String::from("cmp-file-1234")),
+        match_pass_name: None,
+        recon_result_reasons: vec![],
     }
 }
@@ -0,0 +1,85 @@
+use async_trait::async_trait;
+use std::{
+    future::Future,
+    time::{Duration, Instant},
+};
+
+use crate::internal::{
+    interfaces::{
+        health_check_service::HealthCheckServiceInterface,
+        pubsub_repository::PubSubRepositoryInterface,
+    },
+    models::view_models::responses::health_check_response::{DependencyStatus, ReadinessResponse},
+    shared_reconciler_rust_libraries::models::entities::app_errors::AppError,
+};
+
+pub struct HealthCheckService {
+    pub pubsub_repo: Box<dyn PubSubRepositoryInterface>,
+
+    //how long a dependency has to answer before it is reported as not ready
+    pub dependency_check_timeout: Duration,
+}
+
+#[async_trait]
+impl HealthCheckServiceInterface for HealthCheckService {
+    async fn check_readiness(&self) -> ReadinessResponse {
+        //the dependencies are checked at the same time
+        //so a slow one does not hold up the report on the other
+        let (redis_status, dapr_status) = tokio::join!(
+            self.check_dependency(String::from("redis"), async {
+                let reply = self
+                    .pubsub_repo
+                    .ping_redis(self.dependency_check_timeout)
+                    .await?;
+                return Ok((true, format!("redis replied [{}]", reply)));
+            }),
+            self.check_dependency(String::from("dapr"), async {
+                let is_pubsub_component_loaded =
+                    self.pubsub_repo.is_dapr_pubsub_component_loaded().await?;
+                return Ok(match is_pubsub_component_loaded {
+                    true => (true, String::from("the pubsub component is loaded")),
+                    false => (false, String::from("the pubsub component is not loaded")),
+                });
+            }),
+        );
+
+        let dependencies = vec![redis_status, dapr_status];
+
+        return ReadinessResponse {
+            is_ready: dependencies.iter().all(|dependency| dependency.is_ready),
+            dependencies,
+        };
+    }
+}
+
+impl HealthCheckService {
+    //runs the check of a dependency, which resolves to whether the dependency is ready
+    //and what was found, reporting errors and timeouts as the dependency not being ready
+    async fn check_dependency(
+        &self,
+        name: String,
+        dependency_check: impl Future<Output = Result<(bool, String), AppError>>,
+    ) -> DependencyStatus {
+        let started_at = Instant::now();
+
+        let (is_ready, details) =
+            match tokio::time::timeout(self.dependency_check_timeout, dependency_check).await {
+                Ok(Ok((is_ready, details))) => (is_ready, details),
+                Ok(Err(error)) => (false, error.to_string()),
+                Err(_) => (
+                    false,
+                    format!(
+                        "no answer within [{}] milliseconds",
+                        self.dependency_check_timeout.as_millis()
+                    ),
+                ),
+            };
+
+        return DependencyStatus {
+            name,
+            is_ready,
+            details,
+            response_time_in_milliseconds: started_at.elapsed().as_millis() as u64,
+        };
+    }
+}
@@ -0,0 +1,133 @@
+use std::time::Duration;
+
+use crate::internal::{
+    interfaces::{
+        health_check_service::HealthCheckServiceInterface,
+        pubsub_repository::MockPubSubRepositoryInterface,
+    },
+    shared_reconciler_rust_libraries::models::entities::app_errors::{AppError, AppErrorKind},
+};
+
+use super::health_check_service::HealthCheckService;
+
+#[actix_web::test]
+async fn test_check_readiness_given_reachable_dependencies_returns_ready() {
+    //setup
+    let mut mock_pubsub_repo = Box::new(MockPubSubRepositoryInterface::new());
+
+    mock_pubsub_repo
+        .expect_ping_redis()
+        .times(1)
+        .returning(|_| Ok(String::from("PONG")));
+
+    mock_pubsub_repo
+        .expect_is_dapr_pubsub_component_loaded()
+        .times(1)
+        .returning(|| Ok(true));
+
+    let sut = setup(mock_pubsub_repo);
+
+    //act
+    let actual = sut.check_readiness().await;
+
+    //assert
+    assert_eq!(actual.is_ready, true);
+    assert_eq!(actual.dependencies[0].name, "redis");
+    assert_eq!(actual.dependencies[0].is_ready, true);
+    assert_eq!(actual.dependencies[0].details, "redis replied [PONG]");
+    assert_eq!(actual.dependencies[1].name, "dapr");
+    assert_eq!(actual.dependencies[1].is_ready, true);
+}
+
+#[actix_web::test]
+async fn test_check_readiness_given_unreachable_redis_reports_why_it_is_not_ready() {
+    //setup
+    let mut mock_pubsub_repo = Box::new(MockPubSubRepositoryInterface::new());
+
+    mock_pubsub_repo.expect_ping_redis().times(1).returning(|_| {
+        Err(AppError::new(
+            AppErrorKind::ConnectionError,
+            String::from("Connection refused"),
+        ))
+    });
+
+    mock_pubsub_repo
+        .expect_is_dapr_pubsub_component_loaded()
+        .times(1)
+        .returning(|| Ok(true));
+
+    let sut = setup(mock_pubsub_repo);
+
+    //act
+    let actual = sut.check_readiness().await;
+
+    //assert
+    assert_eq!(actual.is_ready, false);
+    assert_eq!(actual.dependencies[0].is_ready, false);
+    assert!(actual.dependencies[0]
+        .details
+        .contains("Connection refused"));
+    assert_eq!(actual.dependencies[1].is_ready, true);
+}
+
+#[actix_web::test]
+async fn test_check_readiness_given_dapr_without_the_pubsub_component_returns_not_ready() {
+    //setup
+    let mut mock_pubsub_repo = Box::new(MockPubSubRepositoryInterface::new());
+
+    mock_pubsub_repo
+        .expect_ping_redis()
+        .times(1)
+        .returning(|_| Ok(String::from("PONG")));
+
+    mock_pubsub_repo
+        .expect_is_dapr_pubsub_component_loaded()
+        .times(1)
+        .returning(|| Ok(false));
+
+    let sut = setup(mock_pubsub_repo);
+
+    //act
+    let actual = sut.check_readiness().await;
+
+    //assert
+    assert_eq!(actual.is_ready, false);
+    assert_eq!(actual.dependencies[1].is_ready, false);
+    assert_eq!(
+        actual.dependencies[1].details,
+        "the pubsub component is not loaded"
+    );
+}
+
+#[actix_web::test]
+async fn test_check_readiness_passes_the_dependency_check_timeout_to_the_redis_ping() {
+    //setup
+    let mut mock_pubsub_repo = Box::new(MockPubSubRepositoryInterface::new());
+
+    mock_pubsub_repo
+        .expect_ping_redis()
+        .withf(|timeout| *timeout == Duration::from_millis(250))
+        .times(1)
+        .returning(|_| Ok(String::from("PONG")));
+
+    mock_pubsub_repo
+        .expect_is_dapr_pubsub_component_loaded()
+        .times(1)
+        .returning(|| Ok(true));
+
+    let mut sut = setup(mock_pubsub_repo);
+    sut.dependency_check_timeout = Duration::from_millis(250);
+
+    //act
+    let actual = sut.check_readiness().await;
+
+    //assert
+    assert_eq!(actual.is_ready, true);
+}
+
+fn setup(mock_pubsub_repo: Box<MockPubSubRepositoryInterface>) -> HealthCheckService {
+    HealthCheckService {
+        pubsub_repo: mock_pubsub_repo,
+        dependency_check_timeout: Duration::from_secs(2),
+    }
+}
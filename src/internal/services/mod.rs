@@ -1,6 +1,21 @@
 pub mod core_logic;
 pub mod file_chunk_reconciliation_service;
+pub mod health_check_service;
+pub mod upload_progress_service;
 
 #[cfg(test)]
 #[path = "./file_chunk_reconciliation_service_tests.rs"]
 mod file_chunk_reconciliation_service_tests;
+
+//the benchmark replaces the global allocator of the test binary, so it is only built when asked for
+#[cfg(all(test, feature = "memory-benchmarks"))]
+#[path = "./file_chunk_reconciliation_service_benchmarks.rs"]
+mod file_chunk_reconciliation_service_benchmarks;
+
+#[cfg(test)]
+#[path = "./health_check_service_tests.rs"]
+mod health_check_service_tests;
+
+#[cfg(test)]
+#[path = "./upload_progress_service_tests.rs"]
+mod upload_progress_service_tests;
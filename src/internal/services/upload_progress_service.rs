@@ -0,0 +1,61 @@
+use async_trait::async_trait;
+
+use crate::internal::{
+    interfaces::{
+        upload_progress_repository::UploadProgressRepositoryInterface,
+        upload_progress_service::UploadProgressServiceInterface,
+    },
+    models::view_models::responses::upload_progress_response::UploadProgressResponse,
+    shared_reconciler_rust_libraries::models::entities::app_errors::{AppError, AppErrorKind},
+};
+
+pub struct UploadProgressService {
+    pub upload_progress_repo: Box<dyn UploadProgressRepositoryInterface>,
+}
+
+#[async_trait]
+impl UploadProgressServiceInterface for UploadProgressService {
+    /**
+    sums up the progress recorded for the primary file chunks of an upload request
+
+    # Errors
+
+    This function will return a not found error if no primary file chunk of the upload request
+    has been reconciled yet, or an error if the progress cannot be read.
+    */
+    async fn get_upload_progress(
+        &self,
+        upload_request_id: String,
+    ) -> Result<UploadProgressResponse, AppError> {
+        let upload_progress = self
+            .upload_progress_repo
+            .get_upload_progress(&upload_request_id)
+            .await?;
+
+        //a primary file chunk is only recorded once it has been reconciled,
+        //so an upload request still waiting for its first chunk looks like one that never existed
+        if upload_progress.primary_file_chunks.is_empty()
+            && upload_progress.completed_primary_file_chunks.is_empty()
+        {
+            return Err(AppError::new(
+                AppErrorKind::NotFound,
+                format!(
+                    "no progress has been recorded for upload request [{}]",
+                    upload_request_id
+                ),
+            ));
+        }
+
+        return Ok(UploadProgressResponse {
+            upload_request_id,
+            primary_file_chunks_seen: upload_progress.primary_file_chunks.len() as u64,
+            primary_file_chunks_completed: upload_progress.completed_primary_file_chunks.len()
+                as u64,
+            primary_file_chunks_total: upload_progress.get_primary_file_chunks_total(),
+            is_completed: upload_progress.is_completed(),
+            comparison_file_chunks_consumed: upload_progress
+                .count_comparison_file_chunks_consumed(),
+            rows_by_recon_status: upload_progress.count_completed_rows_by_recon_status(),
+        });
+    }
+}
@@ -0,0 +1,252 @@
+use std::collections::HashMap;
+
+use crate::internal::{
+    interfaces::{
+        upload_progress_repository::MockUploadProgressRepositoryInterface,
+        upload_progress_service::UploadProgressServiceInterface,
+    },
+    models::entities::upload_progress::UploadProgress,
+    shared_reconciler_rust_libraries::models::entities::app_errors::{AppError, AppErrorKind},
+};
+
+use super::upload_progress_service::UploadProgressService;
+
+#[actix_web::test]
+async fn test_get_upload_progress_sums_up_the_progress_of_every_primary_file_chunk() {
+    //setup
+    let mut mock_upload_progress_repo = Box::new(MockUploadProgressRepositoryInterface::new());
+
+    mock_upload_progress_repo
+        .expect_get_upload_progress()
+        .withf(|upload_request_id| upload_request_id == "file-1234")
+        .times(1)
+        .returning(|_x| {
+            Ok(UploadProgress {
+                primary_file_chunks: HashMap::from([(1, 4), (2, 4), (3, 2)]),
+                completed_primary_file_chunks: HashMap::from([
+                    (
+                        1,
+                        HashMap::from([
+                            (String::from("Successful"), 90),
+                            (String::from("Failed"), 10),
+                        ]),
+                    ),
+                    (2, HashMap::from([(String::from("Successful"), 100)])),
+                ]),
+                last_primary_chunk_sequence_number: Some(3),
+                first_chunk_sequence_number: 1,
+            })
+        });
+
+    let sut = setup(mock_upload_progress_repo);
+
+    //act
+    let actual = sut
+        .get_upload_progress(String::from("file-1234"))
+        .await
+        .unwrap();
+
+    //assert
+    assert_eq!(actual.upload_request_id, "file-1234");
+    assert_eq!(actual.primary_file_chunks_seen, 3);
+    assert_eq!(actual.primary_file_chunks_completed, 2);
+    assert_eq!(actual.primary_file_chunks_total, Some(3));
+    assert_eq!(actual.is_completed, false);
+    assert_eq!(actual.comparison_file_chunks_consumed, 10);
+    assert_eq!(
+        actual.rows_by_recon_status,
+        HashMap::from([
+            (String::from("Successful"), 190),
+            (String::from("Failed"), 10),
+        ])
+    );
+}
+
+#[actix_web::test]
+async fn test_get_upload_progress_given_every_primary_file_chunk_completed_is_completed() {
+    //setup
+    let mut mock_upload_progress_repo = Box::new(MockUploadProgressRepositoryInterface::new());
+
+    mock_upload_progress_repo
+        .expect_get_upload_progress()
+        .times(1)
+        .returning(|_x| {
+            Ok(UploadProgress {
+                primary_file_chunks: HashMap::from([(1, 2), (2, 2)]),
+                completed_primary_file_chunks: HashMap::from([
+                    (1, HashMap::from([(String::from("Successful"), 100)])),
+                    (2, HashMap::from([(String::from("Successful"), 50)])),
+                ]),
+                last_primary_chunk_sequence_number: Some(2),
+                first_chunk_sequence_number: 1,
+            })
+        });
+
+    let sut = setup(mock_upload_progress_repo);
+
+    //act
+    let actual = sut
+        .get_upload_progress(String::from("file-1234"))
+        .await
+        .unwrap();
+
+    //assert
+    assert_eq!(actual.primary_file_chunks_total, Some(2));
+    assert_eq!(actual.is_completed, true);
+}
+
+#[actix_web::test]
+async fn test_get_upload_progress_given_chunks_numbered_from_zero_counts_every_chunk() {
+    //setup
+    let mut mock_upload_progress_repo = Box::new(MockUploadProgressRepositoryInterface::new());
+
+    mock_upload_progress_repo
+        .expect_get_upload_progress()
+        .times(1)
+        .returning(|_x| {
+            Ok(UploadProgress {
+                primary_file_chunks: HashMap::from([(0, 1), (1, 1), (2, 1)]),
+                completed_primary_file_chunks: HashMap::from([
+                    (1, HashMap::from([(String::from("Successful"), 100)])),
+                    (2, HashMap::from([(String::from("Successful"), 50)])),
+                ]),
+                last_primary_chunk_sequence_number: Some(2),
+                first_chunk_sequence_number: 0,
+            })
+        });
+
+    let sut = setup(mock_upload_progress_repo);
+
+    //act
+    let actual = sut
+        .get_upload_progress(String::from("file-1234"))
+        .await
+        .unwrap();
+
+    //assert
+    //the chunk numbered zero is still being reconciled, so the upload is not completed
+    assert_eq!(actual.primary_file_chunks_total, Some(3));
+    assert_eq!(actual.primary_file_chunks_completed, 2);
+    assert_eq!(actual.is_completed, false);
+    assert_eq!(actual.comparison_file_chunks_consumed, 6);
+}
+
+#[actix_web::test]
+async fn test_get_upload_progress_given_every_chunk_numbered_from_zero_completed_is_completed() {
+    //setup
+    let mut mock_upload_progress_repo = Box::new(MockUploadProgressRepositoryInterface::new());
+
+    mock_upload_progress_repo
+        .expect_get_upload_progress()
+        .times(1)
+        .returning(|_x| {
+            Ok(UploadProgress {
+                primary_file_chunks: HashMap::from([(0, 0), (1, 0)]),
+                completed_primary_file_chunks: HashMap::from([
+                    (0, HashMap::from([(String::from("Successful"), 100)])),
+                    (1, HashMap::from([(String::from("Failed"), 50)])),
+                ]),
+                last_primary_chunk_sequence_number: Some(1),
+                first_chunk_sequence_number: 0,
+            })
+        });
+
+    let sut = setup(mock_upload_progress_repo);
+
+    //act
+    let actual = sut
+        .get_upload_progress(String::from("file-1234"))
+        .await
+        .unwrap();
+
+    //assert
+    assert_eq!(actual.primary_file_chunks_total, Some(2));
+    assert_eq!(actual.is_completed, true);
+    assert_eq!(actual.comparison_file_chunks_consumed, 2);
+}
+
+#[actix_web::test]
+async fn test_get_upload_progress_given_the_first_chunk_not_yet_recorded_is_not_completed() {
+    //setup
+    let mut mock_upload_progress_repo = Box::new(MockUploadProgressRepositoryInterface::new());
+
+    //chunks 1 and 2 moved past comparison chunk 0 before chunk 0 was recorded,
+    //so no chunk numbered zero is left in the progress
+    mock_upload_progress_repo
+        .expect_get_upload_progress()
+        .times(1)
+        .returning(|_x| {
+            Ok(UploadProgress {
+                primary_file_chunks: HashMap::from([(1, 1), (2, 1)]),
+                completed_primary_file_chunks: HashMap::from([
+                    (1, HashMap::from([(String::from("Successful"), 100)])),
+                    (2, HashMap::from([(String::from("Successful"), 50)])),
+                ]),
+                last_primary_chunk_sequence_number: Some(2),
+                first_chunk_sequence_number: 0,
+            })
+        });
+
+    let sut = setup(mock_upload_progress_repo);
+
+    //act
+    let actual = sut
+        .get_upload_progress(String::from("file-1234"))
+        .await
+        .unwrap();
+
+    //assert
+    assert_eq!(actual.primary_file_chunks_total, Some(3));
+    assert_eq!(actual.is_completed, false);
+}
+
+#[actix_web::test]
+async fn test_get_upload_progress_given_nothing_recorded_returns_not_found() {
+    //setup
+    let mut mock_upload_progress_repo = Box::new(MockUploadProgressRepositoryInterface::new());
+
+    mock_upload_progress_repo
+        .expect_get_upload_progress()
+        .times(1)
+        .returning(|_x| Ok(UploadProgress::default()));
+
+    let sut = setup(mock_upload_progress_repo);
+
+    //act
+    let actual = sut.get_upload_progress(String::from("file-1234")).await;
+
+    //assert
+    assert_eq!(actual.unwrap_err().kind, AppErrorKind::NotFound);
+}
+
+#[actix_web::test]
+async fn test_get_upload_progress_given_unreachable_redis_returns_its_error() {
+    //setup
+    let mut mock_upload_progress_repo = Box::new(MockUploadProgressRepositoryInterface::new());
+
+    mock_upload_progress_repo
+        .expect_get_upload_progress()
+        .times(1)
+        .returning(|_x| {
+            Err(AppError::new(
+                AppErrorKind::ConnectionError,
+                String::from("Connection refused"),
+            ))
+        });
+
+    let sut = setup(mock_upload_progress_repo);
+
+    //act
+    let actual = sut.get_upload_progress(String::from("file-1234")).await;
+
+    //assert
+    assert_eq!(actual.unwrap_err().kind, AppErrorKind::ConnectionError);
+}
+
+fn setup(
+    mock_upload_progress_repo: Box<MockUploadProgressRepositoryInterface>,
+) -> UploadProgressService {
+    UploadProgressService {
+        upload_progress_repo: mock_upload_progress_repo,
+    }
+}
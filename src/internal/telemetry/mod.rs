@@ -0,0 +1,6 @@
+pub mod trace_context;
+pub mod tracing_setup;
+
+#[cfg(test)]
+#[path = "./trace_context_tests.rs"]
+mod trace_context_tests;
@@ -0,0 +1,83 @@
+use opentelemetry::{
+    propagation::TextMapPropagator, sdk::propagation::TraceContextPropagator,
+    trace::TraceContextExt,
+};
+use std::collections::HashMap;
+use tracing::Span;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+//the headers the w3c trace context is carried in
+pub const TRACEPARENT_HEADER: &str = "traceparent";
+pub const TRACESTATE_HEADER: &str = "tracestate";
+
+//makes the span part of the trace the w3c trace context belongs to.
+//without a traceparent the span is left to start a trace of its own
+pub fn set_parent_trace_context(span: &Span, traceparent: Option<&str>, tracestate: Option<&str>) {
+    let carrier = build_trace_context_carrier(traceparent, tracestate);
+
+    if !carrier.contains_key(TRACEPARENT_HEADER) {
+        return;
+    }
+
+    span.set_parent(TraceContextPropagator::new().extract(&carrier));
+}
+
+//links the span to the trace another message was published in,
+//such as that of a comparison file chunk read off its stream
+pub fn add_trace_context_link(span: &Span, traceparent: &str, tracestate: &str) {
+    let carrier = build_trace_context_carrier(Some(traceparent), Some(tracestate));
+    let context = TraceContextPropagator::new().extract(&carrier);
+    let span_context = context.span().span_context().clone();
+
+    //messages published outside a trace have an empty or malformed traceparent
+    if span_context.is_valid() {
+        span.add_link(span_context);
+    }
+}
+
+//the w3c trace context of the span, keyed by the header it is carried in
+pub fn get_trace_context(span: &Span) -> HashMap<String, String> {
+    let mut carrier = HashMap::new();
+    TraceContextPropagator::new().inject_context(&span.context(), &mut carrier);
+    return carrier;
+}
+
+//the publish metadata that has dapr write the trace context of the span
+//into the cloud event instead of the trace context of the sidecar
+pub fn get_cloud_event_trace_context_metadata(span: &Span) -> HashMap<String, String> {
+    let trace_context = get_trace_context(span);
+    let mut metadata = HashMap::new();
+
+    //dapr fills the traceid of a cloud event with the traceparent, so both are set
+    if let Some(traceparent) = trace_context.get(TRACEPARENT_HEADER) {
+        metadata.insert(String::from("cloudevent.traceid"), traceparent.clone());
+        metadata.insert(String::from("cloudevent.traceparent"), traceparent.clone());
+    }
+
+    if let Some(tracestate) = trace_context.get(TRACESTATE_HEADER) {
+        metadata.insert(String::from("cloudevent.tracestate"), tracestate.clone());
+    }
+
+    return metadata;
+}
+
+fn build_trace_context_carrier(
+    traceparent: Option<&str>,
+    tracestate: Option<&str>,
+) -> HashMap<String, String> {
+    let mut carrier = HashMap::new();
+
+    for (header, value) in [
+        (TRACEPARENT_HEADER, traceparent),
+        (TRACESTATE_HEADER, tracestate),
+    ] {
+        match value {
+            Some(value) if !value.trim().is_empty() => {
+                carrier.insert(header.to_string(), value.trim().to_string());
+            }
+            _ => (),
+        }
+    }
+
+    return carrier;
+}
@@ -0,0 +1,88 @@
+use opentelemetry::{sdk::trace::TracerProvider, trace::TracerProvider as _};
+use tracing::{subscriber::with_default, Span};
+use tracing_subscriber::layer::SubscriberExt;
+
+use super::trace_context::{
+    get_cloud_event_trace_context_metadata, get_trace_context, set_parent_trace_context,
+    TRACEPARENT_HEADER, TRACESTATE_HEADER,
+};
+
+const TRACEPARENT: &str = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+const TRACE_ID: &str = "4bf92f3577b34da6a3ce929d0e0e4736";
+const TRACESTATE: &str = "congo=t61rcWkgMzE";
+
+#[actix_web::test]
+async fn test_set_parent_trace_context_given_traceparent_continues_its_trace() {
+    //setup
+    let tracer_provider = TracerProvider::builder().build();
+
+    with_default(build_subscriber(&tracer_provider), || {
+        let span = tracing::info_span!("reconcile_file_chunk");
+
+        //act
+        set_parent_trace_context(&span, Some(TRACEPARENT), Some(TRACESTATE));
+
+        //assert
+        let actual = get_trace_context(&span);
+        let actual_traceparent = &actual[TRACEPARENT_HEADER];
+        assert!(actual_traceparent.contains(TRACE_ID));
+        assert_ne!(actual_traceparent, TRACEPARENT);
+        assert_eq!(actual[TRACESTATE_HEADER], TRACESTATE);
+    });
+}
+
+#[actix_web::test]
+async fn test_set_parent_trace_context_given_no_traceparent_starts_a_new_trace() {
+    //setup
+    let tracer_provider = TracerProvider::builder().build();
+
+    with_default(build_subscriber(&tracer_provider), || {
+        let span = tracing::info_span!("reconcile_file_chunk");
+
+        //act
+        set_parent_trace_context(&span, None, Some(""));
+
+        //assert
+        let actual = get_trace_context(&span);
+        assert!(actual.contains_key(TRACEPARENT_HEADER));
+        assert!(!actual[TRACEPARENT_HEADER].contains(TRACE_ID));
+    });
+}
+
+#[actix_web::test]
+async fn test_get_cloud_event_trace_context_metadata_writes_the_trace_context_of_the_span() {
+    //setup
+    let tracer_provider = TracerProvider::builder().build();
+
+    with_default(build_subscriber(&tracer_provider), || {
+        let parent_span = tracing::info_span!("reconcile_file_chunk");
+        set_parent_trace_context(&parent_span, Some(TRACEPARENT), Some(TRACESTATE));
+        let span = tracing::info_span!(parent: &parent_span, "requeue_primary_file_chunk");
+
+        //act
+        let actual = get_cloud_event_trace_context_metadata(&span);
+
+        //assert
+        let span_traceparent = get_trace_context(&span)[TRACEPARENT_HEADER].clone();
+        assert!(span_traceparent.contains(TRACE_ID));
+        assert_eq!(actual["cloudevent.traceparent"], span_traceparent);
+        assert_eq!(actual["cloudevent.traceid"], span_traceparent);
+        assert_eq!(actual["cloudevent.tracestate"], TRACESTATE);
+    });
+}
+
+#[actix_web::test]
+async fn test_get_cloud_event_trace_context_metadata_given_span_outside_a_trace_returns_no_metadata(
+) {
+    //act
+    let actual = get_cloud_event_trace_context_metadata(&Span::none());
+
+    //assert
+    assert_eq!(actual.is_empty(), true);
+}
+
+fn build_subscriber(tracer_provider: &TracerProvider) -> impl tracing::Subscriber {
+    return tracing_subscriber::registry().with(
+        tracing_opentelemetry::layer().with_tracer(tracer_provider.tracer("trace-context-tests")),
+    );
+}
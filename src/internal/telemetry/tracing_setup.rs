@@ -0,0 +1,76 @@
+use opentelemetry::{
+    global,
+    sdk::{
+        trace::{self as sdk_trace, Tracer},
+        Resource,
+    },
+    trace::TracerProvider,
+    KeyValue,
+};
+use opentelemetry_otlp::WithExportConfig;
+use tracing_subscriber::{fmt, layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
+
+use crate::internal::shared_reconciler_rust_libraries::models::entities::app_errors::{
+    AppError, AppErrorKind,
+};
+
+//used when RUST_LOG is not set
+const DEFAULT_LOG_FILTER: &str = "info";
+
+//sets up json logs and the spans around the chunk loop.
+//spans are only exported when an otlp endpoint is given, but they are always kept
+//so that the trace context of a request can be passed on in what we publish
+pub fn init_tracing(service_name: &str, otlp_endpoint: Option<&str>) -> Result<(), AppError> {
+    let tracer = match otlp_endpoint {
+        Some(otlp_endpoint) => build_otlp_tracer(service_name, otlp_endpoint)?,
+        None => build_unexported_tracer(service_name),
+    };
+
+    let log_filter =
+        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(DEFAULT_LOG_FILTER));
+
+    return tracing_subscriber::registry()
+        .with(log_filter)
+        .with(fmt::layer().json().with_current_span(true))
+        .with(tracing_opentelemetry::layer().with_tracer(tracer))
+        .try_init()
+        .map_err(|e| AppError::new(AppErrorKind::InternalError, e.to_string()));
+}
+
+//sends the spans that are still buffered to the otlp endpoint
+pub fn shutdown_tracing() {
+    global::shutdown_tracer_provider();
+}
+
+fn build_otlp_tracer(service_name: &str, otlp_endpoint: &str) -> Result<Tracer, AppError> {
+    return opentelemetry_otlp::new_pipeline()
+        .tracing()
+        .with_exporter(
+            opentelemetry_otlp::new_exporter()
+                .tonic()
+                .with_endpoint(otlp_endpoint),
+        )
+        .with_trace_config(sdk_trace::config().with_resource(build_resource(service_name)))
+        .install_batch(opentelemetry::runtime::Tokio)
+        .map_err(|e| AppError::new(AppErrorKind::ConnectionError, e.to_string()));
+}
+
+fn build_unexported_tracer(service_name: &str) -> Tracer {
+    let tracer_provider = sdk_trace::TracerProvider::builder()
+        .with_config(sdk_trace::config().with_resource(build_resource(service_name)))
+        .build();
+
+    let tracer = tracer_provider.tracer(service_name.to_string());
+
+    //the tracer only holds on to its provider weakly, so the provider is kept globally
+    global::set_tracer_provider(tracer_provider);
+
+    return tracer;
+}
+
+fn build_resource(service_name: &str) -> Resource {
+    return Resource::new(vec![KeyValue::new(
+        "service.name",
+        service_name.to_string(),
+    )]);
+}
@@ -1,25 +1,198 @@
 use crate::internal::interfaces::file_chunk_reconciliation_service::FileChunkReconciliationServiceInterface;
+use crate::internal::interfaces::health_check_service::HealthCheckServiceInterface;
+use crate::internal::interfaces::upload_progress_service::UploadProgressServiceInterface;
+use crate::internal::metrics::reconciliation_metrics::{
+    gather_metrics, record_error, RECONCILE_FILE_CHUNK_OPERATION,
+};
+use crate::internal::models::entities::chunk_content_type::ChunkContentType;
+use crate::internal::models::entities::chunk_payload_limits::ChunkPayloadLimits;
+use crate::internal::models::entities::file_chunk_claim_check::FileChunkClaimCheck;
 use crate::internal::models::view_models::requests::reconcile_file_chunk_request::ReconcileFileChunkRequest;
-use crate::internal::shared_reconciler_rust_libraries::models::entities::app_errors::AppErrorKind;
+use crate::internal::models::view_models::responses::health_check_response::LivenessResponse;
+use crate::internal::models::view_models::responses::reconcile_file_chunk_response::ReconcileFileChunkResponse;
+use crate::internal::services::core_logic::chunk_serialization::deserialize_chunk_payload;
+use crate::internal::shared_reconciler_rust_libraries::models::entities::app_errors::{
+    AppError, AppErrorKind,
+};
+use crate::internal::telemetry::trace_context::{
+    set_parent_trace_context, TRACEPARENT_HEADER, TRACESTATE_HEADER,
+};
 use actix_web::{
+    get,
+    http::header,
     post,
     web::{self, Data},
-    HttpResponse,
+    HttpRequest, HttpResponse, HttpResponseBuilder,
 };
+use tracing::{field, Instrument, Span};
 
 #[post("/reconcile-file-chunk")]
 async fn reconcile_file_chunk(
-    task_details: web::Json<ReconcileFileChunkRequest>,
+    http_request: HttpRequest,
+    payload: web::Bytes,
     service: Data<Box<dyn FileChunkReconciliationServiceInterface>>,
+    chunk_payload_limits: Data<ChunkPayloadLimits>,
 ) -> HttpResponse {
-    let recon_task_details = service.reconcile_file_chunk(&task_details.0).await;
+    let request_span = build_request_span(&http_request);
 
-    return match recon_task_details {
-        Ok(details) => HttpResponse::Ok().json(details),
+    //the chunk can be sent as json, messagepack or cbor, going by its content type
+    let content_type = http_request
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|content_type| content_type.to_str().ok())
+        .unwrap_or_default();
+
+    //chunks that were too large to publish come as a claim check for their body
+    if FileChunkClaimCheck::is_claim_check_content_type(content_type) {
+        let claim_check: FileChunkClaimCheck = match serde_json::from_slice(&payload) {
+            Ok(claim_check) => claim_check,
+            Err(err) => {
+                return reject_request(
+                    HttpResponse::BadRequest(),
+                    AppError::new(AppErrorKind::BadClientRequest, err.to_string()),
+                )
+            }
+        };
+
+        request_span.record(
+            "file_chunk_id",
+            field::display(&claim_check.primary_file_chunk_id),
+        );
+
+        let recon_task_details = service
+            .reconcile_claim_checked_file_chunk(claim_check)
+            .instrument(request_span.clone())
+            .await;
+
+        return request_span.in_scope(|| to_http_response(recon_task_details));
+    }
+
+    let chunk_content_type = match ChunkContentType::parse(content_type) {
+        Ok(chunk_content_type) => chunk_content_type,
+        Err(err) => return reject_request(HttpResponse::UnsupportedMediaType(), err),
+    };
+
+    let task_details: ReconcileFileChunkRequest = match deserialize_chunk_payload(
+        &payload,
+        &chunk_content_type,
+        chunk_payload_limits.max_decompressed_bytes,
+    ) {
+        Ok(task_details) => task_details,
+        Err(err) => return reject_request(HttpResponse::BadRequest(), err),
+    };
+
+    request_span.record(
+        "file_chunk_id",
+        field::display(&task_details.primary_file_chunk.id),
+    );
+
+    let recon_task_details = service
+        .reconcile_file_chunk(task_details)
+        .instrument(request_span.clone())
+        .await;
+
+    return request_span.in_scope(|| to_http_response(recon_task_details));
+}
+
+//the processor is alive for as long as it can answer, whatever the state of its dependencies
+#[get("/health/live")]
+async fn get_liveness() -> HttpResponse {
+    return HttpResponse::Ok().json(LivenessResponse { is_alive: true });
+}
+
+//the processor is only sent file chunks while it can reach redis and the dapr sidecar
+#[get("/health/ready")]
+async fn get_readiness(service: Data<Box<dyn HealthCheckServiceInterface>>) -> HttpResponse {
+    let readiness = service.check_readiness().await;
+
+    return match readiness.is_ready {
+        true => HttpResponse::Ok().json(readiness),
+        false => HttpResponse::ServiceUnavailable().json(readiness),
+    };
+}
+
+//how far reconciliation of the primary file chunks of an upload request has got
+#[get("/uploads/{upload_request_id}/progress")]
+async fn get_upload_progress(
+    upload_request_id: web::Path<String>,
+    service: Data<Box<dyn UploadProgressServiceInterface>>,
+) -> HttpResponse {
+    let upload_progress = service
+        .get_upload_progress(upload_request_id.into_inner())
+        .await;
+
+    return match upload_progress {
+        Ok(upload_progress) => HttpResponse::Ok().json(upload_progress),
 
         Err(err) => match err.kind {
-            AppErrorKind::BadClientRequest => HttpResponse::BadRequest().json(format!("{}", err)),
+            AppErrorKind::NotFound => HttpResponse::NotFound().json(format!("{}", err)),
             _ => HttpResponse::InternalServerError().json(format!("{}", err)),
         },
     };
 }
+
+//exposes the reconciliation metrics for prometheus to scrape
+#[get("/metrics")]
+async fn get_metrics() -> HttpResponse {
+    return match gather_metrics() {
+        Ok(metrics) => HttpResponse::Ok()
+            .content_type(prometheus::TEXT_FORMAT)
+            .body(metrics),
+        Err(err) => HttpResponse::InternalServerError().json(format!("{}", err)),
+    };
+}
+
+//the span a file chunk is reconciled in, continuing the trace it was published in.
+//the upload request and sequence numbers are recorded once the chunk has been read
+fn build_request_span(http_request: &HttpRequest) -> Span {
+    let request_span = tracing::info_span!(
+        "reconcile_file_chunk",
+        file_chunk_id = field::Empty,
+        upload_request_id = field::Empty,
+        chunk_sequence_number = field::Empty,
+        comparison_chunk_sequence_number = field::Empty,
+    );
+
+    let get_header = |header_name: &str| {
+        http_request
+            .headers()
+            .get(header_name)
+            .and_then(|header_value| header_value.to_str().ok())
+    };
+
+    set_parent_trace_context(
+        &request_span,
+        get_header(TRACEPARENT_HEADER),
+        get_header(TRACESTATE_HEADER),
+    );
+
+    return request_span;
+}
+
+//a chunk that cannot be read never reaches the service, but is counted
+//against the same operation as one that fails to reconcile
+fn reject_request(mut response: HttpResponseBuilder, err: AppError) -> HttpResponse {
+    record_error(RECONCILE_FILE_CHUNK_OPERATION, &err);
+
+    return response.json(format!("{}", err));
+}
+
+fn to_http_response(
+    recon_task_details: Result<ReconcileFileChunkResponse, AppError>,
+) -> HttpResponse {
+    return match recon_task_details {
+        Ok(details) => HttpResponse::Ok().json(details),
+
+        Err(err) => {
+            record_error(RECONCILE_FILE_CHUNK_OPERATION, &err);
+            tracing::error!(error_kind = ?err.kind, error = %err, "failed to reconcile file chunk");
+
+            match err.kind {
+                AppErrorKind::BadClientRequest => {
+                    HttpResponse::BadRequest().json(format!("{}", err))
+                }
+                _ => HttpResponse::InternalServerError().json(format!("{}", err)),
+            }
+        }
+    };
+}
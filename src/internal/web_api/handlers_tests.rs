@@ -1,24 +1,47 @@
+use std::collections::HashMap;
+
 use actix_web::{
+    http::{header, StatusCode},
     test::{self, TestRequest},
     web::Data,
     App,
 };
 
 use crate::internal::{
-    interfaces::file_chunk_reconciliation_service::{
-        FileChunkReconciliationServiceInterface, MockFileChunkReconciliationServiceInterface,
+    interfaces::{
+        file_chunk_reconciliation_service::{
+            FileChunkReconciliationServiceInterface, MockFileChunkReconciliationServiceInterface,
+        },
+        health_check_service::{HealthCheckServiceInterface, MockHealthCheckServiceInterface},
+        upload_progress_service::{
+            MockUploadProgressServiceInterface, UploadProgressServiceInterface,
+        },
+    },
+    metrics::reconciliation_metrics::{ERRORS, RECONCILE_FILE_CHUNK_OPERATION},
+    models::entities::{
+        chunk_content_type::{ChunkContentType, ChunkEncoding},
+        chunk_payload_limits::ChunkPayloadLimits,
+        file_chunk_claim_check::{FileChunkClaimCheck, FILE_CHUNK_CLAIM_CHECK_CONTENT_TYPE},
+        reconciliation_options::ReconciliationOptions,
     },
     models::view_models::{
         requests::reconcile_file_chunk_request::ReconcileFileChunkRequest,
-        responses::reconcile_file_chunk_response::ReconcileFileChunkResponse,
+        responses::{
+            health_check_response::{DependencyStatus, ReadinessResponse},
+            reconcile_file_chunk_response::ReconcileFileChunkResponse,
+            upload_progress_response::UploadProgressResponse,
+        },
     },
+    services::core_logic::chunk_serialization::serialize_chunk_payload,
     shared_reconciler_rust_libraries::models::entities::{
         app_errors::{AppError, AppErrorKind},
         file_chunk_queue::FileChunkQueue,
         file_upload_chunk::{FileUploadChunk, FileUploadChunkSource},
         recon_tasks_models::ReconciliationConfigs,
     },
-    web_api::handlers::reconcile_file_chunk,
+    web_api::handlers::{
+        get_liveness, get_metrics, get_readiness, get_upload_progress, reconcile_file_chunk,
+    },
 };
 
 #[actix_web::test]
@@ -37,6 +60,7 @@ async fn test_reconcile_file_chunk_calls_correct_dependecies_and_returns_success
 
         App::new()
             .app_data(Data::new(service)) // add shared state
+            .app_data(Data::new(get_chunk_payload_limits()))
             .service(reconcile_file_chunk)
     })())
     .await;
@@ -69,6 +93,7 @@ async fn test_upload_file_chunk_when_invalid_request_returns_bad_request() {
 
         App::new()
             .app_data(Data::new(service)) // add shared state
+            .app_data(Data::new(get_chunk_payload_limits()))
             .service(reconcile_file_chunk)
     })())
     .await;
@@ -101,6 +126,7 @@ async fn test_upload_file_chunk_when_service_returns_error_returns_internal_erro
 
         App::new()
             .app_data(Data::new(service)) // add shared state
+            .app_data(Data::new(get_chunk_payload_limits()))
             .service(reconcile_file_chunk)
     })())
     .await;
@@ -116,6 +142,389 @@ async fn test_upload_file_chunk_when_service_returns_error_returns_internal_erro
     assert!(resp.status().is_server_error());
 }
 
+#[actix_web::test]
+async fn test_reconcile_file_chunk_given_compressed_message_pack_chunk_passes_it_to_the_service() {
+    let mut app = test::init_service((move || {
+        // Create some global state prior to running the handler thread
+        let mut mock_service = Box::new(MockFileChunkReconciliationServiceInterface::new());
+
+        mock_service
+            .expect_reconcile_file_chunk()
+            .withf(|request| request.primary_file_chunk.id == "TEST-UPLOAD-1")
+            .times(1)
+            .returning(|_y| {
+                Ok(ReconcileFileChunkResponse {
+                    file_chunk_id: String::from("FILE-CHUNK-1"),
+                })
+            });
+
+        let service: Box<dyn FileChunkReconciliationServiceInterface> = mock_service;
+
+        App::new()
+            .app_data(Data::new(service)) // add shared state
+            .app_data(Data::new(get_chunk_payload_limits()))
+            .service(reconcile_file_chunk)
+    })())
+    .await;
+
+    let chunk_content_type = ChunkContentType {
+        encoding: ChunkEncoding::MessagePack,
+        is_zstd_compressed: true,
+    };
+    let payload = serialize_chunk_payload(&get_dummy_request(), &chunk_content_type).unwrap();
+
+    let resp = TestRequest::post()
+        .uri(&format!("/reconcile-file-chunk"))
+        .insert_header((header::CONTENT_TYPE, "application/msgpack+zstd"))
+        .set_payload(payload)
+        .send_request(&mut app)
+        .await;
+
+    assert!(resp.status().is_success());
+}
+
+#[actix_web::test]
+async fn test_reconcile_file_chunk_given_cloudevents_json_chunk_passes_it_to_the_service() {
+    let mut app = test::init_service((move || {
+        // Create some global state prior to running the handler thread
+        let mut mock_service = Box::new(MockFileChunkReconciliationServiceInterface::new());
+
+        mock_service
+            .expect_reconcile_file_chunk()
+            .withf(|request| request.primary_file_chunk.id == "TEST-UPLOAD-1")
+            .times(1)
+            .returning(|_y| {
+                Ok(ReconcileFileChunkResponse {
+                    file_chunk_id: String::from("FILE-CHUNK-1"),
+                })
+            });
+
+        let service: Box<dyn FileChunkReconciliationServiceInterface> = mock_service;
+
+        App::new()
+            .app_data(Data::new(service)) // add shared state
+            .app_data(Data::new(get_chunk_payload_limits()))
+            .service(reconcile_file_chunk)
+    })())
+    .await;
+
+    let payload = serde_json::to_vec(&get_dummy_request()).unwrap();
+
+    let resp = TestRequest::post()
+        .uri(&format!("/reconcile-file-chunk"))
+        .insert_header((header::CONTENT_TYPE, "application/cloudevents+json"))
+        .set_payload(payload)
+        .send_request(&mut app)
+        .await;
+
+    assert!(resp.status().is_success());
+}
+
+#[actix_web::test]
+async fn test_reconcile_file_chunk_given_unsupported_content_type_returns_unsupported_media_type() {
+    let mut app = test::init_service((move || {
+        // Create some global state prior to running the handler thread
+        let mut mock_service = Box::new(MockFileChunkReconciliationServiceInterface::new());
+
+        mock_service.expect_reconcile_file_chunk().times(0);
+
+        let service: Box<dyn FileChunkReconciliationServiceInterface> = mock_service;
+
+        App::new()
+            .app_data(Data::new(service)) // add shared state
+            .app_data(Data::new(get_chunk_payload_limits()))
+            .service(reconcile_file_chunk)
+    })())
+    .await;
+
+    let errors_before = get_bad_client_request_errors();
+
+    let resp = TestRequest::post()
+        .uri(&format!("/reconcile-file-chunk"))
+        .insert_header((header::CONTENT_TYPE, "text/csv"))
+        .set_payload("TEST-UPLOAD-1,1")
+        .send_request(&mut app)
+        .await;
+
+    assert_eq!(resp.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    assert!(get_bad_client_request_errors() > errors_before);
+}
+
+#[actix_web::test]
+async fn test_reconcile_file_chunk_given_claim_check_reconciles_the_claim_checked_chunk() {
+    let mut app = test::init_service((move || {
+        // Create some global state prior to running the handler thread
+        let mut mock_service = Box::new(MockFileChunkReconciliationServiceInterface::new());
+
+        mock_service.expect_reconcile_file_chunk().times(0);
+
+        mock_service
+            .expect_reconcile_claim_checked_file_chunk()
+            .withf(|claim_check| claim_check.claim_check_key == "file-chunk-claim-checks:1")
+            .times(1)
+            .returning(|_y| {
+                Ok(ReconcileFileChunkResponse {
+                    file_chunk_id: String::from("FILE-CHUNK-1"),
+                })
+            });
+
+        let service: Box<dyn FileChunkReconciliationServiceInterface> = mock_service;
+
+        App::new()
+            .app_data(Data::new(service)) // add shared state
+            .app_data(Data::new(get_chunk_payload_limits()))
+            .service(reconcile_file_chunk)
+    })())
+    .await;
+
+    let claim_check = FileChunkClaimCheck {
+        claim_check_key: String::from("file-chunk-claim-checks:1"),
+        content_type: String::from("application/msgpack+zstd"),
+        primary_file_chunk_id: String::from("TEST-UPLOAD-1"),
+    };
+
+    let resp = TestRequest::post()
+        .uri(&format!("/reconcile-file-chunk"))
+        .insert_header((header::CONTENT_TYPE, FILE_CHUNK_CLAIM_CHECK_CONTENT_TYPE))
+        .set_payload(serde_json::to_vec(&claim_check).unwrap())
+        .send_request(&mut app)
+        .await;
+
+    assert!(resp.status().is_success());
+}
+
+#[actix_web::test]
+async fn test_reconcile_file_chunk_given_chunk_over_the_decompressed_limit_returns_bad_request() {
+    let mut app = test::init_service((move || {
+        // Create some global state prior to running the handler thread
+        let mut mock_service = Box::new(MockFileChunkReconciliationServiceInterface::new());
+
+        mock_service.expect_reconcile_file_chunk().times(0);
+
+        let service: Box<dyn FileChunkReconciliationServiceInterface> = mock_service;
+
+        App::new()
+            .app_data(Data::new(service)) // add shared state
+            .app_data(Data::new(ChunkPayloadLimits {
+                max_decompressed_bytes: 1_024,
+            }))
+            .service(reconcile_file_chunk)
+    })())
+    .await;
+
+    //a small body that decompresses to far more than the limit
+    let payload = zstd::stream::encode_all(vec![b' '; 65_536].as_slice(), 3).unwrap();
+    let errors_before = get_bad_client_request_errors();
+
+    let resp = TestRequest::post()
+        .uri(&format!("/reconcile-file-chunk"))
+        .insert_header((header::CONTENT_TYPE, "application/json+zstd"))
+        .set_payload(payload)
+        .send_request(&mut app)
+        .await;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    assert!(get_bad_client_request_errors() > errors_before);
+}
+
+#[actix_web::test]
+async fn test_reconcile_file_chunk_given_unreadable_claim_check_returns_bad_request() {
+    let mut app = test::init_service((move || {
+        // Create some global state prior to running the handler thread
+        let mut mock_service = Box::new(MockFileChunkReconciliationServiceInterface::new());
+
+        mock_service
+            .expect_reconcile_claim_checked_file_chunk()
+            .times(0);
+
+        let service: Box<dyn FileChunkReconciliationServiceInterface> = mock_service;
+
+        App::new()
+            .app_data(Data::new(service)) // add shared state
+            .app_data(Data::new(get_chunk_payload_limits()))
+            .service(reconcile_file_chunk)
+    })())
+    .await;
+
+    let errors_before = get_bad_client_request_errors();
+
+    let resp = TestRequest::post()
+        .uri(&format!("/reconcile-file-chunk"))
+        .insert_header((header::CONTENT_TYPE, FILE_CHUNK_CLAIM_CHECK_CONTENT_TYPE))
+        .set_payload("not a claim check")
+        .send_request(&mut app)
+        .await;
+
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    assert!(get_bad_client_request_errors() > errors_before);
+}
+
+#[actix_web::test]
+async fn test_get_liveness_returns_success() {
+    let mut app = test::init_service(App::new().service(get_liveness)).await;
+
+    let resp = TestRequest::get()
+        .uri(&format!("/health/live"))
+        .send_request(&mut app)
+        .await;
+
+    assert!(resp.status().is_success());
+}
+
+#[actix_web::test]
+async fn test_get_metrics_returns_prometheus_text_format() {
+    let mut app = test::init_service(App::new().service(get_metrics)).await;
+
+    let resp = TestRequest::get()
+        .uri(&format!("/metrics"))
+        .send_request(&mut app)
+        .await;
+
+    assert!(resp.status().is_success());
+    assert_eq!(
+        resp.headers().get(header::CONTENT_TYPE).unwrap(),
+        prometheus::TEXT_FORMAT
+    );
+}
+
+#[actix_web::test]
+async fn test_get_readiness_when_dependencies_are_ready_returns_success() {
+    let mut app = test::init_service(
+        App::new()
+            .app_data(Data::new(setup_health_check_service(true))) // add shared state
+            .service(get_readiness),
+    )
+    .await;
+
+    let resp = TestRequest::get()
+        .uri(&format!("/health/ready"))
+        .send_request(&mut app)
+        .await;
+
+    assert!(resp.status().is_success());
+}
+
+#[actix_web::test]
+async fn test_get_readiness_when_a_dependency_is_not_ready_returns_service_unavailable() {
+    let mut app = test::init_service(
+        App::new()
+            .app_data(Data::new(setup_health_check_service(false))) // add shared state
+            .service(get_readiness),
+    )
+    .await;
+
+    let resp = TestRequest::get()
+        .uri(&format!("/health/ready"))
+        .send_request(&mut app)
+        .await;
+
+    assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+
+    let readiness: ReadinessResponse = test::read_body_json(resp).await;
+    assert_eq!(readiness.dependencies[0].name, "redis");
+    assert_eq!(readiness.dependencies[0].is_ready, false);
+}
+
+#[actix_web::test]
+async fn test_get_upload_progress_returns_the_progress_of_the_upload_request() {
+    let mut app = test::init_service(
+        App::new()
+            .app_data(Data::new(setup_upload_progress_service(Ok(
+                UploadProgressResponse {
+                    upload_request_id: String::from("TEST-UPLOAD-1"),
+                    primary_file_chunks_seen: 2,
+                    primary_file_chunks_completed: 1,
+                    primary_file_chunks_total: None,
+                    is_completed: false,
+                    comparison_file_chunks_consumed: 5,
+                    rows_by_recon_status: HashMap::from([(String::from("Successful"), 100)]),
+                },
+            )))) // add shared state
+            .service(get_upload_progress),
+    )
+    .await;
+
+    let resp = TestRequest::get()
+        .uri(&format!("/uploads/TEST-UPLOAD-1/progress"))
+        .send_request(&mut app)
+        .await;
+
+    assert!(resp.status().is_success());
+
+    let upload_progress: UploadProgressResponse = test::read_body_json(resp).await;
+    assert_eq!(upload_progress.primary_file_chunks_completed, 1);
+}
+
+#[actix_web::test]
+async fn test_get_upload_progress_of_unknown_upload_request_returns_not_found() {
+    let mut app = test::init_service(
+        App::new()
+            .app_data(Data::new(setup_upload_progress_service(Err(
+                AppError::new(
+                    AppErrorKind::NotFound,
+                    String::from(
+                        "no progress has been recorded for upload request [TEST-UPLOAD-1]",
+                    ),
+                ),
+            )))) // add shared state
+            .service(get_upload_progress),
+    )
+    .await;
+
+    let resp = TestRequest::get()
+        .uri(&format!("/uploads/TEST-UPLOAD-1/progress"))
+        .send_request(&mut app)
+        .await;
+
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+}
+
+fn setup_upload_progress_service(
+    upload_progress: Result<UploadProgressResponse, AppError>,
+) -> Box<dyn UploadProgressServiceInterface> {
+    let mut mock_service = Box::new(MockUploadProgressServiceInterface::new());
+
+    mock_service
+        .expect_get_upload_progress()
+        .withf(|upload_request_id| upload_request_id == "TEST-UPLOAD-1")
+        .times(1)
+        .return_once(move |_x| upload_progress);
+
+    return mock_service;
+}
+
+fn setup_health_check_service(is_redis_ready: bool) -> Box<dyn HealthCheckServiceInterface> {
+    let mut mock_service = Box::new(MockHealthCheckServiceInterface::new());
+
+    mock_service
+        .expect_check_readiness()
+        .times(1)
+        .returning(move || ReadinessResponse {
+            is_ready: is_redis_ready,
+            dependencies: vec![DependencyStatus {
+                name: String::from("redis"),
+                is_ready: is_redis_ready,
+                details: String::from("redis replied [PONG]"),
+                response_time_in_milliseconds: 1,
+            }],
+        });
+
+    return mock_service;
+}
+
+//other tests reject requests at the same time, so the errors are only checked for going up
+fn get_bad_client_request_errors() -> u64 {
+    ERRORS
+        .with_label_values(&[RECONCILE_FILE_CHUNK_OPERATION, "BadClientRequest"])
+        .get()
+}
+
+fn get_chunk_payload_limits() -> ChunkPayloadLimits {
+    ChunkPayloadLimits {
+        max_decompressed_bytes: 67_108_864,
+    }
+}
+
 fn get_dummy_request() -> ReconcileFileChunkRequest {
     ReconcileFileChunkRequest {
         primary_file_chunk: FileUploadChunk {
@@ -148,5 +557,7 @@ fn get_dummy_request() -> ReconcileFileChunkRequest {
             },
             is_last_chunk: false,
         },
+        reconciliation_options: ReconciliationOptions::default(),
+        rows_reconciliation_details: vec![],
     }
 }
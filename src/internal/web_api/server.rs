@@ -1,20 +1,48 @@
 use crate::external::pubsub::dapr_pubsub::DaprPubSubRepositoryManager;
+use crate::internal::models::entities::chunk_content_type::ChunkContentType;
+use crate::internal::models::entities::chunk_payload_limits::ChunkPayloadLimits;
 use crate::internal::services::core_logic::generic_file_reconciliation_algorithm::GenericFileReconciliationAlgorithm;
+use crate::internal::services::core_logic::match_rule_file_reconciliation_algorithm::MatchRuleFileReconciliationAlgorithm;
+use crate::internal::services::core_logic::multi_pass_file_reconciliation_algorithm::MultiPassFileReconciliationAlgorithm;
+use crate::internal::telemetry::tracing_setup::{init_tracing, shutdown_tracing};
 
 use crate::internal::web_api::handlers;
 use crate::internal::{
-    interfaces::file_chunk_reconciliation_service::FileChunkReconciliationServiceInterface,
-    services::file_chunk_reconciliation_service::FileChunkReconciliationService,
+    interfaces::{
+        file_chunk_reconciliation_service::FileChunkReconciliationServiceInterface,
+        health_check_service::HealthCheckServiceInterface,
+        upload_progress_service::UploadProgressServiceInterface,
+    },
+    services::{
+        file_chunk_reconciliation_service::FileChunkReconciliationService,
+        health_check_service::HealthCheckService, upload_progress_service::UploadProgressService,
+    },
 };
-use actix_web::{web::Data, App, HttpServer};
+use actix_web::{
+    web::{Data, PayloadConfig},
+    App, HttpServer,
+};
+use std::time::Duration;
 
 // constants
 const DEFAULT_DAPR_CONNECTION_URL: &'static str = "http://localhost:5005";
-const DEFAULT_REDIS_CONNECTION_URL: &'static str = "http://localhost:5005";
+const DEFAULT_REDIS_CONNECTION_URL: &'static str = "redis://127.0.0.1:6379/";
 const DEFAULT_DAPR_PUBSUB_NAME: &'static str = "FileChunksQueue";
 const DEFAULT_DAPR_PUBSUB_TOPIC: &'static str = "FileChunks";
+const DEFAULT_DAPR_UPLOAD_RECONCILIATION_COMPLETED_TOPIC: &'static str =
+    "UploadReconciliationCompleted";
 const DEFAULT_APP_LISTEN_IP: &'static str = "0.0.0.0";
 const DEFAULT_APP_LISTEN_PORT: u16 = 8080;
+const DEFAULT_PRIMARY_FILE_CHUNK_CONTENT_TYPE: &'static str = "application/json";
+const DEFAULT_MAX_FILE_CHUNK_PAYLOAD_BYTES: usize = 2_097_152;
+const DEFAULT_MAX_DECOMPRESSED_FILE_CHUNK_PAYLOAD_BYTES: usize = 67_108_864;
+const DEFAULT_MAX_PUBLISHED_FILE_CHUNK_BYTES: usize = 1_048_576;
+const DEFAULT_FILE_CHUNK_CLAIM_CHECK_TTL_SECONDS: usize = 86_400;
+const DEFAULT_DEPENDENCY_CHECK_TIMEOUT_MILLISECONDS: u64 = 2_000;
+const DEFAULT_UPLOAD_PROGRESS_TTL_SECONDS: usize = 604_800;
+const DEFAULT_UPLOAD_COMPLETION_CLAIM_TTL_SECONDS: usize = 300;
+const DEFAULT_FIRST_CHUNK_SEQUENCE_NUMBER: i64 = 1;
+const DEFAULT_OTEL_SERVICE_NAME: &'static str = "recon-file-chunk-processor";
 
 #[derive(Clone, Debug)]
 struct AppSettings {
@@ -26,36 +54,112 @@ struct AppSettings {
 
     pub dapr_pubsub_topic: String,
 
+    //where an event is published once every primary file chunk of an upload is reconciled
+    pub dapr_upload_reconciliation_completed_topic: String,
+
     pub dapr_grpc_server_ip_address: String,
 
     pub redis_url: String,
+
+    //the size of the thread pool rows are reconciled on
+    pub row_reconciliation_threads: usize,
+
+    //the content type primary file chunks are requeued with e.g application/msgpack+zstd
+    pub primary_file_chunk_content_type: ChunkContentType,
+
+    //the largest file chunk request body accepted, in whichever encoding it comes
+    pub max_file_chunk_payload_bytes: usize,
+
+    //the largest a compressed file chunk payload may get once decompressed
+    pub max_decompressed_file_chunk_payload_bytes: usize,
+
+    //requeued and reconciled chunks larger than this are published as a claim check
+    pub max_published_file_chunk_bytes: usize,
+
+    pub file_chunk_claim_check_ttl_seconds: usize,
+
+    //how long redis and dapr have to answer a readiness check
+    pub dependency_check_timeout_milliseconds: u64,
+
+    //how long the progress of an upload request is kept after it was last updated
+    pub upload_progress_ttl_seconds: usize,
+
+    //how long a claim on publishing the completion event of an upload request
+    //lasts before the event has been published
+    pub upload_completion_claim_ttl_seconds: usize,
+
+    //the sequence number the file splitter gives the first chunk of a file
+    pub first_chunk_sequence_number: i64,
+
+    //the name spans are exported under
+    pub otel_service_name: String,
+
+    //spans are only exported when an otlp collector is given e.g http://localhost:4317
+    pub otel_exporter_otlp_endpoint: Option<String>,
 }
 
 pub async fn run_async() -> Result<(), std::io::Error> {
     //retrieve app settings from the env variables
-    let app_settings = read_app_settings();
+    let app_settings = read_app_settings()?;
+
+    init_tracing(
+        &app_settings.otel_service_name,
+        app_settings.otel_exporter_otlp_endpoint.as_deref(),
+    )
+    .map_err(|e| std::io::Error::other(e.to_string()))?;
 
     let app_listen_url = format!("{}:{}", app_settings.app_ip, app_settings.app_port);
 
-    //just for logging purposes
-    println!("App is listening on: {:?}", app_listen_url);
+    //rows of every chunk share one bounded thread pool,
+    //however many requests are being handled at once
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(app_settings.row_reconciliation_threads)
+        .thread_name(|index| format!("row-reconciliation-{}", index))
+        .build_global()
+        .map_err(std::io::Error::other)?;
 
-    HttpServer::new(move || {
+    tracing::info!(app_listen_url = %app_listen_url, "App is listening");
+
+    let server_result = HttpServer::new(move || {
         // Create some global state prior to running the handler threads
         let service = setup_service(app_settings.clone());
+        let health_check_service = setup_health_check_service(app_settings.clone());
+        let upload_progress_service = setup_upload_progress_service(app_settings.clone());
 
         // add shared state and routing
         App::new()
             .app_data(Data::new(service))
+            .app_data(Data::new(health_check_service))
+            .app_data(Data::new(upload_progress_service))
+            .app_data(Data::new(get_chunk_payload_limits(&app_settings)))
+            .app_data(PayloadConfig::new(
+                app_settings.max_file_chunk_payload_bytes,
+            ))
             .service(handlers::reconcile_file_chunk)
+            .service(handlers::get_liveness)
+            .service(handlers::get_readiness)
+            .service(handlers::get_upload_progress)
+            .service(handlers::get_metrics)
     })
     .bind(app_listen_url)?
     .run()
-    .await
+    .await;
+
+    //the spans of the last chunks are exported before the processor exits
+    shutdown_tracing();
+
+    return server_result;
 }
 
-fn read_app_settings() -> AppSettings {
-    AppSettings {
+fn read_app_settings() -> Result<AppSettings, std::io::Error> {
+    //a misspelt content type would otherwise leave chunks unreadable once requeued
+    let primary_file_chunk_content_type = ChunkContentType::parse(
+        &std::env::var("PRIMARY_FILE_CHUNK_CONTENT_TYPE")
+            .unwrap_or(DEFAULT_PRIMARY_FILE_CHUNK_CONTENT_TYPE.to_string()),
+    )
+    .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e.to_string()))?;
+
+    Ok(AppSettings {
         app_port: std::env::var("APP_PORT").unwrap_or(DEFAULT_APP_LISTEN_PORT.to_string()),
 
         app_ip: std::env::var("APP_IP").unwrap_or(DEFAULT_APP_LISTEN_IP.to_string()),
@@ -66,23 +170,135 @@ fn read_app_settings() -> AppSettings {
         dapr_pubsub_topic: std::env::var("DAPR_PUBSUB_TOPIC")
             .unwrap_or(DEFAULT_DAPR_PUBSUB_TOPIC.to_string()),
 
+        dapr_upload_reconciliation_completed_topic: std::env::var(
+            "DAPR_UPLOAD_RECONCILIATION_COMPLETED_TOPIC",
+        )
+        .unwrap_or(DEFAULT_DAPR_UPLOAD_RECONCILIATION_COMPLETED_TOPIC.to_string()),
+
         dapr_grpc_server_ip_address: std::env::var("DAPR_IP")
             .unwrap_or(DEFAULT_DAPR_CONNECTION_URL.to_string()),
 
         redis_url: std::env::var("REDIS_URL").unwrap_or(DEFAULT_REDIS_CONNECTION_URL.to_string()),
-    }
+
+        //defaults to one thread per cpu
+        row_reconciliation_threads: std::env::var("ROW_RECONCILIATION_THREADS")
+            .ok()
+            .and_then(|threads| threads.parse().ok())
+            .unwrap_or(0),
+
+        primary_file_chunk_content_type,
+
+        max_file_chunk_payload_bytes: std::env::var("MAX_FILE_CHUNK_PAYLOAD_BYTES")
+            .ok()
+            .and_then(|max_bytes| max_bytes.parse().ok())
+            .unwrap_or(DEFAULT_MAX_FILE_CHUNK_PAYLOAD_BYTES),
+
+        max_decompressed_file_chunk_payload_bytes: std::env::var(
+            "MAX_DECOMPRESSED_FILE_CHUNK_PAYLOAD_BYTES",
+        )
+        .ok()
+        .and_then(|max_bytes| max_bytes.parse().ok())
+        .unwrap_or(DEFAULT_MAX_DECOMPRESSED_FILE_CHUNK_PAYLOAD_BYTES),
+
+        max_published_file_chunk_bytes: std::env::var("MAX_PUBLISHED_FILE_CHUNK_BYTES")
+            .ok()
+            .and_then(|max_bytes| max_bytes.parse().ok())
+            .unwrap_or(DEFAULT_MAX_PUBLISHED_FILE_CHUNK_BYTES),
+
+        file_chunk_claim_check_ttl_seconds: std::env::var("FILE_CHUNK_CLAIM_CHECK_TTL_SECONDS")
+            .ok()
+            .and_then(|ttl_seconds| ttl_seconds.parse().ok())
+            .unwrap_or(DEFAULT_FILE_CHUNK_CLAIM_CHECK_TTL_SECONDS),
+
+        dependency_check_timeout_milliseconds: std::env::var(
+            "DEPENDENCY_CHECK_TIMEOUT_MILLISECONDS",
+        )
+        .ok()
+        .and_then(|timeout| timeout.parse().ok())
+        .unwrap_or(DEFAULT_DEPENDENCY_CHECK_TIMEOUT_MILLISECONDS),
+
+        upload_progress_ttl_seconds: std::env::var("UPLOAD_PROGRESS_TTL_SECONDS")
+            .ok()
+            .and_then(|ttl_seconds| ttl_seconds.parse().ok())
+            .unwrap_or(DEFAULT_UPLOAD_PROGRESS_TTL_SECONDS),
+
+        upload_completion_claim_ttl_seconds: std::env::var("UPLOAD_COMPLETION_CLAIM_TTL_SECONDS")
+            .ok()
+            .and_then(|ttl_seconds| ttl_seconds.parse().ok())
+            .unwrap_or(DEFAULT_UPLOAD_COMPLETION_CLAIM_TTL_SECONDS),
+
+        first_chunk_sequence_number: std::env::var("FIRST_CHUNK_SEQUENCE_NUMBER")
+            .ok()
+            .and_then(|sequence_number| sequence_number.parse().ok())
+            .unwrap_or(DEFAULT_FIRST_CHUNK_SEQUENCE_NUMBER),
+
+        otel_service_name: std::env::var("OTEL_SERVICE_NAME")
+            .unwrap_or(DEFAULT_OTEL_SERVICE_NAME.to_string()),
+
+        otel_exporter_otlp_endpoint: std::env::var("OTEL_EXPORTER_OTLP_ENDPOINT")
+            .ok()
+            .filter(|endpoint| !endpoint.trim().is_empty()),
+    })
 }
 
 fn setup_service(app_settings: AppSettings) -> Box<dyn FileChunkReconciliationServiceInterface> {
     let service: Box<dyn FileChunkReconciliationServiceInterface> =
         Box::new(FileChunkReconciliationService {
-            pubsub_repo: Box::new(DaprPubSubRepositoryManager {
-                dapr_grpc_server_address: app_settings.dapr_grpc_server_ip_address.clone(),
-                dapr_pubsub_name: app_settings.dapr_pubsub_name.clone(),
-                dapr_pubsub_topic: app_settings.dapr_pubsub_topic.clone(),
-                redis_url: app_settings.redis_url.clone(),
-            }),
+            pubsub_repo: Box::new(setup_pubsub_repo(&app_settings)),
             file_reconciliation_algorithm: Box::new(GenericFileReconciliationAlgorithm {}),
+            match_rule_reconciliation_algorithm: Box::new(MatchRuleFileReconciliationAlgorithm {}),
+            multi_pass_reconciliation_algorithm: Box::new(MultiPassFileReconciliationAlgorithm {
+                file_reconciliation_algorithm: Box::new(GenericFileReconciliationAlgorithm {}),
+                match_rule_reconciliation_algorithm: Box::new(
+                    MatchRuleFileReconciliationAlgorithm {},
+                ),
+            }),
+            upload_progress_repo: Box::new(setup_pubsub_repo(&app_settings)),
+            chunk_payload_limits: get_chunk_payload_limits(&app_settings),
         });
     service
 }
+
+fn setup_pubsub_repo(app_settings: &AppSettings) -> DaprPubSubRepositoryManager {
+    DaprPubSubRepositoryManager {
+        dapr_grpc_server_address: app_settings.dapr_grpc_server_ip_address.clone(),
+        dapr_pubsub_name: app_settings.dapr_pubsub_name.clone(),
+        dapr_pubsub_topic: app_settings.dapr_pubsub_topic.clone(),
+        dapr_upload_reconciliation_completed_topic: app_settings
+            .dapr_upload_reconciliation_completed_topic
+            .clone(),
+        redis_url: app_settings.redis_url.clone(),
+        primary_file_chunk_content_type: app_settings.primary_file_chunk_content_type,
+        max_published_file_chunk_bytes: app_settings.max_published_file_chunk_bytes,
+        file_chunk_claim_check_ttl_seconds: app_settings.file_chunk_claim_check_ttl_seconds,
+        upload_progress_ttl_seconds: app_settings.upload_progress_ttl_seconds,
+        upload_completion_claim_ttl_seconds: app_settings.upload_completion_claim_ttl_seconds,
+        first_chunk_sequence_number: app_settings.first_chunk_sequence_number,
+        chunk_payload_limits: get_chunk_payload_limits(app_settings),
+    }
+}
+
+fn get_chunk_payload_limits(app_settings: &AppSettings) -> ChunkPayloadLimits {
+    ChunkPayloadLimits {
+        max_decompressed_bytes: app_settings.max_decompressed_file_chunk_payload_bytes,
+    }
+}
+
+fn setup_health_check_service(app_settings: AppSettings) -> Box<dyn HealthCheckServiceInterface> {
+    let service: Box<dyn HealthCheckServiceInterface> = Box::new(HealthCheckService {
+        pubsub_repo: Box::new(setup_pubsub_repo(&app_settings)),
+        dependency_check_timeout: Duration::from_millis(
+            app_settings.dependency_check_timeout_milliseconds,
+        ),
+    });
+    service
+}
+
+fn setup_upload_progress_service(
+    app_settings: AppSettings,
+) -> Box<dyn UploadProgressServiceInterface> {
+    let service: Box<dyn UploadProgressServiceInterface> = Box::new(UploadProgressService {
+        upload_progress_repo: Box::new(setup_pubsub_repo(&app_settings)),
+    });
+    service
+}